@@ -5,4 +5,6 @@ cargo lambda build --release --features aws --bin aws_migrator
 cargo lambda build --release --features aws --bin aws_standard_tx_signer
 cargo lambda build --release --features aws --bin aws_standard_tx_sender
 cargo lambda build --release --features aws --bin aws_receipt_poller
-cargo lambda build --release --features aws --bin aws_retry_handler
\ No newline at end of file
+cargo lambda build --release --features aws --bin aws_retry_handler
+cargo lambda build --release --features aws --bin aws_status_query
+cargo lambda build --release --features aws --bin aws_ingestion_gateway
\ No newline at end of file
@@ -45,7 +45,7 @@ impl S3BlobStorageManager {
         let mut body = resp.body.into_async_read();
         let mut contents = String::new();
         body.read_to_string(&mut contents).await?;
-        let blob_input: BlobInputJsonFile = serde_json::from_str(&contents).unwrap();
+        let blob_input: BlobInputJsonFile = serde_json::from_str(&contents)?;
 
         Ok(blob_input)
     }
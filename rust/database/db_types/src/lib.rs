@@ -1,16 +1,24 @@
 use serde::{Deserialize, Serialize};
 use sqlx::Type;
 
+mod wei;
+
+pub use wei::Wei;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
 #[sqlx(type_name = "text")]
 pub enum TxType {
     STANDARD,
     BLOB,
+    /// Zero-value self-transfer that frees a stuck nonce. Only used by execution attempts.
+    CANCELLATION,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
 #[sqlx(type_name = "text")]
 pub enum TxStatus {
+    /// Accepted by the ingestion gateway and queued for signing.
+    RECEIVED,
     SIGNED,
     LOCKED,
     BROADCASTED,
@@ -26,10 +34,57 @@ pub enum BlobStorageType {
     S3,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
+#[sqlx(type_name = "text", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WalletSelectionStrategy {
+    LeastRecentlyUsed,
+    HighestBalance,
+    WeightedByBalance,
+    StickyPerRequester,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
+#[sqlx(type_name = "text")]
+pub enum NonceReservationStatus {
+    /// Handed out to a sender, not broadcasted yet.
+    RESERVED,
+    BROADCASTED,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionErrorObject {
     pub error_type: String,
     pub error_body: Option<String>,
+    /// Why the target contract reverted, when the failure carried its revert data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<RevertReason>,
+}
+
+/// Revert data of a target call, decoded as far as the known ABIs allow.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind")]
+pub enum RevertReason {
+    /// `Error(string)`, raised by `require` and `revert("...")`.
+    Error { message: String },
+    /// `Panic(uint256)`, raised by failed asserts, overflows and similar.
+    Panic { code: u64, description: String },
+    /// Custom error found in the ABI registry of the batch targets.
+    Custom {
+        name: String,
+        signature: String,
+        args: Vec<RevertArgument>,
+    },
+    /// Revert without data.
+    Empty,
+    /// Revert data no known error matches, as 0x-prefixed hex.
+    Unknown { data: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RevertArgument {
+    pub name: String,
+    pub value: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
@@ -40,4 +95,9 @@ pub enum TxExecutionOutcome {
     SUCCEED,
     FAILED,
     REVERTED,
+    /// Rejected by validation before signing. Only reported as an outcome event.
+    INVALID,
+    /// The tx_id was resubmitted with a different payload. Only reported as an outcome event,
+    /// never stored on execution attempts.
+    CONFLICT,
 }
@@ -0,0 +1,5 @@
+ALTER TABLE networks
+ADD COLUMN max_fee_per_gas_ceiling BIGINT;
+
+ALTER TABLE networks
+ADD COLUMN max_fee_per_blob_gas_ceiling BIGINT;
@@ -0,0 +1,8 @@
+ALTER TABLE operator_wallets
+ADD COLUMN needs_manual_review BOOLEAN NOT NULL DEFAULT false;
+
+DROP INDEX IF EXISTS idx_wallet_free;
+
+CREATE INDEX idx_wallet_free
+    ON operator_wallets (chain_id)
+    WHERE in_use = FALSE AND is_enabled = TRUE AND needs_manual_review = FALSE;
@@ -0,0 +1,23 @@
+ALTER TABLE operator_wallets
+ADD COLUMN lease_owner TEXT,
+ADD COLUMN lease_expires_at TIMESTAMPTZ;
+
+-- Wallets held at migration time get an already expired lease, so the sweeper checks them.
+UPDATE operator_wallets
+SET
+    lease_owner = 'in_use_migration',
+    lease_expires_at = NOW()
+WHERE in_use = true;
+
+DROP INDEX IF EXISTS idx_wallet_free;
+
+ALTER TABLE operator_wallets
+DROP COLUMN in_use;
+
+CREATE INDEX idx_wallet_free
+    ON operator_wallets (chain_id)
+    WHERE lease_owner IS NULL AND is_enabled = TRUE AND needs_manual_review = FALSE;
+
+CREATE INDEX idx_wallet_lease_expires_at
+    ON operator_wallets (lease_expires_at)
+    WHERE lease_owner IS NOT NULL;
@@ -0,0 +1,2 @@
+ALTER TABLE networks
+ADD COLUMN operator_wallet_balance_warning_level BIGINT;
@@ -0,0 +1,7 @@
+ALTER TABLE networks
+ADD COLUMN wallet_selection_strategy TEXT NOT NULL DEFAULT 'LEAST_RECENTLY_USED';
+
+ALTER TABLE operator_wallets
+ADD COLUMN last_leased_at TIMESTAMPTZ,
+ADD COLUMN last_requester_id TEXT,
+ADD COLUMN balance_wei NUMERIC;
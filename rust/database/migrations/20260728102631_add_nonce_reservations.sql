@@ -0,0 +1,16 @@
+ALTER TABLE networks
+ADD COLUMN max_in_flight_txs_per_wallet INTEGER NOT NULL DEFAULT 1;
+
+CREATE TABLE IF NOT EXISTS nonce_reservations (
+    operator_wallet_id UUID NOT NULL REFERENCES operator_wallets(id),
+    nonce BIGINT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (operator_wallet_id, nonce)
+);
+
+CREATE TRIGGER trg_set_updated_at
+BEFORE UPDATE ON nonce_reservations
+FOR EACH ROW
+EXECUTE FUNCTION set_updated_at();
@@ -0,0 +1,4 @@
+-- Hash of the requester-controlled payload, used to tell identical replays of a tx_id
+-- from conflicting resubmissions.
+ALTER TABLE tx_requests
+    ADD COLUMN payload_hash BYTEA;
@@ -0,0 +1,53 @@
+-- Policies the standard signer enforces per requester before signing. NULL limits are not enforced.
+CREATE TABLE IF NOT EXISTS signing_policies (
+    requester_id TEXT PRIMARY KEY,
+    allow_any_target BOOLEAN NOT NULL DEFAULT FALSE,
+    max_value_wei_per_request BIGINT,
+    max_value_wei_per_window BIGINT,
+    value_window_sec BIGINT,
+    max_deadline_horizon_sec BIGINT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_value_window CHECK (
+        (max_value_wei_per_window IS NULL) = (value_window_sec IS NULL)
+    )
+);
+
+CREATE TRIGGER trg_set_updated_at
+BEFORE UPDATE ON signing_policies
+FOR EACH ROW
+EXECUTE FUNCTION set_updated_at();
+
+-- Allowed targets of a requester. A NULL selector allows every function of the target.
+CREATE TABLE IF NOT EXISTS signing_policy_allowed_calls (
+    id UUID PRIMARY KEY,
+    requester_id TEXT NOT NULL REFERENCES signing_policies(requester_id) ON DELETE CASCADE,
+    to_address TEXT NOT NULL,
+    selector TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_signing_policy_allowed_calls_requester
+    ON signing_policy_allowed_calls (requester_id);
+
+-- Audit log of every policy decision, allowed or denied.
+CREATE TABLE IF NOT EXISTS signing_policy_decisions (
+    id UUID PRIMARY KEY,
+    tx_id TEXT NOT NULL,
+    requester_id TEXT NOT NULL,
+    chain_id BIGINT NOT NULL,
+    to_address TEXT NOT NULL,
+    selector TEXT,
+    value_wei BIGINT NOT NULL,
+    deadline_timestamp BIGINT NOT NULL,
+    allowed BOOLEAN NOT NULL,
+    violations TEXT[] NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_signing_policy_decisions_requester_allowed
+    ON signing_policy_decisions (requester_id, created_at)
+    WHERE allowed = TRUE;
+
+CREATE INDEX idx_signing_policy_decisions_tx_id
+    ON signing_policy_decisions (tx_id);
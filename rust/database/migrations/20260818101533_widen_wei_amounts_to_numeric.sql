@@ -0,0 +1,15 @@
+-- Wei amounts are uint256 values. NUMERIC(78, 0) holds every value up to 2^256 - 1.
+-- The check is NOT VALID so rows written before value_wei was unsigned don't block the migration.
+ALTER TABLE standard_tx_inputs
+    ALTER COLUMN value_wei TYPE NUMERIC(78, 0),
+    ADD CONSTRAINT chk_value_wei_non_negative CHECK (value_wei >= 0) NOT VALID;
+
+ALTER TABLE execution_attempts
+    ALTER COLUMN tx_value TYPE NUMERIC(78, 0);
+
+ALTER TABLE signing_policies
+    ALTER COLUMN max_value_wei_per_request TYPE NUMERIC(78, 0),
+    ALTER COLUMN max_value_wei_per_window TYPE NUMERIC(78, 0);
+
+ALTER TABLE signing_policy_decisions
+    ALTER COLUMN value_wei TYPE NUMERIC(78, 0);
@@ -0,0 +1,5 @@
+-- Per-item result decoded from the Executed/BlobSent logs of the attempt's receipt.
+-- Both stay NULL until the receipt poller has seen the receipt.
+ALTER TABLE execution_attempt_items
+    ADD COLUMN IF NOT EXISTS executed BOOLEAN,
+    ADD COLUMN IF NOT EXISTS log_index BIGINT;
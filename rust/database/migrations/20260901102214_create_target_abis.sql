@@ -0,0 +1,15 @@
+-- ABIs of target contracts, used to decode custom errors a target reverts with.
+-- Only the "error" entries of the ABI are read.
+CREATE TABLE IF NOT EXISTS target_abis (
+    chain_id BIGINT NOT NULL,
+    to_address TEXT NOT NULL,
+    abi JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (chain_id, to_address)
+);
+
+CREATE TRIGGER trg_set_updated_at
+BEFORE UPDATE ON target_abis
+FOR EACH ROW
+EXECUTE FUNCTION set_updated_at();
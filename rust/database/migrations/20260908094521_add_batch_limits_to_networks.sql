@@ -0,0 +1,7 @@
+-- Batch packing limits. NULL means no limit.
+-- max_batch_gas_ppm is the share of the block gas limit a single batch may use, in ppm.
+ALTER TABLE networks
+    ADD COLUMN max_batch_items INTEGER CHECK (max_batch_items > 0),
+    ADD COLUMN max_batch_gas_ppm BIGINT CHECK (max_batch_gas_ppm > 0 AND max_batch_gas_ppm <= 1000000),
+    ADD COLUMN max_batch_value_wei NUMERIC(78, 0) CHECK (max_batch_value_wei >= 0),
+    ADD COLUMN max_blobs_per_tx INTEGER CHECK (max_blobs_per_tx > 0);
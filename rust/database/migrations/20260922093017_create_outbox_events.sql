@@ -0,0 +1,17 @@
+-- Events written in the same transaction as the state change they report. A relay publishes
+-- pending rows and sets `delivered_at`, so every event goes out at least once under its `id`.
+CREATE TABLE IF NOT EXISTS outbox_events (
+    id UUID PRIMARY KEY,
+    event_type TEXT NOT NULL,
+    tx_id TEXT,
+    payload JSONB NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    lease_expires_at TIMESTAMPTZ,
+    delivered_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_outbox_events_pending
+    ON outbox_events (created_at)
+    WHERE delivered_at IS NULL;
@@ -0,0 +1,4 @@
+-- Writers without an emitter at hand have the pending events of their requests relayed by tx_id.
+CREATE INDEX IF NOT EXISTS idx_outbox_events_tx_id
+    ON outbox_events (tx_id)
+    WHERE delivered_at IS NULL;
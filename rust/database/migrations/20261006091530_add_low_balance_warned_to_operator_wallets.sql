@@ -0,0 +1,2 @@
+ALTER TABLE operator_wallets
+ADD COLUMN low_balance_warned BOOLEAN NOT NULL DEFAULT false;
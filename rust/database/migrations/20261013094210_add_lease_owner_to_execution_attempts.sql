@@ -0,0 +1,4 @@
+-- Lease owner of the wallet that sent the attempt, so only that lease is released once the
+-- attempt is resolved.
+ALTER TABLE execution_attempts
+ADD COLUMN lease_owner TEXT;
@@ -0,0 +1,12 @@
+-- Events written in one transaction share `created_at`, so relays order them by `seq`.
+-- Events that ran out of attempts or failed for good are parked for manual review
+-- instead of being retried forever.
+ALTER TABLE outbox_events
+    ADD COLUMN seq BIGSERIAL,
+    ADD COLUMN parked_at TIMESTAMPTZ;
+
+DROP INDEX IF EXISTS idx_outbox_events_pending;
+
+CREATE INDEX IF NOT EXISTS idx_outbox_events_pending
+    ON outbox_events (seq)
+    WHERE delivered_at IS NULL AND parked_at IS NULL;
@@ -1,11 +1,13 @@
 use std::collections::HashMap;
 
 use crate::types::{
+    ExecutionAttemptWithLineage, ExecutionAttemptWithLineageRow,
     ExecutionAttemptWithTxInputRequestRow, ExecutionAttemptWithTxInputs, ExecutionAttemptWithTxRow,
     ExecutionAttemptWithTxs, OutcomePropagationInput,
 };
 use db_types::{BlobStorageType, TxExecutionOutcome};
-use db_types::{TxStatus, TxType};
+use db_types::{TxStatus, TxType, Wei};
+use outbox_db::outbox_events::OutboxEventRepo;
 use serde::{Deserialize, Serialize};
 use sqlx::{
     PgPool,
@@ -19,7 +21,7 @@ pub struct ExecutionAttempt {
     pub chain_id: i64,
     pub operator_wallet_id: Uuid,
     pub nonce_used: Option<i64>,
-    pub tx_value: i64,
+    pub tx_value: Wei,
     pub tx_type: TxType,
     pub tx_hash: Option<String>,
     pub gas_limit: Option<i64>,
@@ -37,7 +39,7 @@ pub struct NewExecutionAttempt {
     pub chain_id: i64,
     pub operator_wallet_id: Uuid,
     pub nonce_used: Option<i64>,
-    pub tx_value: i64,
+    pub tx_value: Wei,
     pub tx_type: TxType,
     pub tx_hash: Option<String>,
     pub gas_limit: Option<i64>,
@@ -49,10 +51,12 @@ pub struct NewExecutionAttempt {
     pub error_object: Option<String>,
     pub retryable: Option<bool>,
     pub source_execution_attempt_id: Option<Uuid>,
+    /// Lease owner of the wallet that sent the attempt, None for attempts never broadcasted.
+    pub lease_owner: Option<String>,
 }
 
 impl NewExecutionAttempt {
-    pub fn default_standard(chain_id: i64, operator_wallet_id: Uuid, tx_value: i64) -> Self {
+    pub fn default_standard(chain_id: i64, operator_wallet_id: Uuid, tx_value: Wei) -> Self {
         Self {
             chain_id,
             operator_wallet_id,
@@ -69,6 +73,7 @@ impl NewExecutionAttempt {
             error_object: None,
             retryable: None,
             source_execution_attempt_id: None,
+            lease_owner: None,
         }
     }
 }
@@ -91,7 +96,7 @@ impl ExecutionAttemptRepo {
                 ea.chain_id,
                 ea.operator_wallet_id,
                 ea.nonce_used,
-                ea.tx_value,
+                ea.tx_value::TEXT as "tx_value!: Wei",
                 ea.tx_type as "tx_type: TxType",
                 ea.tx_hash,
                 ea.gas_limit,
@@ -114,7 +119,8 @@ impl ExecutionAttemptRepo {
                 tr.attempts,
                 tr.metadata,
                 tr.created_at as request_created_at,
-                tr.updated_at as request_updated_at
+                tr.updated_at as request_updated_at,
+                sti.value_wei::TEXT as "value_wei?: Wei"
 
             FROM (
                 SELECT *
@@ -130,6 +136,9 @@ impl ExecutionAttemptRepo {
             LEFT JOIN tx_requests tr
                 ON tr.tx_id = eai.tx_id
 
+            LEFT JOIN standard_tx_inputs sti
+                ON sti.tx_id = tr.tx_id
+
             ORDER BY ea.created_at ASC
         "#
         )
@@ -161,8 +170,13 @@ impl ExecutionAttemptRepo {
                         updated_at: row.attempt_updated_at,
                     },
                     tx_requests: Vec::new(),
+                    value_wei_by_tx_id: HashMap::new(),
                 });
 
+            if let (Some(tx_id), Some(value_wei)) = (&row.tx_id, row.value_wei) {
+                entry.value_wei_by_tx_id.insert(tx_id.clone(), value_wei);
+            }
+
             if let (
                 Some(sequence_id),
                 Some(tx_id),
@@ -227,6 +241,91 @@ impl ExecutionAttemptRepo {
         Ok(())
     }
 
+    /// Broadcasted attempts the given one replaced on the same nonce, following
+    /// `source_execution_attempt_id` back to the first send, newest first.
+    pub async fn select_replaced(&self, id: &Uuid) -> anyhow::Result<Vec<ExecutionAttempt>> {
+        let attempts = sqlx::query_as!(
+            ExecutionAttempt,
+            r#"
+            WITH RECURSIVE lineage AS (
+                SELECT id, source_execution_attempt_id, nonce_used, 0 AS depth
+                FROM execution_attempts
+                WHERE id = $1
+                UNION ALL
+                SELECT ea.id, ea.source_execution_attempt_id, ea.nonce_used, l.depth + 1
+                FROM execution_attempts ea
+                JOIN lineage l ON ea.id = l.source_execution_attempt_id
+                WHERE ea.nonce_used = l.nonce_used
+            )
+            SELECT
+                ea.id,
+                ea.chain_id,
+                ea.operator_wallet_id,
+                ea.tx_type as "tx_type: TxType",
+                ea.nonce_used,
+                ea.tx_value::TEXT as "tx_value!: Wei",
+                ea.tx_hash,
+                ea.gas_limit,
+                ea.used_gas,
+                ea.max_fee_per_gas,
+                ea.max_priority_fee,
+                ea.max_fee_per_blob_gas,
+                ea.outcome as "outcome: TxExecutionOutcome",
+                ea.error_object,
+                ea.created_at,
+                ea.updated_at
+            FROM lineage l
+            JOIN execution_attempts ea ON ea.id = l.id
+            WHERE
+                l.depth > 0
+                AND ea.tx_hash IS NOT NULL
+            ORDER BY l.depth ASC
+            "#,
+            id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(attempts)
+    }
+
+    /// Clears the STUCK or DROPPED outcome of an attempt found mined later, so the receipt
+    /// poller resolves it again. Returns false when the attempt was resolved otherwise.
+    pub async fn reopen(&self, id: &Uuid) -> anyhow::Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE execution_attempts
+            SET
+                outcome = NULL,
+                retryable = NULL,
+                retry_lock = false
+            WHERE
+                id = $1
+                AND outcome IN ('STUCK', 'DROPPED')
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Lease owner recorded when the attempt was sent.
+    pub async fn find_lease_owner(&self, id: &Uuid) -> anyhow::Result<Option<String>> {
+        let lease_owner = sqlx::query_scalar!(
+            r#"
+            SELECT lease_owner
+            FROM execution_attempts
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(lease_owner)
+    }
+
     pub async fn find_by_id(&self, id: &Uuid) -> anyhow::Result<ExecutionAttempt> {
         let attempt = sqlx::query_as!(
             ExecutionAttempt,
@@ -237,7 +336,7 @@ impl ExecutionAttemptRepo {
                 operator_wallet_id,
                 tx_type as "tx_type: TxType", 
                 nonce_used,
-                tx_value,
+                tx_value::TEXT as "tx_value!: Wei",
                 tx_hash,
                 gas_limit,
                 used_gas,
@@ -260,6 +359,88 @@ impl ExecutionAttemptRepo {
         Ok(attempt)
     }
 
+    /// Every attempt that carried each of `tx_ids`, plus the cancellations sent on their
+    /// behalf, keyed by tx_id in creation order.
+    pub async fn select_by_tx_ids(
+        &self,
+        tx_ids: &[String],
+    ) -> anyhow::Result<HashMap<String, Vec<ExecutionAttemptWithLineage>>> {
+        let items = sqlx::query!(
+            r#"
+            SELECT tx_id, execution_attempt_id
+            FROM execution_attempt_items
+            WHERE tx_id = ANY($1)"#,
+            tx_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut tx_ids_by_attempt_id: HashMap<Uuid, Vec<String>> = HashMap::new();
+        for item in items {
+            tx_ids_by_attempt_id
+                .entry(item.execution_attempt_id)
+                .or_default()
+                .push(item.tx_id);
+        }
+        let execution_attempt_ids: Vec<Uuid> = tx_ids_by_attempt_id.keys().copied().collect();
+
+        let rows = sqlx::query_as!(
+            ExecutionAttemptWithLineageRow,
+            r#"
+            SELECT
+                id,
+                chain_id,
+                operator_wallet_id,
+                tx_type as "tx_type: TxType",
+                nonce_used,
+                tx_value::TEXT as "tx_value!: Wei",
+                tx_hash,
+                gas_limit,
+                used_gas,
+                max_fee_per_gas,
+                max_priority_fee,
+                max_fee_per_blob_gas,
+                outcome as "outcome: TxExecutionOutcome",
+                error_object,
+                retryable,
+                source_execution_attempt_id,
+                created_at,
+                updated_at
+            FROM
+                execution_attempts
+            WHERE
+                id = ANY($1)
+                OR (
+                    tx_type = 'CANCELLATION'
+                    AND source_execution_attempt_id = ANY($1)
+                )
+            ORDER BY created_at ASC"#,
+            &execution_attempt_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut execution_attempts_by_tx_id: HashMap<String, Vec<ExecutionAttemptWithLineage>> =
+            HashMap::new();
+        for execution_attempt in rows.into_iter().map(ExecutionAttemptWithLineage::from) {
+            let carrier_id = match execution_attempt.execution_attempt.tx_type {
+                TxType::CANCELLATION => execution_attempt.source_execution_attempt_id,
+                _ => Some(execution_attempt.execution_attempt.id),
+            };
+            let Some(tx_ids) = carrier_id.and_then(|id| tx_ids_by_attempt_id.get(&id)) else {
+                continue;
+            };
+            for tx_id in tx_ids {
+                execution_attempts_by_tx_id
+                    .entry(tx_id.clone())
+                    .or_default()
+                    .push(execution_attempt.clone());
+            }
+        }
+
+        Ok(execution_attempts_by_tx_id)
+    }
+
     pub async fn insert(&self, input: &NewExecutionAttempt) -> anyhow::Result<ExecutionAttempt> {
         let attempt = sqlx::query_as!(
             ExecutionAttempt,
@@ -280,10 +461,12 @@ impl ExecutionAttemptRepo {
                 outcome,
                 error_object,
                 retryable,
-                source_execution_attempt_id
+                source_execution_attempt_id,
+                lease_owner
             )
             VALUES (
-                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12::TEXT::NUMERIC, $13, $14, $15, $16,
+                $17
             )
             RETURNING
                 id,
@@ -291,7 +474,7 @@ impl ExecutionAttemptRepo {
                 operator_wallet_id,
                 tx_type as "tx_type: TxType",
                 nonce_used,
-                tx_value,
+                tx_value::TEXT as "tx_value!: Wei",
                 tx_hash,
                 gas_limit,
                 used_gas,
@@ -314,11 +497,12 @@ impl ExecutionAttemptRepo {
             input.max_fee_per_gas,
             input.max_priority_fee,
             input.max_fee_per_blob_gas,
-            input.tx_value,
+            input.tx_value.to_string(),
             input.outcome.clone() as Option<TxExecutionOutcome>,
             input.error_object,
             input.retryable,
-            input.source_execution_attempt_id
+            input.source_execution_attempt_id,
+            input.lease_owner
         )
         .fetch_one(&self.pool)
         .await?;
@@ -348,7 +532,7 @@ impl ExecutionAttemptRepo {
                 operator_wallet_id,
                 tx_type as "tx_type: TxType",
                 nonce_used,
-                tx_value,
+                tx_value::TEXT as "tx_value!: Wei",
                 tx_hash,
                 gas_limit,
                 used_gas,
@@ -372,7 +556,10 @@ impl ExecutionAttemptRepo {
             r#"
             UPDATE tx_requests tr
             SET
-                tx_status = $2
+                tx_status = CASE
+                    WHEN tr.tx_id = ANY($3) THEN 'FAILED'
+                    ELSE $2
+                END
             FROM execution_attempt_items eai
             JOIN execution_attempts ea
                 ON ea.id = eai.execution_attempt_id
@@ -382,11 +569,14 @@ impl ExecutionAttemptRepo {
                 AND ea.outcome IS NOT NULL
             "#,
             propagation_input.execution_attempt_id,
-            propagation_input.tx_requests_status.clone() as TxStatus
+            propagation_input.tx_requests_status.clone() as TxStatus,
+            &propagation_input.failed_tx_ids
         )
         .execute(&mut *tx)
         .await?;
 
+        OutboxEventRepo::insert_many(&mut tx, &propagation_input.outbox_events).await?;
+
         tx.commit().await?;
 
         Ok(())
@@ -420,7 +610,7 @@ impl ExecutionAttemptRepo {
                     ea.operator_wallet_id,
                     ea.tx_type as "tx_type: TxType",
                     ea.nonce_used,
-                    ea.tx_value,
+                    ea.tx_value::TEXT as "tx_value!: Wei",
                     ea.tx_hash,
                     ea.gas_limit,
                     ea.used_gas,
@@ -466,7 +656,7 @@ impl ExecutionAttemptRepo {
                     sti.signature as "standard_signature?",
                     sti.calldata as "calldata?",
                     sti.to_address as "to_address?",
-                    sti.value_wei as "value_wei?",
+                    sti.value_wei::TEXT as "value_wei?: Wei",
                     sti.deadline_timestamp as "standard_deadline_timestamp?",
                     sti.pass_value_from_operator_wallet as "pass_value_from_operator_wallet?",
                     sti.created_at as "standard_created_at?"
@@ -516,6 +706,21 @@ impl ExecutionAttemptRepo {
         }))
     }
 
+    pub async fn release_retry_lock(&self, execution_attempt_id: &Uuid) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+                UPDATE execution_attempts
+                SET
+                    retry_lock = false
+                WHERE id = $1
+            "#,
+            execution_attempt_id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn select_with_txs(
         &self,
         execution_attempt_id: &Uuid,
@@ -528,7 +733,7 @@ impl ExecutionAttemptRepo {
                     ea.chain_id,
                     ea.operator_wallet_id,
                     ea.nonce_used,
-                    ea.tx_value,
+                    ea.tx_value::TEXT as "tx_value!: Wei",
                     ea.tx_type as "tx_type: TxType",
                     ea.tx_hash,
                     ea.gas_limit,
@@ -551,13 +756,16 @@ impl ExecutionAttemptRepo {
                     tr.attempts,
                     tr.metadata,
                     tr.created_at as request_created_at,
-                    tr.updated_at as request_updated_at
+                    tr.updated_at as request_updated_at,
+                    sti.value_wei::TEXT as "value_wei?: Wei"
 
                 FROM execution_attempts ea
                 LEFT JOIN execution_attempt_items eai
                     ON eai.execution_attempt_id = ea.id
                 LEFT JOIN tx_requests tr
                     ON tr.tx_id = eai.tx_id
+                LEFT JOIN standard_tx_inputs sti
+                    ON sti.tx_id = tr.tx_id
                 WHERE ea.id = $1
         "#,
             execution_attempt_id
@@ -588,6 +796,11 @@ impl ExecutionAttemptRepo {
             updated_at: first.attempt_updated_at,
         };
 
+        let value_wei_by_tx_id = rows
+            .iter()
+            .filter_map(|row| Some((row.tx_id.clone()?, row.value_wei?)))
+            .collect();
+
         let tx_requests = rows
             .into_iter()
             .filter_map(|row| {
@@ -610,6 +823,7 @@ impl ExecutionAttemptRepo {
         Ok(Some(ExecutionAttemptWithTxs {
             execution_attempt,
             tx_requests,
+            value_wei_by_tx_id,
         }))
     }
 }
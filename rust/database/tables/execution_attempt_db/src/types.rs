@@ -1,8 +1,10 @@
 use anyhow::anyhow;
 use blob_tx_input_db::blob_tx_inputs::BlobTxInput;
-use db_types::{BlobStorageType, TxExecutionOutcome, TxStatus, TxType};
+use db_types::{BlobStorageType, TxExecutionOutcome, TxStatus, TxType, Wei};
+use outbox_db::outbox_events::NewOutboxEvent;
 use sqlx::types::time::OffsetDateTime;
 use standard_tx_input_db::standard_tx_inputs::StandardTxInput;
+use std::collections::HashMap;
 use tx_input_types::TxInput;
 use tx_request_db::types::{TxRequest, TxRequestWithInput};
 use uuid::Uuid;
@@ -13,6 +15,8 @@ use crate::execution_attempts::ExecutionAttempt;
 pub struct ExecutionAttemptWithTxs {
     pub execution_attempt: ExecutionAttempt,
     pub tx_requests: Vec<TxRequest>,
+    /// value_wei of each standard request of the attempt.
+    pub value_wei_by_tx_id: HashMap<String, Wei>,
 }
 
 #[derive(Debug, Clone)]
@@ -21,12 +25,74 @@ pub struct ExecutionAttemptWithTxInputs {
     pub tx_requests: Vec<TxRequestWithInput>,
 }
 
+/// Execution attempt together with the columns describing its place in the retry lineage.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ExecutionAttemptWithLineage {
+    pub execution_attempt: ExecutionAttempt,
+    pub retryable: Option<bool>,
+    pub source_execution_attempt_id: Option<Uuid>,
+}
+
+#[derive(sqlx::FromRow)]
+pub struct ExecutionAttemptWithLineageRow {
+    pub id: Uuid,
+    pub chain_id: i64,
+    pub operator_wallet_id: Uuid,
+    pub nonce_used: Option<i64>,
+    pub tx_value: Wei,
+    pub tx_type: TxType,
+    pub tx_hash: Option<String>,
+    pub gas_limit: Option<i64>,
+    pub used_gas: Option<i64>,
+    pub max_fee_per_gas: Option<i64>,
+    pub max_priority_fee: Option<i64>,
+    pub max_fee_per_blob_gas: Option<i64>,
+    pub outcome: Option<TxExecutionOutcome>,
+    pub error_object: Option<String>,
+    pub retryable: Option<bool>,
+    pub source_execution_attempt_id: Option<Uuid>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<ExecutionAttemptWithLineageRow> for ExecutionAttemptWithLineage {
+    fn from(row: ExecutionAttemptWithLineageRow) -> Self {
+        Self {
+            execution_attempt: ExecutionAttempt {
+                id: row.id,
+                chain_id: row.chain_id,
+                operator_wallet_id: row.operator_wallet_id,
+                nonce_used: row.nonce_used,
+                tx_value: row.tx_value,
+                tx_type: row.tx_type,
+                tx_hash: row.tx_hash,
+                gas_limit: row.gas_limit,
+                used_gas: row.used_gas,
+                max_fee_per_gas: row.max_fee_per_gas,
+                max_priority_fee: row.max_priority_fee,
+                max_fee_per_blob_gas: row.max_fee_per_blob_gas,
+                outcome: row.outcome,
+                error_object: row.error_object,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            },
+            retryable: row.retryable,
+            source_execution_attempt_id: row.source_execution_attempt_id,
+        }
+    }
+}
+
 pub struct OutcomePropagationInput {
     pub execution_attempt_id: Uuid,
     pub outcome: TxExecutionOutcome,
     pub tx_requests_status: TxStatus,
+    /// Requests of the attempt set to FAILED instead of `tx_requests_status`, e.g. items a
+    /// successful batch did not run.
+    pub failed_tx_ids: Vec<String>,
     pub retryable: Option<bool>,
     pub used_gas: Option<i64>,
+    /// Written in the same transaction as the outcome.
+    pub outbox_events: Vec<NewOutboxEvent>,
 }
 
 #[derive(sqlx::FromRow)]
@@ -35,7 +101,7 @@ pub struct ExecutionAttemptWithTxRow {
     pub chain_id: i64,
     pub operator_wallet_id: Uuid,
     pub nonce_used: Option<i64>,
-    pub tx_value: i64,
+    pub tx_value: Wei,
     pub tx_type: TxType,
     pub tx_hash: Option<String>,
     pub gas_limit: Option<i64>,
@@ -59,6 +125,7 @@ pub struct ExecutionAttemptWithTxRow {
     pub metadata: Option<String>,
     pub request_created_at: Option<OffsetDateTime>,
     pub request_updated_at: Option<OffsetDateTime>,
+    pub value_wei: Option<Wei>,
 }
 
 #[derive(sqlx::FromRow, Debug)]
@@ -83,7 +150,7 @@ pub struct ExecutionAttemptWithTxInputRequestRow {
     pub standard_signature: Option<Vec<u8>>,
     pub calldata: Option<Vec<u8>>,
     pub to_address: Option<String>,
-    pub value_wei: Option<i64>,
+    pub value_wei: Option<Wei>,
     pub standard_deadline_timestamp: Option<i64>,
     pub pass_value_from_operator_wallet: Option<bool>,
     pub standard_created_at: Option<OffsetDateTime>,
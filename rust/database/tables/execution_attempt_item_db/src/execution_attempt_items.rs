@@ -9,9 +9,19 @@ pub struct ExecutionAttemptItem {
     pub id: Uuid,
     pub execution_attempt_id: Uuid,
     pub tx_id: String,
+    /// Whether the item's Executed/BlobSent log was found in the receipt. None until resolved.
+    pub executed: Option<bool>,
+    pub log_index: Option<i64>,
     pub created_at: OffsetDateTime,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionAttemptItemResult {
+    pub tx_id: String,
+    pub executed: bool,
+    pub log_index: Option<i64>,
+}
+
 pub struct ExecutionAttemptItemRepo {
     pool: PgPool,
 }
@@ -29,6 +39,8 @@ impl ExecutionAttemptItemRepo {
                 id,
                 execution_attempt_id,
                 tx_id,
+                executed,
+                log_index,
                 created_at
             FROM
                 execution_attempt_items
@@ -64,4 +76,62 @@ impl ExecutionAttemptItemRepo {
 
         Ok(())
     }
+
+    pub async fn record_results(
+        &self,
+        execution_attempt_id: Uuid,
+        results: &[ExecutionAttemptItemResult],
+    ) -> anyhow::Result<()> {
+        let tx_ids: Vec<String> = results.iter().map(|result| result.tx_id.clone()).collect();
+        let executed: Vec<bool> = results.iter().map(|result| result.executed).collect();
+        let log_indexes: Vec<Option<i64>> = results.iter().map(|result| result.log_index).collect();
+
+        sqlx::query!(
+            r#"
+            UPDATE execution_attempt_items eai
+            SET
+                executed = t.executed,
+                log_index = t.log_index
+            FROM UNNEST($2::text[], $3::bool[], $4::int8[]) AS t(tx_id, executed, log_index)
+            WHERE
+                eai.execution_attempt_id = $1
+                AND eai.tx_id = t.tx_id
+            "#,
+            execution_attempt_id,
+            &tx_ids,
+            &executed,
+            &log_indexes as &[Option<i64>]
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn select_by_execution_attempt_id(
+        &self,
+        execution_attempt_id: Uuid,
+    ) -> anyhow::Result<Vec<ExecutionAttemptItem>> {
+        let attempt_items = sqlx::query_as!(
+            ExecutionAttemptItem,
+            r#"
+            SELECT
+                id,
+                execution_attempt_id,
+                tx_id,
+                executed,
+                log_index,
+                created_at
+            FROM
+                execution_attempt_items
+            WHERE
+                execution_attempt_id = $1
+            ORDER BY log_index ASC NULLS LAST, created_at ASC"#,
+            execution_attempt_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(attempt_items)
+    }
 }
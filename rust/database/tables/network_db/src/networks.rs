@@ -1,3 +1,4 @@
+use db_types::{WalletSelectionStrategy, Wei};
 use serde::{Deserialize, Serialize};
 use sqlx::{PgPool, types::time::OffsetDateTime};
 
@@ -12,6 +13,15 @@ pub struct Network {
     pub blob_gas_estimation_buffer_ppm: i64,
     pub tx_max_age_sec: i64,
     pub max_retry_attempts: i32,
+    pub max_fee_per_gas_ceiling: Option<i64>,
+    pub max_fee_per_blob_gas_ceiling: Option<i64>,
+    pub operator_wallet_balance_warning_level: Option<i64>,
+    pub wallet_selection_strategy: WalletSelectionStrategy,
+    pub max_in_flight_txs_per_wallet: i32,
+    pub max_batch_items: Option<i32>,
+    pub max_batch_gas_ppm: Option<i64>,
+    pub max_batch_value_wei: Option<Wei>,
+    pub max_blobs_per_tx: Option<i32>,
     pub created_at: OffsetDateTime,
     pub updated_at: OffsetDateTime,
 }
@@ -26,6 +36,15 @@ pub struct NewNetwork {
     pub max_retry_attempts: i32,
     pub gas_estimation_buffer_ppm: i64,
     pub blob_gas_estimation_buffer_ppm: i64,
+    pub max_fee_per_gas_ceiling: Option<i64>,
+    pub max_fee_per_blob_gas_ceiling: Option<i64>,
+    pub operator_wallet_balance_warning_level: Option<i64>,
+    pub wallet_selection_strategy: WalletSelectionStrategy,
+    pub max_in_flight_txs_per_wallet: i32,
+    pub max_batch_items: Option<i32>,
+    pub max_batch_gas_ppm: Option<i64>,
+    pub max_batch_value_wei: Option<Wei>,
+    pub max_blobs_per_tx: Option<i32>,
 }
 
 pub struct NetworkRepo {
@@ -51,6 +70,15 @@ impl NetworkRepo {
                 blob_gas_estimation_buffer_ppm,
                 tx_max_age_sec,
                 max_retry_attempts,
+                max_fee_per_gas_ceiling,
+                max_fee_per_blob_gas_ceiling,
+                operator_wallet_balance_warning_level,
+                wallet_selection_strategy as "wallet_selection_strategy: WalletSelectionStrategy",
+                max_in_flight_txs_per_wallet,
+                max_batch_items,
+                max_batch_gas_ppm,
+                max_batch_value_wei::TEXT as "max_batch_value_wei?: Wei",
+                max_blobs_per_tx,
                 created_at,
                 updated_at
             FROM
@@ -79,6 +107,15 @@ impl NetworkRepo {
                 blob_gas_estimation_buffer_ppm,
                 tx_max_age_sec,
                 max_retry_attempts,
+                max_fee_per_gas_ceiling,
+                max_fee_per_blob_gas_ceiling,
+                operator_wallet_balance_warning_level,
+                wallet_selection_strategy as "wallet_selection_strategy: WalletSelectionStrategy",
+                max_in_flight_txs_per_wallet,
+                max_batch_items,
+                max_batch_gas_ppm,
+                max_batch_value_wei::TEXT as "max_batch_value_wei?: Wei",
+                max_blobs_per_tx,
                 created_at,
                 updated_at
             FROM
@@ -102,9 +139,18 @@ impl NetworkRepo {
                 gas_estimation_buffer_ppm,
                 blob_gas_estimation_buffer_ppm,
                 max_retry_attempts,
-                tx_max_age_sec
+                tx_max_age_sec,
+                max_fee_per_gas_ceiling,
+                max_fee_per_blob_gas_ceiling,
+                operator_wallet_balance_warning_level,
+                wallet_selection_strategy,
+                max_in_flight_txs_per_wallet,
+                max_batch_items,
+                max_batch_gas_ppm,
+                max_batch_value_wei,
+                max_blobs_per_tx
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17::TEXT::NUMERIC, $18)"#,
             network.chain_id,
             network.chain_name,
             network.rpc_url,
@@ -114,6 +160,17 @@ impl NetworkRepo {
             network.blob_gas_estimation_buffer_ppm,
             i16::try_from(network.max_retry_attempts)?,
             network.tx_max_age_sec,
+            network.max_fee_per_gas_ceiling,
+            network.max_fee_per_blob_gas_ceiling,
+            network.operator_wallet_balance_warning_level,
+            network.wallet_selection_strategy.clone() as WalletSelectionStrategy,
+            network.max_in_flight_txs_per_wallet,
+            network.max_batch_items,
+            network.max_batch_gas_ppm,
+            network
+                .max_batch_value_wei
+                .map(|max_batch_value_wei| max_batch_value_wei.to_string()),
+            network.max_blobs_per_tx,
         )
         .execute(&self.pool)
         .await?;
@@ -1 +1,2 @@
+pub mod nonce_reservations;
 pub mod operator_wallets;
@@ -0,0 +1,244 @@
+use db_types::NonceReservationStatus;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, types::time::OffsetDateTime};
+use uuid::Uuid;
+
+/// Picks tried by `reserve_next` before giving up on concurrent reservations.
+const RESERVE_NEXT_ATTEMPTS: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, Clone)]
+pub struct NonceReservation {
+    pub operator_wallet_id: Uuid,
+    pub nonce: i64,
+    pub status: NonceReservationStatus,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+/// In-flight nonces of wallets on networks that allow more than one transaction per wallet.
+/// A reservation lives from the moment a sender takes the nonce until its receipt is resolved.
+#[derive(Clone)]
+pub struct NonceReservationRepo {
+    pub pool: PgPool,
+}
+
+impl NonceReservationRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn select_by_wallet(
+        &self,
+        operator_wallet_id: Uuid,
+    ) -> anyhow::Result<Vec<NonceReservation>> {
+        let reservations = sqlx::query_as!(
+            NonceReservation,
+            r#"
+            SELECT
+                operator_wallet_id,
+                nonce,
+                status as "status: NonceReservationStatus",
+                created_at,
+                updated_at
+            FROM
+                nonce_reservations
+            WHERE
+                operator_wallet_id = $1
+            ORDER BY nonce ASC
+            "#,
+            operator_wallet_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(reservations)
+    }
+
+    /// Reserves the lowest free nonce at or above both the wallet nonce and `min_nonce`.
+    /// Nonces released before broadcast leave a gap below the highest reservation, which
+    /// is handed out again here before the range grows. A nonce taken concurrently, e.g. by
+    /// a gap fill, is skipped and the pick is repeated.
+    pub async fn reserve_next(
+        &self,
+        operator_wallet_id: Uuid,
+        min_nonce: i64,
+    ) -> anyhow::Result<i64> {
+        for _ in 0..RESERVE_NEXT_ATTEMPTS {
+            if let Some(nonce) = self.try_reserve_next(operator_wallet_id, min_nonce).await? {
+                return Ok(nonce);
+            }
+        }
+
+        anyhow::bail!(
+            "No free nonce of wallet {operator_wallet_id} after {RESERVE_NEXT_ATTEMPTS} picks"
+        )
+    }
+
+    async fn try_reserve_next(
+        &self,
+        operator_wallet_id: Uuid,
+        min_nonce: i64,
+    ) -> anyhow::Result<Option<i64>> {
+        let reservation = sqlx::query!(
+            r#"
+        WITH wallet AS (
+            SELECT GREATEST(nonce, $2) AS floor
+            FROM operator_wallets
+            WHERE id = $1
+        ),
+        next_nonce AS (
+            SELECT MIN(candidate) AS nonce
+            FROM
+                wallet,
+                generate_series(
+                    wallet.floor,
+                    wallet.floor + (
+                        SELECT COUNT(*) FROM nonce_reservations WHERE operator_wallet_id = $1
+                    )
+                ) AS candidate
+            WHERE NOT EXISTS (
+                SELECT 1
+                FROM nonce_reservations nr
+                WHERE nr.operator_wallet_id = $1 AND nr.nonce = candidate
+            )
+        )
+        INSERT INTO nonce_reservations (operator_wallet_id, nonce, status)
+        SELECT $1, next_nonce.nonce, 'RESERVED'
+        FROM next_nonce
+        ON CONFLICT DO NOTHING
+        RETURNING nonce
+        "#,
+            operator_wallet_id,
+            min_nonce
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(reservation.map(|reservation| reservation.nonce))
+    }
+
+    /// Reserves a specific nonce. Returns false if it is already reserved.
+    pub async fn reserve(&self, operator_wallet_id: Uuid, nonce: i64) -> anyhow::Result<bool> {
+        let result = sqlx::query!(
+            r#"
+        INSERT INTO nonce_reservations (operator_wallet_id, nonce, status)
+        VALUES ($1, $2, 'RESERVED')
+        ON CONFLICT DO NOTHING
+        "#,
+            operator_wallet_id,
+            nonce
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn mark_broadcasted(
+        &self,
+        operator_wallet_id: Uuid,
+        nonce: i64,
+    ) -> anyhow::Result<()> {
+        let result = sqlx::query!(
+            r#"
+        UPDATE nonce_reservations
+        SET
+            status = 'BROADCASTED'
+        WHERE
+            operator_wallet_id = $1
+            AND nonce = $2
+        "#,
+            operator_wallet_id,
+            nonce
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            anyhow::bail!("nonce reservation not found");
+        }
+        Ok(())
+    }
+
+    pub async fn release_reserved(
+        &self,
+        operator_wallet_id: Uuid,
+        nonce: Option<i64>,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+        DELETE FROM nonce_reservations
+        WHERE
+            operator_wallet_id = $1
+            AND status = 'RESERVED'
+            AND ($2::BIGINT IS NULL OR nonce = $2)
+        "#,
+            operator_wallet_id,
+            nonce
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Drops the reserved but never broadcasted nonces of a wallet, as long as `lease_owner`
+    /// still holds its lease. A lost lease means the reservations belong to the new holder.
+    pub async fn release_reserved_by_lease(
+        &self,
+        operator_wallet_id: Uuid,
+        lease_owner: &str,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+        DELETE FROM nonce_reservations nr
+        USING operator_wallets ow
+        WHERE
+            nr.operator_wallet_id = $1
+            AND nr.status = 'RESERVED'
+            AND ow.id = nr.operator_wallet_id
+            AND ow.lease_owner = $2
+        "#,
+            operator_wallet_id,
+            lease_owner
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Drops the reservations up to `nonce` and moves the wallet nonce past it. Receipts
+    /// resolved out of order never move the wallet nonce back.
+    pub async fn confirm(&self, operator_wallet_id: Uuid, nonce: i64) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+        DELETE FROM nonce_reservations
+        WHERE
+            operator_wallet_id = $1
+            AND nonce <= $2
+        "#,
+            operator_wallet_id,
+            nonce
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+        UPDATE operator_wallets
+        SET
+            nonce = GREATEST(nonce, $2::BIGINT + 1)
+        WHERE
+            id = $1
+        "#,
+            operator_wallet_id,
+            nonce
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,621 @@
+use db_types::{TxExecutionOutcome, WalletSelectionStrategy};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Type, types::time::OffsetDateTime};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
+#[sqlx(type_name = "text")]
+pub enum KeyType {
+    AwsKms,
+    #[cfg(feature = "test-keys")]
+    TestPrivateKey,
+}
+
+#[derive(Debug)]
+pub struct NewOperatorWallet {
+    pub id: Uuid,
+    pub wallet_address: String,
+    pub key_ref: String,
+    pub key_type: KeyType,
+    pub chain_id: i64,
+    pub current_nonce: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, Clone)]
+pub struct OperatorWallet {
+    pub id: Uuid,
+    pub wallet_address: String,
+    pub key_ref: String,
+    pub key_type: KeyType,
+    pub chain_id: i64,
+    pub nonce: i64,
+    pub is_enabled: bool,
+    pub lease_owner: Option<String>,
+    pub lease_expires_at: Option<OffsetDateTime>,
+    pub no_funds: bool,
+    pub needs_manual_review: bool,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct NonceHistoryEntry {
+    pub nonce_used: i64,
+    pub outcome: Option<TxExecutionOutcome>,
+}
+
+#[derive(Clone)]
+pub struct OperatorWalletRepo {
+    pub pool: PgPool,
+}
+
+impl OperatorWalletRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_by_id(&self, operator_wallet_id: Uuid) -> anyhow::Result<OperatorWallet> {
+        let transaction = sqlx::query_as!(
+            OperatorWallet,
+            r#"
+            SELECT
+                id,
+                wallet_address,
+                key_ref,
+                key_type as "key_type: KeyType",
+                chain_id,
+                nonce,
+                is_enabled,
+                lease_owner,
+                lease_expires_at,
+                no_funds,
+                needs_manual_review,
+                created_at,
+                updated_at
+            FROM
+                operator_wallets
+            WHERE
+                id = $1"#,
+            operator_wallet_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(transaction)
+    }
+
+    pub async fn lock_by_id(
+        &self,
+        operator_wallet_id: Uuid,
+        chain_id: i64,
+        max_in_flight_txs: i64,
+        lease_owner: &str,
+        lease_duration_sec: f64,
+    ) -> anyhow::Result<Option<OperatorWallet>> {
+        let wallet = sqlx::query_as!(
+            OperatorWallet,
+            r#"
+        WITH candidate AS (
+            SELECT id, nonce AS current_nonce
+            FROM operator_wallets
+            WHERE
+                id = $1
+                AND chain_id = $2
+                AND is_enabled = true
+                AND lease_owner IS NULL
+                AND no_funds = false
+                AND needs_manual_review = false
+                AND (
+                    SELECT COUNT(*) FROM nonce_reservations nr WHERE nr.operator_wallet_id = operator_wallets.id
+                ) < $3
+            FOR UPDATE SKIP LOCKED
+        )
+        UPDATE operator_wallets ow
+        SET
+            lease_owner = $4,
+            lease_expires_at = NOW() + make_interval(secs => $5),
+            last_leased_at = NOW()
+        FROM candidate
+        WHERE ow.id = candidate.id
+        RETURNING
+            ow.id,
+            ow.wallet_address,
+            ow.key_ref,
+            ow.key_type as "key_type: KeyType",
+            ow.chain_id,
+            candidate.current_nonce AS nonce,
+            ow.is_enabled,
+            ow.lease_owner,
+            ow.lease_expires_at,
+            ow.no_funds,
+            ow.needs_manual_review,
+            ow.created_at,
+            ow.updated_at
+        "#,
+            operator_wallet_id,
+            chain_id,
+            max_in_flight_txs,
+            lease_owner,
+            lease_duration_sec
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(wallet)
+    }
+
+    pub async fn lock_any_by_chain(
+        &self,
+        chain_id: i64,
+        strategy: &WalletSelectionStrategy,
+        requester_id: Option<&str>,
+        max_in_flight_txs: i64,
+        lease_owner: &str,
+        lease_duration_sec: f64,
+    ) -> anyhow::Result<Option<OperatorWallet>> {
+        let wallet = sqlx::query_as!(
+            OperatorWallet,
+            r#"
+        WITH candidate AS (
+            SELECT id, nonce AS current_nonce
+            FROM operator_wallets
+            WHERE
+                chain_id = $1
+                AND is_enabled = true
+                AND lease_owner IS NULL
+                AND no_funds = false
+                AND needs_manual_review = false
+                AND (
+                    SELECT COUNT(*) FROM nonce_reservations nr WHERE nr.operator_wallet_id = operator_wallets.id
+                ) < $4
+            ORDER BY
+                CASE
+                    WHEN $2 = 'STICKY_PER_REQUESTER' AND last_requester_id = $3 THEN 0
+                    ELSE 1
+                END,
+                CASE WHEN $2 = 'HIGHEST_BALANCE' THEN balance_wei END DESC NULLS LAST,
+                CASE
+                    WHEN $2 = 'WEIGHTED_BY_BALANCE'
+                    THEN -LN(1 - RANDOM()) / NULLIF(balance_wei, 0)::DOUBLE PRECISION
+                END ASC NULLS LAST,
+                last_leased_at ASC NULLS FIRST
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        )
+        UPDATE operator_wallets ow
+        SET
+            lease_owner = $5,
+            lease_expires_at = NOW() + make_interval(secs => $6),
+            last_leased_at = NOW(),
+            last_requester_id = COALESCE($3, ow.last_requester_id)
+        FROM candidate
+        WHERE ow.id = candidate.id
+        RETURNING
+            ow.id,
+            ow.wallet_address,
+            ow.key_ref,
+            ow.key_type as "key_type: KeyType",
+            ow.chain_id,
+            candidate.current_nonce AS nonce,
+            ow.is_enabled,
+            ow.lease_owner,
+            ow.lease_expires_at,
+            ow.no_funds,
+            ow.needs_manual_review,
+            ow.created_at,
+            ow.updated_at
+        "#,
+            chain_id,
+            strategy.clone() as WalletSelectionStrategy,
+            requester_id,
+            max_in_flight_txs,
+            lease_owner,
+            lease_duration_sec
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(wallet)
+    }
+
+    pub async fn mark_no_funds(&self, operator_wallet_id: Uuid) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+        UPDATE operator_wallets
+        SET
+            no_funds = true,
+            lease_owner = NULL,
+            lease_expires_at = NULL
+        WHERE
+            id = $1
+        "#,
+            operator_wallet_id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn clear_no_funds(&self, operator_wallet_id: Uuid) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+        UPDATE operator_wallets
+        SET
+            no_funds = false
+        WHERE
+            id = $1
+        "#,
+            operator_wallet_id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Stores the last seen balance, which balance based selection strategies order by.
+    pub async fn set_balance(
+        &self,
+        operator_wallet_id: Uuid,
+        balance_wei: &str,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+        UPDATE operator_wallets
+        SET
+            balance_wei = $2::TEXT::NUMERIC
+        WHERE
+            id = $1
+        "#,
+            operator_wallet_id,
+            balance_wei
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Flips the low balance warning flag, returns whether it changed. Lets the balance sweep
+    /// warn once when a wallet drops below the warning level and again only after a refill.
+    pub async fn set_low_balance_warned(
+        &self,
+        operator_wallet_id: Uuid,
+        low_balance_warned: bool,
+    ) -> anyhow::Result<bool> {
+        let result = sqlx::query!(
+            r#"
+        UPDATE operator_wallets
+        SET
+            low_balance_warned = $2
+        WHERE
+            id = $1
+            AND low_balance_warned <> $2
+        "#,
+            operator_wallet_id,
+            low_balance_warned
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn mark_needs_manual_review(&self, operator_wallet_id: Uuid) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+        UPDATE operator_wallets
+        SET
+            needs_manual_review = true,
+            lease_owner = NULL,
+            lease_expires_at = NULL
+        WHERE
+            id = $1
+        "#,
+            operator_wallet_id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns false when `lease_owner` no longer holds the lease.
+    pub async fn set_nonce(
+        &self,
+        operator_wallet_id: Uuid,
+        lease_owner: &str,
+        nonce: i64,
+    ) -> anyhow::Result<bool> {
+        let result = sqlx::query!(
+            r#"
+        UPDATE operator_wallets
+        SET
+            nonce = $3
+        WHERE
+            id = $1
+            AND lease_owner = $2
+        "#,
+            operator_wallet_id,
+            lease_owner,
+            nonce
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn select_idle(&self) -> anyhow::Result<Vec<OperatorWallet>> {
+        let wallets = sqlx::query_as!(
+            OperatorWallet,
+            r#"
+            SELECT
+                id,
+                wallet_address,
+                key_ref,
+                key_type as "key_type: KeyType",
+                chain_id,
+                nonce,
+                is_enabled,
+                lease_owner,
+                lease_expires_at,
+                no_funds,
+                needs_manual_review,
+                created_at,
+                updated_at
+            FROM
+                operator_wallets
+            WHERE
+                is_enabled = true
+                AND lease_owner IS NULL
+                AND no_funds = false
+                AND needs_manual_review = false
+                AND NOT EXISTS (
+                    SELECT 1 FROM nonce_reservations nr WHERE nr.operator_wallet_id = operator_wallets.id
+                )
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(wallets)
+    }
+
+    pub async fn select_enabled(&self) -> anyhow::Result<Vec<OperatorWallet>> {
+        let wallets = sqlx::query_as!(
+            OperatorWallet,
+            r#"
+            SELECT
+                id,
+                wallet_address,
+                key_ref,
+                key_type as "key_type: KeyType",
+                chain_id,
+                nonce,
+                is_enabled,
+                lease_owner,
+                lease_expires_at,
+                no_funds,
+                needs_manual_review,
+                created_at,
+                updated_at
+            FROM
+                operator_wallets
+            WHERE
+                is_enabled = true
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(wallets)
+    }
+
+    /// Broadcasted execution attempts of the wallet at or above `from_nonce`.
+    pub async fn select_nonce_history(
+        &self,
+        operator_wallet_id: Uuid,
+        from_nonce: i64,
+    ) -> anyhow::Result<Vec<NonceHistoryEntry>> {
+        let history = sqlx::query_as!(
+            NonceHistoryEntry,
+            r#"
+            SELECT
+                nonce_used as "nonce_used!",
+                outcome as "outcome: TxExecutionOutcome"
+            FROM
+                execution_attempts
+            WHERE
+                operator_wallet_id = $1
+                AND nonce_used >= $2
+                AND tx_hash IS NOT NULL
+            ORDER BY nonce_used ASC
+            "#,
+            operator_wallet_id,
+            from_nonce
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(history)
+    }
+
+    /// Gives a lease taken over by `lease_owner` back to `previous_lease_owner` with a fresh
+    /// expiry. Returns false when `lease_owner` no longer holds the lease.
+    pub async fn hand_back_lease(
+        &self,
+        operator_wallet_id: Uuid,
+        lease_owner: &str,
+        previous_lease_owner: &str,
+        lease_duration_sec: f64,
+    ) -> anyhow::Result<bool> {
+        let result = sqlx::query!(
+            r#"
+        UPDATE operator_wallets
+        SET
+            lease_owner = $3,
+            lease_expires_at = NOW() + make_interval(secs => $4)
+        WHERE
+            id = $1
+            AND lease_owner = $2
+        "#,
+            operator_wallet_id,
+            lease_owner,
+            previous_lease_owner,
+            lease_duration_sec
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Moves every expired lease to `lease_owner`, so only one sweeper handles each wallet. The
+    /// returned wallets carry the lease owner they had before the takeover.
+    pub async fn take_over_expired_leases(
+        &self,
+        lease_owner: &str,
+        lease_duration_sec: f64,
+    ) -> anyhow::Result<Vec<OperatorWallet>> {
+        let wallets = sqlx::query_as!(
+            OperatorWallet,
+            r#"
+        WITH expired AS (
+            SELECT id, lease_owner
+            FROM operator_wallets
+            WHERE
+                lease_owner IS NOT NULL
+                AND lease_expires_at < NOW()
+            FOR UPDATE SKIP LOCKED
+        )
+        UPDATE operator_wallets ow
+        SET
+            lease_owner = $1,
+            lease_expires_at = NOW() + make_interval(secs => $2)
+        FROM expired
+        WHERE ow.id = expired.id
+        RETURNING
+            ow.id,
+            ow.wallet_address,
+            ow.key_ref,
+            ow.key_type as "key_type: KeyType",
+            ow.chain_id,
+            ow.nonce,
+            ow.is_enabled,
+            expired.lease_owner,
+            ow.lease_expires_at,
+            ow.no_funds,
+            ow.needs_manual_review,
+            ow.created_at,
+            ow.updated_at
+        "#,
+            lease_owner,
+            lease_duration_sec
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(wallets)
+    }
+
+    pub async fn insert(&self, new_wallet: NewOperatorWallet) -> anyhow::Result<OperatorWallet> {
+        let wallet = sqlx::query_as!(
+            OperatorWallet,
+            r#"
+        INSERT INTO operator_wallets (
+            id,
+            wallet_address,
+            key_ref,
+            key_type,
+            chain_id,
+            nonce,
+            is_enabled,
+            no_funds
+        )
+        VALUES (
+            $1,
+            $2,
+            $3,
+            $4,
+            $5,
+            $6,
+            true,
+            false
+        )
+        RETURNING
+            id,
+            wallet_address,
+            key_ref,
+            key_type as "key_type: KeyType",
+            chain_id,
+            nonce,
+            is_enabled,
+            lease_owner,
+            lease_expires_at,
+            no_funds,
+            needs_manual_review,
+            created_at,
+            updated_at
+        "#,
+            new_wallet.id,
+            new_wallet.wallet_address,
+            new_wallet.key_ref,
+            new_wallet.key_type as KeyType,
+            new_wallet.chain_id,
+            new_wallet.current_nonce
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(wallet)
+    }
+
+    /// Returns false when `lease_owner` no longer holds the lease, e.g. after the sweeper took
+    /// it over and resynced the nonce. The nonce is left alone then.
+    pub async fn release_used(
+        &self,
+        operator_wallet_id: Uuid,
+        lease_owner: &str,
+    ) -> anyhow::Result<bool> {
+        let result = sqlx::query!(
+            r#"
+        UPDATE operator_wallets
+        SET
+            lease_owner = NULL,
+            lease_expires_at = NULL,
+            nonce = nonce + 1
+        WHERE
+            id = $1
+            AND lease_owner = $2
+        "#,
+            operator_wallet_id,
+            lease_owner
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Returns false when `lease_owner` no longer holds the lease.
+    pub async fn release_unused(
+        &self,
+        operator_wallet_id: Uuid,
+        lease_owner: &str,
+    ) -> anyhow::Result<bool> {
+        let result = sqlx::query!(
+            r#"
+        UPDATE operator_wallets
+        SET
+            lease_owner = NULL,
+            lease_expires_at = NULL
+        WHERE
+            id = $1
+            AND lease_owner = $2
+        "#,
+            operator_wallet_id,
+            lease_owner
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+#[cfg(test)]
+mod tests;
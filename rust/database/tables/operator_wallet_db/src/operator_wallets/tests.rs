@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use crate::operator_wallets::{KeyType, NewOperatorWallet, OperatorWalletRepo};
+    use sqlx::PgPool;
+    use uuid::Uuid;
+
+    const CHAIN_ID: i64 = 31337;
+    const LEASE_DURATION_SEC: f64 = 900.0;
+
+    async fn insert_wallet(pool: &PgPool) -> anyhow::Result<Uuid> {
+        sqlx::query!(
+            r#"
+        INSERT INTO networks (
+            chain_id,
+            chain_name,
+            rpc_url,
+            contract_address,
+            min_operator_wallet_balance,
+            gas_estimation_buffer_ppm,
+            blob_gas_estimation_buffer_ppm
+        )
+        VALUES ($1, 'anvil', 'http://127.0.0.1:8545', '0x00', 0, 0, 0)
+        "#,
+            CHAIN_ID
+        )
+        .execute(pool)
+        .await?;
+
+        let wallet = OperatorWalletRepo::new(pool.clone())
+            .insert(NewOperatorWallet {
+                id: Uuid::new_v4(),
+                wallet_address: "0x01".to_string(),
+                key_ref: "key".to_string(),
+                key_type: KeyType::AwsKms,
+                chain_id: CHAIN_ID,
+                current_nonce: 7,
+            })
+            .await?;
+        Ok(wallet.id)
+    }
+
+    async fn expire_lease(pool: &PgPool, operator_wallet_id: Uuid) -> anyhow::Result<()> {
+        sqlx::query!(
+            "UPDATE operator_wallets SET lease_expires_at = NOW() - INTERVAL '1 second' WHERE id = $1",
+            operator_wallet_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn stale_release_after_takeover_keeps_new_lease(pool: PgPool) -> anyhow::Result<()> {
+        let repo = OperatorWalletRepo::new(pool.clone());
+        let operator_wallet_id = insert_wallet(&pool).await?;
+
+        repo.lock_by_id(
+            operator_wallet_id,
+            CHAIN_ID,
+            1,
+            "sender",
+            LEASE_DURATION_SEC,
+        )
+        .await?
+        .expect("wallet should be locked");
+        expire_lease(&pool, operator_wallet_id).await?;
+        let taken_over = repo
+            .take_over_expired_leases("sweeper", LEASE_DURATION_SEC)
+            .await?;
+        assert_eq!(taken_over.len(), 1);
+
+        assert!(!repo.release_used(operator_wallet_id, "sender").await?);
+        assert!(!repo.release_unused(operator_wallet_id, "sender").await?);
+        assert!(
+            !repo
+                .hand_back_lease(operator_wallet_id, "sender", "sender", LEASE_DURATION_SEC)
+                .await?
+        );
+        assert!(!repo.set_nonce(operator_wallet_id, "sender", 9).await?);
+
+        let wallet = repo.find_by_id(operator_wallet_id).await?;
+        assert_eq!(wallet.lease_owner.as_deref(), Some("sweeper"));
+        assert_eq!(wallet.nonce, 7);
+
+        assert!(repo.release_used(operator_wallet_id, "sweeper").await?);
+        let wallet = repo.find_by_id(operator_wallet_id).await?;
+        assert_eq!(wallet.lease_owner, None);
+        assert_eq!(wallet.nonce, 8);
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn handed_back_lease_is_released_by_its_owner(pool: PgPool) -> anyhow::Result<()> {
+        let repo = OperatorWalletRepo::new(pool.clone());
+        let operator_wallet_id = insert_wallet(&pool).await?;
+
+        repo.lock_by_id(
+            operator_wallet_id,
+            CHAIN_ID,
+            1,
+            "sender",
+            LEASE_DURATION_SEC,
+        )
+        .await?
+        .expect("wallet should be locked");
+        expire_lease(&pool, operator_wallet_id).await?;
+        let taken_over = repo
+            .take_over_expired_leases("sweeper", LEASE_DURATION_SEC)
+            .await?;
+        assert_eq!(taken_over.len(), 1);
+        assert_eq!(taken_over[0].lease_owner.as_deref(), Some("sender"));
+
+        assert!(
+            repo.hand_back_lease(operator_wallet_id, "sweeper", "sender", LEASE_DURATION_SEC)
+                .await?
+        );
+        let wallet = repo.find_by_id(operator_wallet_id).await?;
+        assert_eq!(wallet.lease_owner.as_deref(), Some("sender"));
+
+        assert!(repo.release_used(operator_wallet_id, "sender").await?);
+        let wallet = repo.find_by_id(operator_wallet_id).await?;
+        assert_eq!(wallet.lease_owner, None);
+        assert_eq!(wallet.nonce, 8);
+        Ok(())
+    }
+}
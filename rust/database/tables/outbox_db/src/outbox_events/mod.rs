@@ -0,0 +1,300 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    PgConnection, PgPool,
+    types::{Uuid, time::OffsetDateTime},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// How long a claimed event is held by one relay before another may pick it up.
+pub const OUTBOX_LEASE_DURATION_SEC: f64 = 60.0;
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, Clone)]
+pub struct OutboxEvent {
+    pub id: Uuid,
+    pub event_type: String,
+    pub tx_id: Option<String>,
+    pub payload: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub lease_expires_at: Option<OffsetDateTime>,
+    pub delivered_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+    /// Insertion order, kept within a transaction where `created_at` is the same.
+    pub seq: i64,
+    /// Set once the event is given up on. Parked events are never claimed again.
+    pub parked_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewOutboxEvent {
+    pub id: Uuid,
+    pub event_type: String,
+    pub tx_id: Option<String>,
+    pub payload: String,
+}
+
+#[derive(Clone)]
+pub struct OutboxEventRepo {
+    pub pool: PgPool,
+}
+
+impl OutboxEventRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Writes the events on the given connection, so they commit or roll back with the state
+    /// change of the caller's transaction.
+    pub async fn insert_many(
+        connection: &mut PgConnection,
+        outbox_events: &[NewOutboxEvent],
+    ) -> anyhow::Result<()> {
+        if outbox_events.is_empty() {
+            return Ok(());
+        }
+
+        let ids: Vec<Uuid> = outbox_events.iter().map(|event| event.id).collect();
+        let event_types: Vec<String> = outbox_events
+            .iter()
+            .map(|event| event.event_type.clone())
+            .collect();
+        let tx_ids: Vec<Option<String>> = outbox_events
+            .iter()
+            .map(|event| event.tx_id.clone())
+            .collect();
+        let payloads: Vec<String> = outbox_events
+            .iter()
+            .map(|event| event.payload.clone())
+            .collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO outbox_events (id, event_type, tx_id, payload)
+            SELECT id, event_type, tx_id, payload::JSONB
+            FROM UNNEST($1::uuid[], $2::text[], $3::text[], $4::text[])
+                WITH ORDINALITY AS t(id, event_type, tx_id, payload, position)
+            ORDER BY position
+            "#,
+            &ids,
+            &event_types,
+            &tx_ids as &[Option<String>],
+            &payloads
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Claims the given events that are still pending and not held by another relay.
+    pub async fn claim(&self, ids: &[Uuid]) -> anyhow::Result<Vec<OutboxEvent>> {
+        let outbox_events = sqlx::query_as!(
+            OutboxEvent,
+            r#"
+            WITH claimable AS (
+                SELECT id
+                FROM outbox_events
+                WHERE
+                    id = ANY($1)
+                    AND delivered_at IS NULL
+                    AND parked_at IS NULL
+                    AND (lease_expires_at IS NULL OR lease_expires_at < NOW())
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE outbox_events oe
+            SET
+                attempts = oe.attempts + 1,
+                lease_expires_at = NOW() + make_interval(secs => $2)
+            FROM claimable
+            WHERE oe.id = claimable.id
+            RETURNING
+                oe.id,
+                oe.event_type,
+                oe.tx_id,
+                oe.payload::TEXT as "payload!",
+                oe.attempts,
+                oe.last_error,
+                oe.lease_expires_at,
+                oe.delivered_at,
+                oe.created_at,
+                oe.seq,
+                oe.parked_at
+            "#,
+            ids,
+            OUTBOX_LEASE_DURATION_SEC
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(sort_by_seq(outbox_events))
+    }
+
+    /// Claims the pending events of the given requests not held by another relay.
+    pub async fn claim_by_tx_ids(&self, tx_ids: &[String]) -> anyhow::Result<Vec<OutboxEvent>> {
+        let outbox_events = sqlx::query_as!(
+            OutboxEvent,
+            r#"
+            WITH claimable AS (
+                SELECT id
+                FROM outbox_events
+                WHERE
+                    tx_id = ANY($1)
+                    AND delivered_at IS NULL
+                    AND parked_at IS NULL
+                    AND (lease_expires_at IS NULL OR lease_expires_at < NOW())
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE outbox_events oe
+            SET
+                attempts = oe.attempts + 1,
+                lease_expires_at = NOW() + make_interval(secs => $2)
+            FROM claimable
+            WHERE oe.id = claimable.id
+            RETURNING
+                oe.id,
+                oe.event_type,
+                oe.tx_id,
+                oe.payload::TEXT as "payload!",
+                oe.attempts,
+                oe.last_error,
+                oe.lease_expires_at,
+                oe.delivered_at,
+                oe.created_at,
+                oe.seq,
+                oe.parked_at
+            "#,
+            tx_ids,
+            OUTBOX_LEASE_DURATION_SEC
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(sort_by_seq(outbox_events))
+    }
+
+    /// Claims up to `limit` of the oldest pending events not held by another relay.
+    pub async fn claim_pending(&self, limit: i64) -> anyhow::Result<Vec<OutboxEvent>> {
+        let outbox_events = sqlx::query_as!(
+            OutboxEvent,
+            r#"
+            WITH claimable AS (
+                SELECT id
+                FROM outbox_events
+                WHERE
+                    delivered_at IS NULL
+                    AND parked_at IS NULL
+                    AND (lease_expires_at IS NULL OR lease_expires_at < NOW())
+                ORDER BY seq
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE outbox_events oe
+            SET
+                attempts = oe.attempts + 1,
+                lease_expires_at = NOW() + make_interval(secs => $2)
+            FROM claimable
+            WHERE oe.id = claimable.id
+            RETURNING
+                oe.id,
+                oe.event_type,
+                oe.tx_id,
+                oe.payload::TEXT as "payload!",
+                oe.attempts,
+                oe.last_error,
+                oe.lease_expires_at,
+                oe.delivered_at,
+                oe.created_at,
+                oe.seq,
+                oe.parked_at
+            "#,
+            limit,
+            OUTBOX_LEASE_DURATION_SEC
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(sort_by_seq(outbox_events))
+    }
+
+    pub async fn mark_delivered(&self, id: Uuid) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE outbox_events
+            SET
+                delivered_at = NOW(),
+                lease_expires_at = NULL,
+                last_error = NULL
+            WHERE
+                id = $1
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Holds the event back for `retry_after_sec` before a relay run may retry it, or parks it
+    /// once it has been claimed `max_attempts` times. Returns whether the event was parked.
+    pub async fn record_failure(
+        &self,
+        id: Uuid,
+        error: &str,
+        max_attempts: i32,
+        retry_after_sec: f64,
+    ) -> anyhow::Result<bool> {
+        let parked = sqlx::query_scalar!(
+            r#"
+            UPDATE outbox_events
+            SET
+                last_error = $2,
+                lease_expires_at = CASE
+                    WHEN attempts >= $3 THEN NULL
+                    ELSE NOW() + make_interval(secs => $4)
+                END,
+                parked_at = CASE WHEN attempts >= $3 THEN NOW() END
+            WHERE
+                id = $1
+            RETURNING parked_at IS NOT NULL as "parked!"
+            "#,
+            id,
+            error,
+            max_attempts,
+            retry_after_sec
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(parked)
+    }
+
+    /// Parks an event right away, for failures that sending it again cannot fix.
+    pub async fn park(&self, id: Uuid, error: &str) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE outbox_events
+            SET
+                last_error = $2,
+                lease_expires_at = NULL,
+                parked_at = NOW()
+            WHERE
+                id = $1
+            "#,
+            id,
+            error
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+// UPDATE ... RETURNING does not keep the order of the claiming SELECT.
+fn sort_by_seq(mut outbox_events: Vec<OutboxEvent>) -> Vec<OutboxEvent> {
+    outbox_events.sort_by_key(|outbox_event| outbox_event.seq);
+    outbox_events
+}
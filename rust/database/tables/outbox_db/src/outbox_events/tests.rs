@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use crate::outbox_events::{NewOutboxEvent, OutboxEventRepo};
+    use sqlx::PgPool;
+    use uuid::Uuid;
+
+    fn new_outbox_event(event_type: &str) -> NewOutboxEvent {
+        NewOutboxEvent {
+            id: Uuid::new_v4(),
+            event_type: event_type.to_string(),
+            tx_id: Some("tx-1".to_string()),
+            payload: "{}".to_string(),
+        }
+    }
+
+    async fn insert(pool: &PgPool, outbox_events: &[NewOutboxEvent]) -> anyhow::Result<()> {
+        let mut tx = pool.begin().await?;
+        OutboxEventRepo::insert_many(&mut tx, outbox_events).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn claims_events_of_one_transaction_in_insertion_order(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        let repo = OutboxEventRepo::new(pool.clone());
+        let outbox_events: Vec<NewOutboxEvent> = ["lifecycle", "outcome", "lifecycle"]
+            .into_iter()
+            .cycle()
+            .take(30)
+            .map(new_outbox_event)
+            .collect();
+        insert(&pool, &outbox_events).await?;
+        let ids: Vec<Uuid> = outbox_events.iter().map(|event| event.id).collect();
+
+        let claimed = repo.claim(&ids).await?;
+        let claimed_ids: Vec<Uuid> = claimed.iter().map(|event| event.id).collect();
+        assert_eq!(claimed_ids, ids);
+
+        sqlx::query!("UPDATE outbox_events SET lease_expires_at = NULL")
+            .execute(&pool)
+            .await?;
+        let claimed = repo.claim_pending(100).await?;
+        let claimed_ids: Vec<Uuid> = claimed.iter().map(|event| event.id).collect();
+        assert_eq!(claimed_ids, ids);
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn parks_event_after_max_attempts(pool: PgPool) -> anyhow::Result<()> {
+        let repo = OutboxEventRepo::new(pool.clone());
+        let outbox_event = new_outbox_event("outcome");
+        insert(&pool, std::slice::from_ref(&outbox_event)).await?;
+
+        assert_eq!(repo.claim_pending(100).await?.len(), 1);
+        assert!(
+            !repo
+                .record_failure(outbox_event.id, "timeout", 2, 0.0)
+                .await?
+        );
+
+        assert_eq!(repo.claim_pending(100).await?.len(), 1);
+        assert!(
+            repo.record_failure(outbox_event.id, "timeout", 2, 0.0)
+                .await?
+        );
+
+        assert!(repo.claim_pending(100).await?.is_empty());
+        assert!(repo.claim(&[outbox_event.id]).await?.is_empty());
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn failed_event_waits_for_its_backoff(pool: PgPool) -> anyhow::Result<()> {
+        let repo = OutboxEventRepo::new(pool.clone());
+        let outbox_event = new_outbox_event("outcome");
+        insert(&pool, std::slice::from_ref(&outbox_event)).await?;
+
+        assert_eq!(repo.claim_pending(100).await?.len(), 1);
+        assert!(
+            !repo
+                .record_failure(outbox_event.id, "timeout", 10, 60.0)
+                .await?
+        );
+
+        assert!(repo.claim_pending(100).await?.is_empty());
+        assert!(repo.claim(&[outbox_event.id]).await?.is_empty());
+
+        sqlx::query!("UPDATE outbox_events SET lease_expires_at = NOW() - INTERVAL '1 second'")
+            .execute(&pool)
+            .await?;
+        assert_eq!(repo.claim_pending(100).await?.len(), 1);
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn parked_event_is_not_claimed_again(pool: PgPool) -> anyhow::Result<()> {
+        let repo = OutboxEventRepo::new(pool.clone());
+        let outbox_event = new_outbox_event("outcome");
+        insert(&pool, std::slice::from_ref(&outbox_event)).await?;
+
+        assert_eq!(repo.claim_pending(100).await?.len(), 1);
+        repo.park(outbox_event.id, "MalformedDetail: rejected")
+            .await?;
+
+        assert!(repo.claim_pending(100).await?.is_empty());
+        assert!(repo.claim(&[outbox_event.id]).await?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod signing_policies;
+pub mod signing_policy_decisions;
@@ -0,0 +1,184 @@
+use db_types::Wei;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, types::time::OffsetDateTime};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, Clone)]
+pub struct SigningPolicy {
+    pub requester_id: String,
+    pub allow_any_target: bool,
+    pub max_value_wei_per_request: Option<Wei>,
+    pub max_value_wei_per_window: Option<Wei>,
+    pub value_window_sec: Option<i64>,
+    pub max_deadline_horizon_sec: Option<i64>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+/// A target contract a requester may call. `selector` is the lowercase 0x-prefixed 4-byte
+/// function selector, or None to allow every function of the target.
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, Clone)]
+pub struct AllowedCall {
+    pub id: Uuid,
+    pub requester_id: String,
+    pub to_address: String,
+    pub selector: Option<String>,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewSigningPolicy {
+    pub requester_id: String,
+    pub allow_any_target: bool,
+    pub max_value_wei_per_request: Option<Wei>,
+    pub max_value_wei_per_window: Option<Wei>,
+    pub value_window_sec: Option<i64>,
+    pub max_deadline_horizon_sec: Option<i64>,
+}
+
+#[derive(Clone)]
+pub struct SigningPolicyRepo {
+    pub pool: PgPool,
+}
+
+impl SigningPolicyRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_by_requester_id(
+        &self,
+        requester_id: &str,
+    ) -> anyhow::Result<Option<SigningPolicy>> {
+        let policy = sqlx::query_as!(
+            SigningPolicy,
+            r#"
+            SELECT
+                requester_id,
+                allow_any_target,
+                max_value_wei_per_request::TEXT as "max_value_wei_per_request: Wei",
+                max_value_wei_per_window::TEXT as "max_value_wei_per_window: Wei",
+                value_window_sec,
+                max_deadline_horizon_sec,
+                created_at,
+                updated_at
+            FROM
+                signing_policies
+            WHERE
+                requester_id = $1
+            "#,
+            requester_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(policy)
+    }
+
+    pub async fn select_allowed_calls(
+        &self,
+        requester_id: &str,
+    ) -> anyhow::Result<Vec<AllowedCall>> {
+        let allowed_calls = sqlx::query_as!(
+            AllowedCall,
+            r#"
+            SELECT
+                id,
+                requester_id,
+                to_address,
+                selector,
+                created_at
+            FROM
+                signing_policy_allowed_calls
+            WHERE
+                requester_id = $1
+            ORDER BY created_at ASC
+            "#,
+            requester_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(allowed_calls)
+    }
+
+    pub async fn upsert_policy(&self, new_policy: &NewSigningPolicy) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO signing_policies (
+                requester_id,
+                allow_any_target,
+                max_value_wei_per_request,
+                max_value_wei_per_window,
+                value_window_sec,
+                max_deadline_horizon_sec
+            )
+            VALUES ($1, $2, $3::TEXT::NUMERIC, $4::TEXT::NUMERIC, $5, $6)
+            ON CONFLICT (requester_id) DO UPDATE
+            SET
+                allow_any_target = EXCLUDED.allow_any_target,
+                max_value_wei_per_request = EXCLUDED.max_value_wei_per_request,
+                max_value_wei_per_window = EXCLUDED.max_value_wei_per_window,
+                value_window_sec = EXCLUDED.value_window_sec,
+                max_deadline_horizon_sec = EXCLUDED.max_deadline_horizon_sec
+            "#,
+            new_policy.requester_id,
+            new_policy.allow_any_target,
+            new_policy
+                .max_value_wei_per_request
+                .map(|value_wei| value_wei.to_string()),
+            new_policy
+                .max_value_wei_per_window
+                .map(|value_wei| value_wei.to_string()),
+            new_policy.value_window_sec,
+            new_policy.max_deadline_horizon_sec,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Addresses and selectors are stored lowercase so they compare with normalized requests.
+    pub async fn insert_allowed_call(
+        &self,
+        requester_id: &str,
+        to_address: &str,
+        selector: Option<&str>,
+    ) -> anyhow::Result<Uuid> {
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO signing_policy_allowed_calls (
+                id,
+                requester_id,
+                to_address,
+                selector
+            )
+            VALUES ($1, $2, $3, $4)
+            RETURNING id
+            "#,
+            Uuid::new_v4(),
+            requester_id,
+            to_address.to_lowercase(),
+            selector.map(str::to_lowercase),
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    pub async fn delete_allowed_call(&self, id: Uuid) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM signing_policy_allowed_calls
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+use db_types::Wei;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, types::time::OffsetDateTime};
+use uuid::Uuid;
+
+/// Audit record of one policy evaluation. `violations` is empty when the request was allowed.
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, Clone)]
+pub struct SigningPolicyDecision {
+    pub id: Uuid,
+    pub tx_id: String,
+    pub requester_id: String,
+    pub chain_id: i64,
+    pub to_address: String,
+    pub selector: Option<String>,
+    pub value_wei: Wei,
+    pub deadline_timestamp: i64,
+    pub allowed: bool,
+    pub violations: Vec<String>,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewSigningPolicyDecision {
+    pub tx_id: String,
+    pub requester_id: String,
+    pub chain_id: i64,
+    pub to_address: String,
+    pub selector: Option<String>,
+    pub value_wei: Wei,
+    pub deadline_timestamp: i64,
+    pub violations: Vec<String>,
+}
+
+#[derive(Clone)]
+pub struct SigningPolicyDecisionRepo {
+    pub pool: PgPool,
+}
+
+impl SigningPolicyDecisionRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn insert_decision(
+        &self,
+        new_decision: &NewSigningPolicyDecision,
+    ) -> anyhow::Result<Uuid> {
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO signing_policy_decisions (
+                id,
+                tx_id,
+                requester_id,
+                chain_id,
+                to_address,
+                selector,
+                value_wei,
+                deadline_timestamp,
+                allowed,
+                violations
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7::TEXT::NUMERIC, $8, $9, $10)
+            RETURNING id
+            "#,
+            Uuid::new_v4(),
+            new_decision.tx_id,
+            new_decision.requester_id,
+            new_decision.chain_id,
+            new_decision.to_address,
+            new_decision.selector,
+            new_decision.value_wei.to_string(),
+            new_decision.deadline_timestamp,
+            new_decision.violations.is_empty(),
+            &new_decision.violations,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Value of the requester's allowed requests in the last `window_sec` seconds. Each tx_id
+    /// counts once, and `excluded_tx_id` not at all, so re-evaluating a replayed request does
+    /// not count its value twice.
+    pub async fn allowed_value_wei_within(
+        &self,
+        requester_id: &str,
+        window_sec: i64,
+        excluded_tx_id: &str,
+    ) -> anyhow::Result<Wei> {
+        let value_wei = sqlx::query_scalar!(
+            r#"
+            SELECT
+                COALESCE(SUM(value_wei), 0)::TEXT as "value_wei!: Wei"
+            FROM (
+                SELECT DISTINCT ON (tx_id)
+                    value_wei
+                FROM
+                    signing_policy_decisions
+                WHERE
+                    requester_id = $1
+                    AND allowed = TRUE
+                    AND created_at > NOW() - ($2 * INTERVAL '1 second')
+                    AND tx_id <> $3
+                ORDER BY tx_id, created_at DESC
+            ) allowed_decisions
+            "#,
+            requester_id,
+            window_sec as f64,
+            excluded_tx_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(value_wei)
+    }
+
+    pub async fn select_by_tx_id(&self, tx_id: &str) -> anyhow::Result<Vec<SigningPolicyDecision>> {
+        let decisions = sqlx::query_as!(
+            SigningPolicyDecision,
+            r#"
+            SELECT
+                id,
+                tx_id,
+                requester_id,
+                chain_id,
+                to_address,
+                selector,
+                value_wei::TEXT as "value_wei!: Wei",
+                deadline_timestamp,
+                allowed,
+                violations,
+                created_at
+            FROM
+                signing_policy_decisions
+            WHERE
+                tx_id = $1
+            ORDER BY created_at ASC
+            "#,
+            tx_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(decisions)
+    }
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, types::time::OffsetDateTime};
+
+/// JSON ABI of a target contract. `to_address` is stored lowercase.
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, Clone)]
+pub struct TargetAbi {
+    pub chain_id: i64,
+    pub to_address: String,
+    pub abi: String,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Clone)]
+pub struct TargetAbiRepo {
+    pub pool: PgPool,
+}
+
+impl TargetAbiRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn select_by_addresses(
+        &self,
+        chain_id: i64,
+        to_addresses: &[String],
+    ) -> anyhow::Result<Vec<TargetAbi>> {
+        let to_addresses: Vec<String> = to_addresses
+            .iter()
+            .map(|to_address| to_address.to_lowercase())
+            .collect();
+
+        let target_abis = sqlx::query_as!(
+            TargetAbi,
+            r#"
+            SELECT
+                chain_id,
+                to_address,
+                abi::TEXT as "abi!",
+                created_at,
+                updated_at
+            FROM
+                target_abis
+            WHERE
+                chain_id = $1
+                AND to_address = ANY($2)
+            "#,
+            chain_id,
+            &to_addresses
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(target_abis)
+    }
+
+    pub async fn upsert_abi(
+        &self,
+        chain_id: i64,
+        to_address: &str,
+        abi: &str,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO target_abis (chain_id, to_address, abi)
+            VALUES ($1, $2, $3::TEXT::JSONB)
+            ON CONFLICT (chain_id, to_address) DO UPDATE
+            SET
+                abi = EXCLUDED.abi
+            "#,
+            chain_id,
+            to_address.to_lowercase(),
+            abi
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
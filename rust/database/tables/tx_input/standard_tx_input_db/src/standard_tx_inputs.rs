@@ -1,3 +1,4 @@
+use db_types::Wei;
 use serde::{Deserialize, Serialize};
 use sqlx::{PgPool, types::time::OffsetDateTime};
 
@@ -7,7 +8,7 @@ pub struct StandardTxInput {
     pub signature: Vec<u8>,
     pub calldata: Vec<u8>,
     pub to_address: String,
-    pub value_wei: i64,
+    pub value_wei: Wei,
     pub deadline_timestamp: i64,
     pub pass_value_from_operator_wallet: bool,
     pub created_at: OffsetDateTime,
@@ -19,7 +20,7 @@ pub struct NewStandardTxInput {
     pub signature: Vec<u8>,
     pub calldata: Vec<u8>,
     pub to_address: String,
-    pub value_wei: i64,
+    pub value_wei: Wei,
     pub deadline_timestamp: i64,
     pub pass_value_from_operator_wallet: bool,
 }
@@ -41,7 +42,7 @@ impl StandardTxInputRepo {
                 tx_id, 
                 calldata,
                 to_address,
-                value_wei,
+                value_wei::TEXT as "value_wei!: Wei",
                 deadline_timestamp,
                 signature,
                 pass_value_from_operator_wallet,
@@ -1,2 +1,3 @@
+pub mod payload_hash;
 pub mod repo;
 pub mod types;
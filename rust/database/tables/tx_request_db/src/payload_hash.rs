@@ -0,0 +1,58 @@
+use alloy::primitives::keccak256;
+use db_types::{BlobStorageType, Wei};
+use uuid::Uuid;
+
+/// Requester-controlled fields of a standard request apart from the tx_id. `calldata` is
+/// hex without a prefix.
+pub struct StandardPayload<'a> {
+    pub requester_id: &'a str,
+    pub chain_id: i64,
+    pub calldata: &'a str,
+    pub to_address: &'a str,
+    pub value_wei: Wei,
+    pub deadline_timestamp: i64,
+    pub pass_value_from_operator_wallet: bool,
+    pub use_operator_wallet_id: Option<Uuid>,
+    pub metadata: Option<&'a str>,
+}
+
+impl StandardPayload<'_> {
+    pub fn hash(&self) -> anyhow::Result<Vec<u8>> {
+        let payload = serde_json::json!({
+            "requester_id": self.requester_id,
+            "chain_id": self.chain_id,
+            "calldata": self.calldata,
+            "to_address": self.to_address.to_lowercase(),
+            "value_wei": self.value_wei,
+            "deadline_timestamp": self.deadline_timestamp,
+            "pass_value_from_operator_wallet": self.pass_value_from_operator_wallet,
+            "use_operator_wallet_id": self.use_operator_wallet_id,
+            "metadata": self.metadata,
+        });
+        Ok(keccak256(serde_json::to_vec(&payload)?).to_vec())
+    }
+}
+
+/// Requester-controlled fields of a blob request apart from the tx_id.
+pub struct BlobPayload<'a> {
+    pub requester_id: &'a str,
+    pub chain_id: i64,
+    pub deadline_timestamp: i64,
+    pub storage_type: &'a BlobStorageType,
+    pub source_file_path: &'a str,
+    pub use_operator_wallet_id: Option<Uuid>,
+}
+
+impl BlobPayload<'_> {
+    pub fn hash(&self) -> anyhow::Result<Vec<u8>> {
+        let payload = serde_json::json!({
+            "requester_id": self.requester_id,
+            "chain_id": self.chain_id,
+            "deadline_timestamp": self.deadline_timestamp,
+            "storage_type": self.storage_type,
+            "source_file_path": self.source_file_path,
+            "use_operator_wallet_id": self.use_operator_wallet_id,
+        });
+        Ok(keccak256(serde_json::to_vec(&payload)?).to_vec())
+    }
+}
@@ -0,0 +1,702 @@
+use crate::payload_hash::{BlobPayload, StandardPayload};
+use crate::types::{
+    BlobTxRequestRaw, NewTxInput, NewTxRequest, NewTxRequestWithTxInput, StandardTxRequestRaw,
+    TxRequest, TxRequestInsert,
+};
+use anyhow::bail;
+use db_types::{BlobStorageType, TxStatus, TxType, Wei};
+use outbox_db::outbox_events::{NewOutboxEvent, OutboxEventRepo};
+use sqlx::{PgPool, Postgres, Transaction};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone)]
+pub struct TxRequestRepo {
+    pool: PgPool,
+}
+
+impl TxRequestRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores a signed request. A RECEIVED row left by the ingestion gateway is moved to
+    /// SIGNED when its payload matches; any other existing tx_id is left untouched.
+    pub async fn insert_tx_request_with_tx_input(
+        &self,
+        request: &NewTxRequestWithTxInput,
+        outbox_events: &[NewOutboxEvent],
+    ) -> anyhow::Result<TxRequestInsert> {
+        let mut postgres_tx = self.pool.begin().await?;
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO tx_requests (
+                tx_id,
+                requester_id,
+                tx_type,
+                tx_status,
+                chain_id,
+                use_operator_wallet_id,
+                metadata,
+                payload_hash
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (tx_id) DO UPDATE
+            SET
+                tx_status = EXCLUDED.tx_status
+            WHERE
+                tx_requests.tx_status = 'RECEIVED'
+                AND tx_requests.payload_hash = EXCLUDED.payload_hash
+            "#,
+            request.new_tx_request.tx_id,
+            request.new_tx_request.requester_id,
+            request.new_tx_request.tx_type.clone() as TxType,
+            request.new_tx_request.tx_status.clone() as TxStatus,
+            request.new_tx_request.chain_id,
+            request.new_tx_request.use_operator_wallet_id,
+            request.new_tx_request.metadata,
+            request.new_tx_request.payload_hash
+        )
+        .execute(&mut *postgres_tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            postgres_tx.rollback().await?;
+            return self.compare_existing(&request.new_tx_request).await;
+        }
+
+        match &request.tx_input {
+            NewTxInput::Blob(new_blob_tx_input) => {
+                if request.new_tx_request.tx_type != TxType::BLOB {
+                    bail!("Non BLOB TxType: request: {:?}", request)
+                }
+                sqlx::query!(
+                    r#"
+                    INSERT INTO blob_tx_inputs (
+                        tx_id,
+                        signature,
+                        image_id,
+                        commitment,
+                        blob_sha2,
+                        deadline_timestamp,
+                        source_file_path,
+                        storage_type
+                    )
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                    ON CONFLICT (tx_id) DO NOTHING
+                    "#,
+                    request.new_tx_request.tx_id,
+                    new_blob_tx_input.signature,
+                    new_blob_tx_input.image_id,
+                    new_blob_tx_input.commitment,
+                    new_blob_tx_input.blob_sha2,
+                    new_blob_tx_input.deadline_timestamp,
+                    new_blob_tx_input.source_file_path,
+                    new_blob_tx_input.storage_type.clone() as BlobStorageType
+                )
+                .execute(&mut *postgres_tx)
+                .await?;
+            }
+            NewTxInput::Standard(new_standard_tx_input) => {
+                if request.new_tx_request.tx_type != TxType::STANDARD {
+                    bail!("Non STANDARD TxType: request: {:?}", request,)
+                }
+                sqlx::query!(
+                    r#"
+                    INSERT INTO standard_tx_inputs (
+                        tx_id,
+                        signature,
+                        calldata,
+                        to_address,
+                        value_wei,
+                        deadline_timestamp,
+                        pass_value_from_operator_wallet
+                    )
+                    VALUES ($1, $2, $3, $4, $5::TEXT::NUMERIC, $6, $7)
+                    ON CONFLICT (tx_id) DO NOTHING
+                    "#,
+                    request.new_tx_request.tx_id,
+                    new_standard_tx_input.signature,
+                    new_standard_tx_input.calldata,
+                    new_standard_tx_input.to_address,
+                    new_standard_tx_input.value_wei.to_string(),
+                    new_standard_tx_input.deadline_timestamp,
+                    new_standard_tx_input.pass_value_from_operator_wallet
+                )
+                .execute(&mut *postgres_tx)
+                .await?;
+            }
+        }
+
+        OutboxEventRepo::insert_many(&mut postgres_tx, outbox_events).await?;
+
+        postgres_tx.commit().await?;
+        Ok(TxRequestInsert::Inserted)
+    }
+
+    /// Records a request accepted by the ingestion gateway before it is signed. The signer
+    /// moves it to SIGNED once the input is stored.
+    pub async fn insert_received(
+        &self,
+        new_tx_request: &NewTxRequest,
+        outbox_events: &[NewOutboxEvent],
+    ) -> anyhow::Result<TxRequestInsert> {
+        let mut postgres_tx = self.pool.begin().await?;
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO tx_requests (
+                tx_id,
+                requester_id,
+                tx_type,
+                tx_status,
+                chain_id,
+                use_operator_wallet_id,
+                metadata,
+                payload_hash
+            )
+            VALUES ($1, $2, $3, 'RECEIVED', $4, $5, $6, $7)
+            ON CONFLICT (tx_id) DO NOTHING
+            "#,
+            new_tx_request.tx_id,
+            new_tx_request.requester_id,
+            new_tx_request.tx_type.clone() as TxType,
+            new_tx_request.chain_id,
+            new_tx_request.use_operator_wallet_id,
+            new_tx_request.metadata,
+            new_tx_request.payload_hash
+        )
+        .execute(&mut *postgres_tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            postgres_tx.rollback().await?;
+            return self.compare_existing(new_tx_request).await;
+        }
+
+        OutboxEventRepo::insert_many(&mut postgres_tx, outbox_events).await?;
+
+        postgres_tx.commit().await?;
+        Ok(TxRequestInsert::Inserted)
+    }
+
+    /// Requests stored before payload hashes were recorded have no hash to compare against.
+    /// Their hash is computed from the stored row and backfilled, under the same row lock
+    /// as the comparison.
+    async fn compare_existing(
+        &self,
+        new_tx_request: &NewTxRequest,
+    ) -> anyhow::Result<TxRequestInsert> {
+        let mut tx = self.pool.begin().await?;
+
+        let existing = sqlx::query!(
+            r#"
+            SELECT payload_hash
+            FROM tx_requests
+            WHERE tx_id = $1
+            FOR UPDATE
+            "#,
+            new_tx_request.tx_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let payload_hash = match existing.payload_hash {
+            Some(payload_hash) => Some(payload_hash),
+            None => {
+                let payload_hash =
+                    Self::stored_payload_hash(&mut tx, &new_tx_request.tx_id).await?;
+                if let Some(payload_hash) = &payload_hash {
+                    sqlx::query!(
+                        r#"
+                        UPDATE tx_requests
+                        SET
+                            payload_hash = $2
+                        WHERE
+                            tx_id = $1
+                        "#,
+                        new_tx_request.tx_id,
+                        payload_hash
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                payload_hash
+            }
+        };
+
+        tx.commit().await?;
+        let tx_request = self.find_by_tx_id(&new_tx_request.tx_id).await?;
+
+        if payload_hash.as_deref() == Some(new_tx_request.payload_hash.as_slice()) {
+            Ok(TxRequestInsert::Replayed(tx_request))
+        } else {
+            Ok(TxRequestInsert::Conflict(tx_request))
+        }
+    }
+
+    /// Hash of a stored request rebuilt from its row and input. None for a request without
+    /// an input row, whose payload can't be rebuilt.
+    async fn stored_payload_hash(
+        tx: &mut Transaction<'_, Postgres>,
+        tx_id: &str,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                tr.requester_id,
+                tr.chain_id,
+                tr.use_operator_wallet_id,
+                tr.metadata,
+                sti.calldata as "calldata?",
+                sti.to_address as "to_address?",
+                sti.value_wei::TEXT as "value_wei?: Wei",
+                sti.deadline_timestamp as "standard_deadline_timestamp?",
+                sti.pass_value_from_operator_wallet as "pass_value_from_operator_wallet?",
+                bti.deadline_timestamp as "blob_deadline_timestamp?",
+                bti.storage_type as "storage_type?: BlobStorageType",
+                bti.source_file_path as "source_file_path?"
+            FROM tx_requests tr
+            LEFT JOIN standard_tx_inputs sti ON sti.tx_id = tr.tx_id
+            LEFT JOIN blob_tx_inputs bti ON bti.tx_id = tr.tx_id
+            WHERE tr.tx_id = $1
+            "#,
+            tx_id
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        if let (
+            Some(calldata),
+            Some(to_address),
+            Some(value_wei),
+            Some(deadline_timestamp),
+            Some(pass_value_from_operator_wallet),
+        ) = (
+            &row.calldata,
+            &row.to_address,
+            row.value_wei,
+            row.standard_deadline_timestamp,
+            row.pass_value_from_operator_wallet,
+        ) {
+            return StandardPayload {
+                requester_id: &row.requester_id,
+                chain_id: row.chain_id,
+                calldata: &hex::encode(calldata),
+                to_address,
+                value_wei,
+                deadline_timestamp,
+                pass_value_from_operator_wallet,
+                use_operator_wallet_id: row.use_operator_wallet_id,
+                metadata: row.metadata.as_deref(),
+            }
+            .hash()
+            .map(Some);
+        }
+
+        if let (Some(deadline_timestamp), Some(storage_type), Some(source_file_path)) = (
+            row.blob_deadline_timestamp,
+            &row.storage_type,
+            &row.source_file_path,
+        ) {
+            return BlobPayload {
+                requester_id: &row.requester_id,
+                chain_id: row.chain_id,
+                deadline_timestamp,
+                storage_type,
+                source_file_path,
+                use_operator_wallet_id: row.use_operator_wallet_id,
+            }
+            .hash()
+            .map(Some);
+        }
+
+        Ok(None)
+    }
+
+    /// Records a request refused before signing as INVALID, taking over a RECEIVED row
+    /// with the same payload.
+    pub async fn insert_invalid(
+        &self,
+        new_tx_request: &NewTxRequest,
+        outbox_events: &[NewOutboxEvent],
+    ) -> anyhow::Result<TxRequestInsert> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO tx_requests (
+                tx_id,
+                requester_id,
+                tx_type,
+                tx_status,
+                chain_id,
+                use_operator_wallet_id,
+                metadata,
+                payload_hash
+            )
+            VALUES ($1, $2, $3, 'INVALID', $4, $5, $6, $7)
+            ON CONFLICT (tx_id) DO UPDATE
+            SET
+                tx_status = EXCLUDED.tx_status
+            WHERE
+                tx_requests.tx_status = 'RECEIVED'
+                AND tx_requests.payload_hash = EXCLUDED.payload_hash
+            "#,
+            new_tx_request.tx_id,
+            new_tx_request.requester_id,
+            new_tx_request.tx_type.clone() as TxType,
+            new_tx_request.chain_id,
+            new_tx_request.use_operator_wallet_id,
+            new_tx_request.metadata,
+            new_tx_request.payload_hash
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            tx.rollback().await?;
+            return self.compare_existing(new_tx_request).await;
+        }
+
+        OutboxEventRepo::insert_many(&mut tx, outbox_events).await?;
+
+        tx.commit().await?;
+        Ok(TxRequestInsert::Inserted)
+    }
+
+    /// Removes a RECEIVED request together with its undelivered outbox events.
+    pub async fn delete_received(&self, tx_id: &str) -> anyhow::Result<()> {
+        let mut postgres_tx = self.pool.begin().await?;
+
+        let result = sqlx::query!(
+            r#"
+        DELETE FROM tx_requests
+        WHERE
+            tx_id = $1
+            AND tx_status = 'RECEIVED'
+        "#,
+            tx_id
+        )
+        .execute(&mut *postgres_tx)
+        .await?;
+
+        if result.rows_affected() == 1 {
+            sqlx::query!(
+                r#"
+            DELETE FROM outbox_events
+            WHERE
+                tx_id = $1
+                AND delivered_at IS NULL
+            "#,
+                tx_id
+            )
+            .execute(&mut *postgres_tx)
+            .await?;
+        }
+
+        postgres_tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn find_by_tx_id(&self, tx_id: &String) -> anyhow::Result<TxRequest> {
+        let transaction = sqlx::query_as!(
+            TxRequest,
+            r#"
+            SELECT 
+                sequence_id,
+                tx_id, 
+                requester_id, 
+                tx_status as "tx_status: TxStatus", 
+                tx_type as "tx_type: TxType", 
+                chain_id,
+                attempts,
+                use_operator_wallet_id,
+                metadata,
+                created_at,
+                updated_at
+            FROM 
+                tx_requests
+            WHERE
+                tx_id = $1"#,
+            tx_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(transaction)
+    }
+
+    /// Up to `limit` requests of a requester in submission order, after `after_sequence_id`.
+    pub async fn select_by_requester_id(
+        &self,
+        requester_id: &str,
+        after_sequence_id: Option<i64>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<TxRequest>> {
+        let tx_requests = sqlx::query_as!(
+            TxRequest,
+            r#"
+            SELECT
+                sequence_id,
+                tx_id,
+                requester_id,
+                tx_status as "tx_status: TxStatus",
+                tx_type as "tx_type: TxType",
+                chain_id,
+                attempts,
+                use_operator_wallet_id,
+                metadata,
+                created_at,
+                updated_at
+            FROM
+                tx_requests
+            WHERE
+                requester_id = $1
+                AND ($2::BIGINT IS NULL OR sequence_id > $2)
+            ORDER BY sequence_id ASC
+            LIMIT $3"#,
+            requester_id,
+            after_sequence_id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(tx_requests)
+    }
+
+    pub async fn select_and_lock_many_standard(
+        &self,
+        ids: &Vec<String>,
+    ) -> anyhow::Result<Vec<StandardTxRequestRaw>> {
+        let mut tx = self.pool.begin().await?;
+
+        let rows = sqlx::query_as!(
+            StandardTxRequestRaw,
+            r#"
+            WITH selected AS (
+                SELECT tx_id
+                FROM tx_requests
+                WHERE tx_id = ANY($1)
+                  AND tx_status = 'SIGNED'
+                  AND tx_type = 'STANDARD'
+                FOR UPDATE SKIP LOCKED
+            ),
+            updated AS (
+                UPDATE tx_requests t
+                SET
+                    tx_status = 'LOCKED',
+                    attempts = attempts + 1
+                FROM selected
+                WHERE t.tx_id = selected.tx_id
+                RETURNING
+                    t.sequence_id,
+                    t.tx_id,
+                    t.requester_id,
+                    t.tx_type,
+                    t.tx_status,
+                    t.chain_id,
+                    t.use_operator_wallet_id,
+                    t.attempts,
+                    t.metadata,
+                    t.created_at,
+                    t.updated_at
+            )
+            SELECT
+                u.sequence_id,
+                u.tx_id,
+                u.requester_id,
+                u.tx_type as "tx_type: TxType",
+                u.tx_status as "tx_status: TxStatus",
+                u.chain_id,
+                u.use_operator_wallet_id,
+                u.attempts,
+                u.metadata,
+                u.created_at,
+                u.updated_at,
+
+                s.signature,
+                s.calldata,
+                s.to_address,
+                s.value_wei::TEXT as "value_wei!: Wei",
+                s.deadline_timestamp,
+                s.pass_value_from_operator_wallet
+
+            FROM updated u
+            INNER JOIN standard_tx_inputs s ON s.tx_id = u.tx_id
+            "#,
+            ids
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(rows)
+    }
+
+    pub async fn select_and_lock_many_blob(
+        &self,
+        ids: &Vec<String>,
+    ) -> anyhow::Result<Vec<BlobTxRequestRaw>> {
+        let mut tx = self.pool.begin().await?;
+
+        let rows = sqlx::query_as!(
+            BlobTxRequestRaw,
+            r#"
+            WITH selected AS (
+                SELECT tx_id
+                FROM tx_requests
+                WHERE tx_id = ANY($1)
+                  AND tx_status = 'SIGNED'
+                  AND tx_type = 'BLOB'
+                FOR UPDATE SKIP LOCKED
+            ),
+            updated AS (
+                UPDATE tx_requests t
+                SET
+                    tx_status = 'LOCKED',
+                    attempts = attempts + 1
+                FROM selected
+                WHERE t.tx_id = selected.tx_id
+                RETURNING
+                    t.sequence_id,
+                    t.tx_id,
+                    t.requester_id,
+                    t.tx_type,
+                    t.tx_status,
+                    t.chain_id,
+                    t.use_operator_wallet_id,
+                    t.attempts,
+                    t.metadata,
+                    t.created_at,
+                    t.updated_at
+            )
+            SELECT
+                u.sequence_id,
+                u.tx_id,
+                u.requester_id,
+                u.tx_type as "tx_type: TxType",
+                u.tx_status as "tx_status: TxStatus",
+                u.chain_id,
+                u.use_operator_wallet_id,
+                u.attempts,
+                u.metadata,
+                u.created_at,
+                u.updated_at,
+
+                b.signature,
+                b.image_id,
+                b.commitment,
+                b.blob_sha2,
+                b.deadline_timestamp,
+                b.source_file_path,
+                b.storage_type as "storage_type: BlobStorageType"
+
+            FROM updated u
+            INNER JOIN blob_tx_inputs b ON b.tx_id = u.tx_id
+            "#,
+            ids
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(rows)
+    }
+
+    pub async fn release_many(&self, ids: &Vec<String>) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+        UPDATE tx_requests
+        SET 
+            tx_status = 'SIGNED'
+        WHERE tx_id = ANY($1)
+        "#,
+            ids
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn mark_as_invalid(
+        &self,
+        tx_id: &String,
+        outbox_events: &[NewOutboxEvent],
+    ) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+        UPDATE tx_requests
+        SET
+            tx_status = 'INVALID'
+        WHERE tx_id = $1
+        "#,
+            tx_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        OutboxEventRepo::insert_many(&mut tx, outbox_events).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Sets the status and writes the events reporting it in one transaction.
+    pub async fn set_status_for_many(
+        &self,
+        tx_ids: &Vec<String>,
+        tx_status: TxStatus,
+        outbox_events: &[NewOutboxEvent],
+    ) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+        UPDATE tx_requests
+        SET
+            tx_status = $2
+        WHERE tx_id = ANY($1)
+        "#,
+            tx_ids,
+            tx_status as TxStatus
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        OutboxEventRepo::insert_many(&mut tx, outbox_events).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn mark_many_as_broadcasted_and_bump_attempts(
+        &self,
+        tx_ids: &Vec<String>,
+        outbox_events: &[NewOutboxEvent],
+    ) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+        UPDATE tx_requests
+        SET
+            tx_status = $2,
+            attempts = attempts + 1
+        WHERE tx_id = ANY($1)
+        "#,
+            tx_ids,
+            TxStatus::BROADCASTED as TxStatus
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        OutboxEventRepo::insert_many(&mut tx, outbox_events).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        payload_hash::StandardPayload,
+        repo::TxRequestRepo,
+        types::{NewTxInput, NewTxRequest, NewTxRequestWithTxInput, TxRequestInsert},
+    };
+    use db_types::{TxStatus, TxType, Wei};
+    use sqlx::PgPool;
+    use standard_tx_input_db::standard_tx_inputs::NewStandardTxInput;
+
+    const CHAIN_ID: i64 = 31337;
+    const TX_ID: &str = "legacy-tx";
+
+    fn payload_hash(value_wei: u64) -> Vec<u8> {
+        StandardPayload {
+            requester_id: "requester",
+            chain_id: CHAIN_ID,
+            calldata: "abcd",
+            to_address: "0xAbC",
+            value_wei: Wei::from(value_wei),
+            deadline_timestamp: 1_000,
+            pass_value_from_operator_wallet: false,
+            use_operator_wallet_id: None,
+            metadata: None,
+        }
+        .hash()
+        .unwrap()
+    }
+
+    fn new_tx_request(payload_hash: Vec<u8>) -> NewTxRequest {
+        NewTxRequest {
+            tx_id: TX_ID.to_string(),
+            requester_id: "requester".to_string(),
+            tx_type: TxType::STANDARD,
+            tx_status: TxStatus::SIGNED,
+            chain_id: CHAIN_ID,
+            use_operator_wallet_id: None,
+            metadata: None,
+            payload_hash,
+        }
+    }
+
+    /// Stores a standard request with value_wei 1 and clears its hash, like a row stored
+    /// before payload hashes were recorded.
+    async fn insert_legacy_request(pool: &PgPool) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+        INSERT INTO networks (
+            chain_id,
+            chain_name,
+            rpc_url,
+            contract_address,
+            min_operator_wallet_balance,
+            gas_estimation_buffer_ppm,
+            blob_gas_estimation_buffer_ppm
+        )
+        VALUES ($1, 'anvil', 'http://127.0.0.1:8545', '0x00', 0, 0, 0)
+        "#,
+            CHAIN_ID
+        )
+        .execute(pool)
+        .await?;
+
+        let inserted = TxRequestRepo::new(pool.clone())
+            .insert_tx_request_with_tx_input(
+                &NewTxRequestWithTxInput {
+                    new_tx_request: new_tx_request(payload_hash(1)),
+                    tx_input: NewTxInput::Standard(NewStandardTxInput {
+                        tx_id: TX_ID.to_string(),
+                        signature: vec![0; 65],
+                        calldata: vec![0xab, 0xcd],
+                        to_address: "0xAbC".to_string(),
+                        value_wei: Wei::from(1),
+                        deadline_timestamp: 1_000,
+                        pass_value_from_operator_wallet: false,
+                    }),
+                },
+                &[],
+            )
+            .await?;
+        assert!(matches!(inserted, TxRequestInsert::Inserted));
+
+        sqlx::query!(
+            "UPDATE tx_requests SET payload_hash = NULL WHERE tx_id = $1",
+            TX_ID
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn stored_payload_hash(pool: &PgPool) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(sqlx::query_scalar!(
+            "SELECT payload_hash FROM tx_requests WHERE tx_id = $1",
+            TX_ID
+        )
+        .fetch_one(pool)
+        .await?)
+    }
+
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn legacy_request_resubmitted_with_other_payload_conflicts(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        insert_legacy_request(&pool).await?;
+        let repo = TxRequestRepo::new(pool.clone());
+
+        let resubmitted = repo
+            .insert_received(&new_tx_request(payload_hash(2)), &[])
+            .await?;
+
+        assert!(matches!(resubmitted, TxRequestInsert::Conflict(_)));
+        assert_eq!(stored_payload_hash(&pool).await?, Some(payload_hash(1)));
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn legacy_request_resubmitted_with_same_payload_replays(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        insert_legacy_request(&pool).await?;
+        let repo = TxRequestRepo::new(pool.clone());
+
+        let resubmitted = repo
+            .insert_received(&new_tx_request(payload_hash(1)), &[])
+            .await?;
+
+        assert!(matches!(resubmitted, TxRequestInsert::Replayed(_)));
+        assert_eq!(stored_payload_hash(&pool).await?, Some(payload_hash(1)));
+        Ok(())
+    }
+}
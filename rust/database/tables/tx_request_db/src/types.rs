@@ -1,6 +1,6 @@
 use anyhow::bail;
-use blob_tx_input_db::blob_tx_inputs::NewBlobTxInput;
-use db_types::{BlobStorageType, TxStatus, TxType};
+use blob_tx_input_db::blob_tx_inputs::{BlobTxInput, NewBlobTxInput};
+use db_types::{BlobStorageType, TxStatus, TxType, Wei};
 use serde::{Deserialize, Serialize};
 use standard_tx_input_db::standard_tx_inputs::{NewStandardTxInput, StandardTxInput};
 use time::OffsetDateTime;
@@ -24,7 +24,7 @@ pub struct StandardTxRequestRaw {
     pub signature: Vec<u8>,
     pub calldata: Vec<u8>,
     pub to_address: String,
-    pub value_wei: i64,
+    pub value_wei: Wei,
     pub deadline_timestamp: i64,
     pub pass_value_from_operator_wallet: bool,
 }
@@ -39,6 +39,7 @@ pub struct BlobTxRequestRaw {
     pub chain_id: i64,
     pub use_operator_wallet_id: Option<Uuid>,
     pub attempts: i32,
+    pub metadata: Option<String>,
     pub created_at: OffsetDateTime,
     pub updated_at: OffsetDateTime,
 
@@ -63,6 +64,16 @@ pub struct TxRequestWithInput {
     pub use_operator_wallet_id: Option<Uuid>,
 }
 
+impl TxRequestWithInput {
+    /// value_wei of a standard request, None for blob requests.
+    pub fn value_wei(&self) -> Option<Wei> {
+        match &self.tx_input {
+            TxInput::Standard(standard_tx_input) => Some(standard_tx_input.value_wei),
+            TxInput::Blob(_) => None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow, Clone)]
 pub struct TxRequest {
     pub sequence_id: i64,
@@ -87,6 +98,18 @@ pub struct NewTxRequest {
     pub chain_id: i64,
     pub use_operator_wallet_id: Option<Uuid>,
     pub metadata: Option<String>,
+    /// Hash of the requester-controlled payload, compared when the tx_id already exists.
+    pub payload_hash: Vec<u8>,
+}
+
+/// Result of storing a request under a tx_id that may already exist.
+#[derive(Debug)]
+pub enum TxRequestInsert {
+    Inserted,
+    /// The stored request carries the same payload. Nothing was written.
+    Replayed(TxRequest),
+    /// The stored request carries a different payload. Nothing was written.
+    Conflict(TxRequest),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -115,7 +138,7 @@ impl IntoTxRequestWithInput for StandardTxRequestRaw {
                 signature: self.signature.clone(),
                 calldata: self.calldata.clone(),
                 to_address: self.to_address.clone(),
-                value_wei: self.value_wei.clone(),
+                value_wei: self.value_wei,
                 deadline_timestamp: self.deadline_timestamp.clone(),
                 pass_value_from_operator_wallet: self.pass_value_from_operator_wallet.clone(),
                 created_at: self.created_at.clone(),
@@ -133,3 +156,32 @@ impl IntoTxRequestWithInput for StandardTxRequestRaw {
         }
     }
 }
+
+impl IntoTxRequestWithInput for BlobTxRequestRaw {
+    fn into_tx_request_with_input(&self) -> anyhow::Result<TxRequestWithInput> {
+        if self.tx_type == TxType::STANDARD {
+            bail!("Trying to parse BlobTxRequestRaw into STANDARD tx");
+        }
+        let tx_input = TxInput::Blob(BlobTxInput {
+            tx_id: self.tx_id.clone(),
+            signature: self.signature.clone(),
+            image_id: self.image_id.clone(),
+            commitment: self.commitment.clone(),
+            blob_sha2: self.blob_sha2.clone(),
+            deadline_timestamp: self.deadline_timestamp,
+            source_file_path: self.source_file_path.clone(),
+            storage_type: self.storage_type.clone(),
+            created_at: self.created_at,
+        });
+        Ok(TxRequestWithInput {
+            tx_id: self.tx_id.clone(),
+            requester_id: self.requester_id.clone(),
+            tx_type: self.tx_type.clone(),
+            tx_status: self.tx_status.clone(),
+            attempts: self.attempts,
+            tx_input,
+            metadata: self.metadata.clone(),
+            use_operator_wallet_id: self.use_operator_wallet_id,
+        })
+    }
+}
@@ -49,6 +49,31 @@ impl WalletAssignmentRepo {
         Ok(transaction_assignment)
     }
 
+    pub async fn select_by_tx_ids(
+        &self,
+        tx_ids: &[String],
+    ) -> anyhow::Result<Vec<WalletAssignment>> {
+        let wallet_assignments = sqlx::query_as!(
+            WalletAssignment,
+            r#"
+            SELECT
+                id,
+                tx_id,
+                operator_wallet_id,
+                created_at,
+                updated_at
+            FROM
+                wallet_assignments
+            WHERE
+                tx_id = ANY($1)
+            ORDER BY created_at ASC"#,
+            tx_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(wallet_assignments)
+    }
+
     pub async fn new_assignment(
         &self,
         tx_id: String,
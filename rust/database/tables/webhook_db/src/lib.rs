@@ -0,0 +1,2 @@
+pub mod webhook_deliveries;
+pub mod webhook_endpoints;
@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, types::time::OffsetDateTime};
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, Clone)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub requester_id: String,
+    pub url: String,
+    pub event_type: String,
+    pub tx_id: Option<String>,
+    pub payload: String,
+    pub attempt: i32,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub delivered: bool,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewWebhookDelivery {
+    pub requester_id: String,
+    pub url: String,
+    pub event_type: String,
+    pub tx_id: Option<String>,
+    pub payload: String,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub delivered: bool,
+}
+
+#[derive(Clone)]
+pub struct WebhookDeliveryRepo {
+    pub pool: PgPool,
+}
+
+impl WebhookDeliveryRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Records a delivery attempt, numbered after the earlier attempts of the same payload.
+    pub async fn insert(&self, delivery: &NewWebhookDelivery) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO webhook_deliveries (
+                requester_id,
+                url,
+                event_type,
+                tx_id,
+                payload,
+                attempt,
+                status_code,
+                error,
+                delivered
+            )
+            SELECT
+                $1,
+                $2,
+                $3,
+                $4,
+                $5::TEXT::JSONB,
+                COALESCE(MAX(wd.attempt), 0) + 1,
+                $6,
+                $7,
+                $8
+            FROM
+                webhook_deliveries wd
+            WHERE
+                wd.tx_id IS NOT DISTINCT FROM $4
+                AND wd.requester_id = $1
+                AND wd.event_type = $3
+                AND wd.payload = $5::TEXT::JSONB
+            "#,
+            delivery.requester_id,
+            delivery.url,
+            delivery.event_type,
+            delivery.tx_id,
+            delivery.payload,
+            delivery.status_code,
+            delivery.error,
+            delivery.delivered
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn select_by_tx_id(&self, tx_id: &str) -> anyhow::Result<Vec<WebhookDelivery>> {
+        let deliveries = sqlx::query_as!(
+            WebhookDelivery,
+            r#"
+            SELECT
+                id,
+                requester_id,
+                url,
+                event_type,
+                tx_id,
+                payload::TEXT as "payload!",
+                attempt,
+                status_code,
+                error,
+                delivered,
+                created_at
+            FROM
+                webhook_deliveries
+            WHERE
+                tx_id = $1
+            ORDER BY
+                id
+            "#,
+            tx_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+}
@@ -0,0 +1,91 @@
+use anyhow::ensure;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, types::time::OffsetDateTime};
+
+/// Events are only posted over TLS.
+pub fn check_endpoint_url(url: &str) -> anyhow::Result<()> {
+    ensure!(
+        url.starts_with("https://"),
+        "Webhook URL {url} does not start with https://"
+    );
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, Clone)]
+pub struct WebhookEndpoint {
+    pub requester_id: String,
+    pub url: String,
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub enabled: bool,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Clone)]
+pub struct WebhookEndpointRepo {
+    pub pool: PgPool,
+}
+
+impl WebhookEndpointRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Enabled endpoint of the requester, if any.
+    pub async fn find_enabled(
+        &self,
+        requester_id: &str,
+    ) -> anyhow::Result<Option<WebhookEndpoint>> {
+        let webhook_endpoint = sqlx::query_as!(
+            WebhookEndpoint,
+            r#"
+            SELECT
+                requester_id,
+                url,
+                secret,
+                enabled,
+                created_at,
+                updated_at
+            FROM
+                webhook_endpoints
+            WHERE
+                requester_id = $1
+                AND enabled
+            "#,
+            requester_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(webhook_endpoint)
+    }
+
+    pub async fn upsert_endpoint(
+        &self,
+        requester_id: &str,
+        url: &str,
+        secret: &str,
+    ) -> anyhow::Result<()> {
+        check_endpoint_url(url)?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO webhook_endpoints (requester_id, url, secret)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (requester_id) DO UPDATE
+            SET
+                url = EXCLUDED.url,
+                secret = EXCLUDED.secret,
+                enabled = TRUE
+            "#,
+            requester_id,
+            url,
+            secret
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
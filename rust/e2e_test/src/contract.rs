@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use alloy::{
-    primitives::{Address, Uint},
+    primitives::{Address, U256},
     providers::{Provider, ProviderBuilder},
 };
 use anyhow::bail;
@@ -46,7 +46,7 @@ impl ContractManagerForTests for ContractManager {
             &provider,
         );
 
-        let tx_value = Uint::<256, 4>::from(tx_context.batch_tx_value);
+        let tx_value: U256 = tx_context.batch_tx_value.into();
 
         let mut fees = provider.estimate_eip1559_fees().await?;
         fees.max_fee_per_gas = 3;
@@ -69,8 +69,12 @@ impl ContractManagerForTests for ContractManager {
 
         let tx_hash = pending_tx.tx_hash().to_string();
 
-        let new_execution_attempt =
-            NewExecutionAttempt::standard_successful(tx_context, wallet.db_record.id, None)?;
+        let new_execution_attempt = NewExecutionAttempt::standard_successful(
+            tx_context,
+            wallet.db_record.id,
+            wallet.db_record.lease_owner.clone(),
+            None,
+        )?;
 
         Ok(new_execution_attempt)
     }
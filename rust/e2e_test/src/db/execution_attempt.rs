@@ -1,4 +1,4 @@
-use db_types::{TxExecutionOutcome, TxType};
+use db_types::{TxExecutionOutcome, TxType, Wei};
 use execution_attempt_db::execution_attempts::{ExecutionAttempt, ExecutionAttemptRepo};
 use uuid::Uuid;
 
@@ -25,7 +25,7 @@ impl ExecutionAttemptTestExt for ExecutionAttemptRepo {
             operator_wallet_id,
             tx_type as "tx_type: TxType",
             nonce_used,
-            tx_value,
+            tx_value::TEXT as "tx_value!: Wei",
             tx_hash,
             gas_limit,
             used_gas,
@@ -60,7 +60,7 @@ impl ExecutionAttemptTestExt for ExecutionAttemptRepo {
             ea.operator_wallet_id,
             ea.tx_type as "tx_type: TxType",
             ea.nonce_used,
-            ea.tx_value,
+            ea.tx_value::TEXT as "tx_value!: Wei",
             ea.tx_hash,
             ea.gas_limit,
             ea.used_gas,
@@ -1,3 +1,4 @@
+use db_types::WalletSelectionStrategy;
 use network_db::networks::{NetworkRepo, NewNetwork};
 
 #[allow(async_fn_in_trait)]
@@ -18,6 +19,15 @@ impl AnvilTestNetwork for NetworkRepo {
             blob_gas_estimation_buffer_ppm: 200_000_000,
             max_retry_attempts: 3,
             tx_max_age_sec: 3600,
+            max_fee_per_gas_ceiling: None,
+            max_fee_per_blob_gas_ceiling: None,
+            operator_wallet_balance_warning_level: None,
+            wallet_selection_strategy: WalletSelectionStrategy::LeastRecentlyUsed,
+            max_in_flight_txs_per_wallet: 1,
+            max_batch_items: None,
+            max_batch_gas_ppm: None,
+            max_batch_value_wei: None,
+            max_blobs_per_tx: None,
         })
         .await?;
         Ok(())
@@ -12,6 +12,7 @@ use execution_attempt_db::execution_attempts::ExecutionAttemptRepo;
 use execution_attempt_item_db::execution_attempt_items::ExecutionAttemptItemRepo;
 use network_db::networks::NetworkRepo;
 use operator_wallet_db::operator_wallets::OperatorWalletRepo;
+use signing_policy_db::signing_policies::{NewSigningPolicy, SigningPolicyRepo};
 use sqlx::PgPool;
 use standard_tx_input_db::standard_tx_inputs::StandardTxInputRepo;
 use tokio::sync::OnceCell;
@@ -34,6 +35,7 @@ pub struct DbRepositories {
 pub struct TestOrchestrators {
     pub standard_tx_sender_orchestrator:
         standard_tx_sender::orchestrator::aws::AwsLambdaOrchestrator,
+    pub blob_tx_sender_orchestrator: blob_tx_sender::orchestrator::aws::AwsLambdaOrchestrator,
     pub receipt_poller_orchestrator: receipt_poller::orchestrator::aws::AwsLambdaOrchestrator,
     pub retry_handler_orchestrator: retry_handler::orchestrator::aws::AwsLambdaOrchestrator,
 }
@@ -94,6 +96,14 @@ pub async fn get_e2e_test_fixture() -> &'static E2eTestFixture {
                     .await
                     .unwrap(),
 
+                blob_tx_sender_orchestrator:
+                    blob_tx_sender::orchestrator::aws::AwsLambdaOrchestrator::build(
+                        &pool,
+                        &aws_config,
+                    )
+                    .await
+                    .unwrap(),
+
                 receipt_poller_orchestrator:
                     receipt_poller::orchestrator::aws::AwsLambdaOrchestrator::build(
                         &pool,
@@ -166,6 +176,17 @@ async fn build_db_repositories(
         .insert_from_mnemonic(&env_vars.anvil_mnemonic, env_vars.anvil_chain_id, 5)
         .await
         .unwrap();
+    SigningPolicyRepo::new(pool.clone())
+        .upsert_policy(&NewSigningPolicy {
+            requester_id: "requester-1".to_string(),
+            allow_any_target: true,
+            max_value_wei_per_request: None,
+            max_value_wei_per_window: None,
+            value_window_sec: None,
+            max_deadline_horizon_sec: None,
+        })
+        .await
+        .unwrap();
 
     Ok(DbRepositories {
         network_repo,
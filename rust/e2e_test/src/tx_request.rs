@@ -1,5 +1,5 @@
 use alloy::primitives::Address;
-use db_types::BlobStorageType;
+use db_types::{BlobStorageType, Wei};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tx_request::{blob_tx::BlobTxRequestBody, standard::StandardTxRequestBody};
 use uuid::Uuid;
@@ -9,7 +9,7 @@ pub struct StandardTxRequestBodyOptional {
     pub requester_id: Option<String>,
     pub calldata: Option<String>,
     pub to_address: Option<String>,
-    pub value_wei: Option<i64>,
+    pub value_wei: Option<Wei>,
     pub chain_id: i64,
     pub deadline_timestamp: Option<i64>,
     pub pass_value_from_operator_wallet: Option<bool>,
@@ -54,7 +54,7 @@ impl StandardTxRequestBodyForTest for StandardTxRequestBody {
             requester_id: input.requester_id.unwrap_or("requester-1".to_string()),
             calldata: input.calldata.unwrap_or(default_tx),
             to_address: input.to_address.unwrap_or(random_address.to_string()),
-            value_wei: input.value_wei.unwrap_or(0),
+            value_wei: input.value_wei.unwrap_or_default(),
             chain_id: input.chain_id,
             deadline_timestamp: input
                 .deadline_timestamp
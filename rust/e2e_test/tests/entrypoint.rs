@@ -7,7 +7,10 @@ use crate::{
         happy_path_two_blob_tx::happy_path_two_blob_tx,
     },
     standard_tx::{
-        fail_path::expired_standard_tx::expired_standard_tx,
+        fail_path::{
+            expired_standard_tx::expired_standard_tx,
+            policy_denied_standard_tx::policy_denied_standard_tx,
+        },
         happy_path::happy_path_single_standard_tx::happy_path_single_standard_tx,
         retry_path::{
             retry_path_standard_dropped::retry_path_standard_dropped,
@@ -22,6 +25,7 @@ use e2e_test::fixture::get_e2e_test_fixture;
 async fn e2e_tests() -> anyhow::Result<()> {
     let e2e_test_fixture = get_e2e_test_fixture().await;
     expired_standard_tx(e2e_test_fixture).await?;
+    policy_denied_standard_tx(e2e_test_fixture).await?;
     happy_path_single_standard_tx(e2e_test_fixture).await?;
     happy_path_single_standard_tx(e2e_test_fixture).await?;
     happy_path_single_blob_tx(e2e_test_fixture).await?;
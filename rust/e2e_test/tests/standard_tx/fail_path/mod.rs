@@ -1 +1,2 @@
 pub mod expired_standard_tx;
+pub mod policy_denied_standard_tx;
@@ -0,0 +1,66 @@
+use db_types::TxStatus;
+use e2e_test::{
+    aws::sqs::{
+        event::{TestEventMessage, build_lambda_sqs_event},
+        test_queue::SqsQueueTester,
+    },
+    fixture::E2eTestFixture,
+    tx_request::{StandardTxRequestBodyForTest, StandardTxRequestBodyOptional},
+};
+use signing_policy_db::signing_policy_decisions::SigningPolicyDecisionRepo;
+use tx_request::standard::StandardTxRequestBody;
+
+pub async fn policy_denied_standard_tx(e2e_test_fixture: &E2eTestFixture) -> anyhow::Result<()> {
+    let mut tx_request_body_optional =
+        StandardTxRequestBodyOptional::default(e2e_test_fixture.env_vars.anvil_chain_id);
+    tx_request_body_optional.requester_id = Some("requester-without-policy".to_string());
+
+    let tx_request_body = StandardTxRequestBody::test_build(tx_request_body_optional)?;
+
+    let tx_request_event = build_lambda_sqs_event(vec![TestEventMessage::new(
+        &tx_request_body.to_string(),
+        None,
+    )])?;
+
+    standard_tx_signer::aws_lambda::function_handler(
+        tx_request_event,
+        &e2e_test_fixture.pool,
+        &e2e_test_fixture.aws_config,
+    )
+    .await
+    .unwrap();
+
+    // Requesters without a signing policy are refused and the decision is audited
+
+    let tx_request = e2e_test_fixture
+        .db_repositories
+        .tx_request_repo
+        .find_by_tx_id(&tx_request_body.tx_id)
+        .await?;
+    assert_eq!(tx_request.tx_status, TxStatus::INVALID);
+
+    let decisions = SigningPolicyDecisionRepo::new(e2e_test_fixture.pool.clone())
+        .select_by_tx_id(&tx_request_body.tx_id)
+        .await?;
+    assert_eq!(decisions.len(), 1);
+    assert!(!decisions[0].allowed);
+    assert_eq!(
+        decisions[0].violations,
+        vec!["no signing policy for requester requester-without-policy".to_string()]
+    );
+
+    let outcome_queue_event = e2e_test_fixture
+        .test_queue_manager
+        .tx_outcome_queue
+        .receive_messages(10)
+        .await?;
+
+    assert!(outcome_queue_event.payload.records.iter().any(|record| {
+        record
+            .body
+            .as_deref()
+            .is_some_and(|body| body.contains(&tx_request_body.tx_id) && body.contains("INVALID"))
+    }));
+
+    Ok(())
+}
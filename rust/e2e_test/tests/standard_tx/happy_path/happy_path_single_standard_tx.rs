@@ -4,6 +4,7 @@ use e2e_test::{
         event::{TestEventMessage, build_lambda_sqs_event},
         test_queue::SqsQueueTester,
     },
+    db::execution_attempt::ExecutionAttemptTestExt,
     fixture::E2eTestFixture,
     tx_request::{StandardTxRequestBodyForTest, StandardTxRequestBodyOptional},
 };
@@ -92,6 +93,22 @@ pub async fn happy_path_single_standard_tx(
 
     println!("receipt was found: {receipt_found}");
 
+    let execution_attempts = e2e_test_fixture
+        .db_repositories
+        .execution_attempt_repo
+        .find_by_tx_id(&tx_request_body.tx_id)
+        .await?;
+    let execution_attempt_items = e2e_test_fixture
+        .db_repositories
+        .execution_attempt_item_repo
+        .select_by_execution_attempt_id(execution_attempts[0].id)
+        .await?;
+
+    // Executed log of the item was matched back to the tx_id
+    assert_eq!(execution_attempt_items.len(), 1);
+    assert_eq!(execution_attempt_items[0].executed, Some(true));
+    assert!(execution_attempt_items[0].log_index.is_some());
+
     let outcome_queue_event = e2e_test_fixture
         .test_queue_manager
         .tx_outcome_queue
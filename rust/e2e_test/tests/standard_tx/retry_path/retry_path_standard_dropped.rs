@@ -63,7 +63,7 @@ pub async fn retry_path_standard_dropped(e2e_test_fixture: &E2eTestFixture) -> a
         .orchestrators
         .standard_tx_sender_orchestrator
         .wallet_pool_manager
-        .acquire(chain_id, None)
+        .acquire(chain_id, None, None)
         .await?
         .unwrap();
 
@@ -0,0 +1,30 @@
+#![cfg(feature = "aws")]
+use aws_config::{BehaviorVersion, meta::region::RegionProviderChain};
+use ingestion_gateway::{
+    Config, intake::IngestionGateway, orchestrator::aws::AwsLambdaOrchestrator,
+};
+use lambda_runtime::{run, service_fn, tracing};
+use sqlx::PgPool;
+
+#[tokio::main]
+async fn main() -> Result<(), lambda_runtime::Error> {
+    tracing::init_default_subscriber();
+    tracing::info!("Cold start");
+
+    let region_provider = RegionProviderChain::default_provider().or_else("us-east-1");
+    let aws_config = aws_config::defaults(BehaviorVersion::latest())
+        .region(region_provider)
+        .load()
+        .await;
+
+    let config = Config::build()?;
+    let pool = PgPool::connect(&config.database_url).await?;
+
+    let aws_lambda_orchestrator =
+        AwsLambdaOrchestrator::build(IngestionGateway::build(&config, &pool, &aws_config)?);
+
+    run(service_fn(|event| {
+        aws_lambda_orchestrator.function_handler(event)
+    }))
+    .await
+}
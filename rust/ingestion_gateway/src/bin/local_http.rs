@@ -0,0 +1,29 @@
+#![cfg(feature = "local")]
+use aws_config::{BehaviorVersion, meta::region::RegionProviderChain};
+use ingestion_gateway::{Config, intake::IngestionGateway, orchestrator::local::build_router};
+use sqlx::PgPool;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let config = Config::build()?;
+    let bind_address = Config::get_env_var("INGESTION_GATEWAY_BIND_ADDRESS");
+    let pool = PgPool::connect(&config.database_url).await?;
+
+    let region_provider = RegionProviderChain::default_provider().or_else("us-east-1");
+    let aws_config = aws_config::defaults(BehaviorVersion::latest())
+        .region(region_provider)
+        .load()
+        .await;
+
+    let listener = tokio::net::TcpListener::bind(&bind_address).await?;
+    tracing::info!("Ingestion gateway listening on {bind_address}");
+    axum::serve(
+        listener,
+        build_router(IngestionGateway::build(&config, &pool, &aws_config)?),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,313 @@
+use crate::{
+    Config,
+    validation::{ValidationError, parse_body, validate_blob, validate_standard},
+};
+use alloy::primitives::{Address, B256};
+use blob_storage::storage::s3::S3BlobStorageManager;
+use db_types::{BlobStorageType, TxStatus, TxType};
+use network_db::networks::NetworkRepo;
+use outcome_emitter::{lifecycle::LifecycleEvent, outbox::to_outbox_events};
+use serde::Serialize;
+use sqs_queue::{message_body::ToJsonString, queue::SqsQueue};
+use std::{
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tx_request::{blob_tx::BlobTxRequestBody, standard::StandardTxRequestBody};
+use tx_request_db::{
+    repo::TxRequestRepo,
+    types::{NewTxRequest, TxRequestInsert},
+};
+
+/// Intake served over HTTP: `POST /tx-requests/standard` and `POST /tx-requests/blob`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntakeRequest {
+    Standard(String),
+    Blob(String),
+}
+
+impl IntakeRequest {
+    pub fn build(tx_type: &str, body: Option<&str>) -> Option<Self> {
+        let body = body.unwrap_or_default().to_string();
+        match tx_type {
+            "standard" => Some(Self::Standard(body)),
+            "blob" => Some(Self::Blob(body)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AcceptedTxRequest {
+    pub tx_id: String,
+    pub tx_status: TxStatus,
+    /// EIP-712 digest the signer will sign for this request.
+    pub eip712_digest: B256,
+}
+
+pub struct IntakeResponse {
+    pub status_code: u16,
+    pub body: String,
+}
+
+impl IntakeResponse {
+    fn json<T: Serialize>(status_code: u16, body: &T) -> anyhow::Result<Self> {
+        Ok(Self {
+            status_code,
+            body: serde_json::to_string(body)?,
+        })
+    }
+
+    fn errors(status_code: u16, errors: &[ValidationError]) -> anyhow::Result<Self> {
+        Self::json(status_code, &serde_json::json!({ "errors": errors }))
+    }
+
+    pub fn internal_error() -> anyhow::Result<Self> {
+        Self::json(
+            500,
+            &serde_json::json!({ "error": "Internal server error" }),
+        )
+    }
+
+    pub fn not_found() -> anyhow::Result<Self> {
+        Self::json(
+            404,
+            &serde_json::json!({ "error": "Expected POST /tx-requests/standard or /tx-requests/blob" }),
+        )
+    }
+}
+
+struct BlobIntake {
+    signer_queue: SqsQueue,
+    storage_manager: S3BlobStorageManager,
+}
+
+pub struct IngestionGateway {
+    tx_request_repo: TxRequestRepo,
+    network_repo: NetworkRepo,
+    standard_signer_queue: SqsQueue,
+    blob_intake: Option<BlobIntake>,
+    signer_address: Address,
+}
+
+impl IngestionGateway {
+    pub fn build(
+        config: &Config,
+        pool: &sqlx::Pool<sqlx::Postgres>,
+        aws_config: &aws_config::SdkConfig,
+    ) -> anyhow::Result<Self> {
+        let sqs_client = aws_sdk_sqs::Client::new(aws_config);
+
+        let standard_signer_queue = SqsQueue::build(
+            &sqs_client,
+            &config.standard_signer_queue_url,
+            &config.standard_signer_queue_message_group_id,
+        )?;
+
+        let blob_intake = match (
+            &config.blob_signer_queue_url,
+            &config.blob_signer_queue_message_group_id,
+            &config.blob_storage_bucket_name,
+        ) {
+            (Some(queue_url), Some(message_group_id), Some(bucket_name)) => Some(BlobIntake {
+                signer_queue: SqsQueue::build(&sqs_client, queue_url, message_group_id)?,
+                storage_manager: S3BlobStorageManager::build(aws_config, bucket_name),
+            }),
+            _ => None,
+        };
+
+        Ok(Self {
+            tx_request_repo: TxRequestRepo::new(pool.clone()),
+            network_repo: NetworkRepo::new(pool.clone()),
+            standard_signer_queue,
+            blob_intake,
+            signer_address: Address::from_str(&config.signer_address)?,
+        })
+    }
+
+    /// Validates, persists and enqueues the request the same way for every transport.
+    pub async fn handle_intake_request(
+        &self,
+        request: Option<IntakeRequest>,
+    ) -> anyhow::Result<IntakeResponse> {
+        match request {
+            Some(IntakeRequest::Standard(body)) => self.accept_standard(&body).await,
+            Some(IntakeRequest::Blob(body)) => self.accept_blob(&body).await,
+            None => IntakeResponse::not_found(),
+        }
+    }
+
+    async fn accept_standard(&self, body: &str) -> anyhow::Result<IntakeResponse> {
+        let tx_request_body = match parse_body::<StandardTxRequestBody>(body) {
+            Ok(tx_request_body) => tx_request_body,
+            Err(error) => return IntakeResponse::errors(400, &[error]),
+        };
+
+        let errors = validate_standard(&tx_request_body, &self.chain_ids().await?, now());
+        if !errors.is_empty() {
+            return IntakeResponse::errors(422, &errors);
+        }
+
+        let eip712_digest =
+            standard_tx_signer::signature::signing_digest(&tx_request_body, self.signer_address)?;
+
+        let new_tx_request = NewTxRequest {
+            tx_id: tx_request_body.tx_id.clone(),
+            requester_id: tx_request_body.requester_id.clone(),
+            tx_type: TxType::STANDARD,
+            tx_status: TxStatus::RECEIVED,
+            chain_id: tx_request_body.chain_id,
+            use_operator_wallet_id: tx_request_body.use_operator_wallet_id,
+            metadata: tx_request_body.metadata.clone(),
+            payload_hash: tx_request_body.payload_hash()?,
+        };
+
+        self.persist_and_enqueue(
+            &new_tx_request,
+            &self.standard_signer_queue,
+            &tx_request_body.to_json_string()?,
+            eip712_digest,
+        )
+        .await
+    }
+
+    async fn accept_blob(&self, body: &str) -> anyhow::Result<IntakeResponse> {
+        let Some(blob_intake) = &self.blob_intake else {
+            return IntakeResponse::json(
+                501,
+                &serde_json::json!({ "error": "Blob intake is not configured" }),
+            );
+        };
+
+        let tx_request_body = match parse_body::<BlobTxRequestBody>(body) {
+            Ok(tx_request_body) => tx_request_body,
+            Err(error) => return IntakeResponse::errors(400, &[error]),
+        };
+
+        let errors = validate_blob(&tx_request_body, &self.chain_ids().await?, now());
+        if !errors.is_empty() {
+            return IntakeResponse::errors(422, &errors);
+        }
+
+        let blob_input_json_file = match tx_request_body.storage_type {
+            BlobStorageType::S3 => match blob_intake
+                .storage_manager
+                .read_json_file(tx_request_body.source_file_path.clone())
+                .await
+            {
+                Ok(blob_input_json_file) => blob_input_json_file,
+                Err(err) => {
+                    return IntakeResponse::errors(
+                        422,
+                        &[ValidationError::new(
+                            "source_file_path",
+                            format!("failed to read blob input file: {err}"),
+                        )],
+                    );
+                }
+            },
+        };
+
+        let eip712_digest = blob_tx_signer::signature::signing_digest(
+            &tx_request_body,
+            &blob_input_json_file,
+            self.signer_address,
+        );
+
+        let new_tx_request = NewTxRequest {
+            tx_id: tx_request_body.tx_id.clone(),
+            requester_id: tx_request_body.requester_id.clone(),
+            tx_type: TxType::BLOB,
+            tx_status: TxStatus::RECEIVED,
+            chain_id: tx_request_body.chain_id,
+            use_operator_wallet_id: tx_request_body.use_operator_wallet_id,
+            metadata: None,
+            payload_hash: tx_request_body.payload_hash()?,
+        };
+
+        self.persist_and_enqueue(
+            &new_tx_request,
+            &blob_intake.signer_queue,
+            &tx_request_body.to_json_string()?,
+            eip712_digest,
+        )
+        .await
+    }
+
+    /// The RECEIVED row and its lifecycle event are removed again if the signer queue rejects
+    /// the message, so the requester can resubmit the same tx_id. An identical resubmission is
+    /// answered with the stored request. It is queued again while the request is still
+    /// RECEIVED, since its first message may have been lost; the signer handles the replay.
+    async fn persist_and_enqueue(
+        &self,
+        new_tx_request: &NewTxRequest,
+        signer_queue: &SqsQueue,
+        message_body: &String,
+        eip712_digest: B256,
+    ) -> anyhow::Result<IntakeResponse> {
+        let outbox_events =
+            to_outbox_events(&[LifecycleEvent::for_new_tx_request(new_tx_request)])?;
+        match self
+            .tx_request_repo
+            .insert_received(new_tx_request, &outbox_events)
+            .await?
+        {
+            TxRequestInsert::Inserted => {}
+            TxRequestInsert::Replayed(existing) => {
+                if existing.tx_status == TxStatus::RECEIVED {
+                    signer_queue.send_new(message_body).await?;
+                }
+                return IntakeResponse::json(
+                    200,
+                    &AcceptedTxRequest {
+                        tx_id: existing.tx_id,
+                        tx_status: existing.tx_status,
+                        eip712_digest,
+                    },
+                );
+            }
+            TxRequestInsert::Conflict(_) => {
+                return IntakeResponse::errors(
+                    409,
+                    &[ValidationError::new(
+                        "tx_id",
+                        "tx_id already used for a different payload",
+                    )],
+                );
+            }
+        }
+
+        if let Err(err) = signer_queue.send_new(message_body).await {
+            self.tx_request_repo
+                .delete_received(&new_tx_request.tx_id)
+                .await?;
+            return Err(err);
+        }
+
+        IntakeResponse::json(
+            202,
+            &AcceptedTxRequest {
+                tx_id: new_tx_request.tx_id.clone(),
+                tx_status: TxStatus::RECEIVED,
+                eip712_digest,
+            },
+        )
+    }
+
+    async fn chain_ids(&self) -> anyhow::Result<Vec<i64>> {
+        Ok(self
+            .network_repo
+            .select_all()
+            .await?
+            .into_iter()
+            .map(|network| network.chain_id)
+            .collect())
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,45 @@
+pub mod intake;
+pub mod orchestrator;
+pub mod validation;
+
+use std::env;
+
+pub struct Config {
+    pub database_url: String,
+    /// Address of the signer key. The sEOA lives there, so it is the EIP-712 verifying contract.
+    pub signer_address: String,
+    pub standard_signer_queue_url: String,
+    pub standard_signer_queue_message_group_id: String,
+    /// Blob intake is only served when the blob signer queue and bucket are configured.
+    pub blob_signer_queue_url: Option<String>,
+    pub blob_signer_queue_message_group_id: Option<String>,
+    pub blob_storage_bucket_name: Option<String>,
+}
+
+impl Config {
+    pub fn build() -> anyhow::Result<Self> {
+        let database_url = Self::get_env_var("DATABASE_URL");
+        let signer_address = Self::get_env_var("SIGNER_ADDRESS");
+        let standard_signer_queue_url = Self::get_env_var("STANDARD_SIGNER_QUEUE_URL");
+        let standard_signer_queue_message_group_id =
+            Self::get_env_var("STANDARD_SIGNER_QUEUE_MESSAGE_GROUP_ID");
+        let blob_signer_queue_url = env::var("BLOB_SIGNER_QUEUE_URL").ok();
+        let blob_signer_queue_message_group_id =
+            env::var("BLOB_SIGNER_QUEUE_MESSAGE_GROUP_ID").ok();
+        let blob_storage_bucket_name = env::var("BLOB_STORAGE_BUCKET_NAME").ok();
+
+        Ok(Self {
+            database_url,
+            signer_address,
+            standard_signer_queue_url,
+            standard_signer_queue_message_group_id,
+            blob_signer_queue_url,
+            blob_signer_queue_message_group_id,
+            blob_storage_bucket_name,
+        })
+    }
+
+    pub fn get_env_var(key: &str) -> String {
+        env::var(key).expect(format!("Missing env variable: {key}").as_str())
+    }
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "aws")]
+
+use crate::intake::{IngestionGateway, IntakeRequest, IntakeResponse};
+use aws_lambda_events::{
+    apigw::{ApiGatewayV2httpRequest, ApiGatewayV2httpResponse},
+    encodings::Body,
+    http::{HeaderMap, HeaderValue, header::CONTENT_TYPE},
+};
+use lambda_runtime::{LambdaEvent, tracing};
+
+pub struct AwsLambdaOrchestrator {
+    ingestion_gateway: IngestionGateway,
+}
+
+impl AwsLambdaOrchestrator {
+    pub fn build(ingestion_gateway: IngestionGateway) -> Self {
+        Self { ingestion_gateway }
+    }
+
+    pub async fn function_handler(
+        &self,
+        event: LambdaEvent<ApiGatewayV2httpRequest>,
+    ) -> anyhow::Result<ApiGatewayV2httpResponse, lambda_runtime::Error> {
+        let request = IntakeRequest::build(
+            event
+                .payload
+                .path_parameters
+                .get("tx_type")
+                .map(String::as_str)
+                .unwrap_or_default(),
+            event.payload.body.as_deref(),
+        );
+        tracing::info!("{request:?}");
+
+        let intake_response = match self.ingestion_gateway.handle_intake_request(request).await {
+            Ok(intake_response) => intake_response,
+            Err(err) => {
+                tracing::error!("Intake failed: {err:?}");
+                IntakeResponse::internal_error()?
+            }
+        };
+
+        Ok(into_api_gateway_response(intake_response))
+    }
+}
+
+fn into_api_gateway_response(intake_response: IntakeResponse) -> ApiGatewayV2httpResponse {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    let mut response = ApiGatewayV2httpResponse::default();
+    response.status_code = i64::from(intake_response.status_code);
+    response.headers = headers;
+    response.body = Some(Body::Text(intake_response.body));
+    response
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "local")]
+
+use crate::intake::{IngestionGateway, IntakeRequest, IntakeResponse};
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::{StatusCode, header::CONTENT_TYPE},
+    response::{IntoResponse, Response},
+    routing::post,
+};
+use std::sync::Arc;
+
+/// Serves the same routes as the API Gateway in front of the ingestion Lambda, for docker-compose.
+pub fn build_router(ingestion_gateway: IngestionGateway) -> Router {
+    Router::new()
+        .route("/tx-requests/{tx_type}", post(intake))
+        .with_state(Arc::new(ingestion_gateway))
+}
+
+async fn intake(
+    State(ingestion_gateway): State<Arc<IngestionGateway>>,
+    Path(tx_type): Path<String>,
+    body: String,
+) -> Response {
+    let request = IntakeRequest::build(&tx_type, Some(&body));
+    let intake_response = match ingestion_gateway.handle_intake_request(request).await {
+        Ok(intake_response) => intake_response,
+        Err(err) => {
+            tracing::error!("Intake failed: {err:?}");
+            match IntakeResponse::internal_error() {
+                Ok(intake_response) => intake_response,
+                Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            }
+        }
+    };
+
+    let IntakeResponse { status_code, body } = intake_response;
+    (
+        StatusCode::from_u16(status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        [(CONTENT_TYPE, "application/json")],
+        body,
+    )
+        .into_response()
+}
@@ -0,0 +1,109 @@
+use alloy::primitives::Address;
+use serde::{Serialize, de::DeserializeOwned};
+use standard_tx_signer::calldata::parse_calldata;
+use std::str::FromStr;
+use tx_request::{blob_tx::BlobTxRequestBody, standard::StandardTxRequestBody};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValidationError {
+    pub field: String,
+    pub reason: String,
+}
+
+impl ValidationError {
+    pub fn new(field: &str, reason: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Parses a request body, reporting malformed JSON and missing or mistyped fields as a
+/// validation error on `body`.
+pub fn parse_body<T: DeserializeOwned>(body: &str) -> Result<T, ValidationError> {
+    serde_json::from_str::<T>(body).map_err(|err| ValidationError::new("body", err.to_string()))
+}
+
+pub fn validate_standard(
+    tx_request_body: &StandardTxRequestBody,
+    chain_ids: &[i64],
+    now: i64,
+) -> Vec<ValidationError> {
+    let mut errors = validate_common(
+        &tx_request_body.tx_id,
+        &tx_request_body.requester_id,
+        tx_request_body.chain_id,
+        tx_request_body.deadline_timestamp,
+        chain_ids,
+        now,
+    );
+
+    if let Err(err) = Address::from_str(&tx_request_body.to_address) {
+        errors.push(ValidationError::new("to_address", err.to_string()));
+    }
+    if let Err(err) = parse_calldata(&tx_request_body.calldata) {
+        errors.push(ValidationError::new("calldata", err.to_string()));
+    }
+
+    errors
+}
+
+pub fn validate_blob(
+    tx_request_body: &BlobTxRequestBody,
+    chain_ids: &[i64],
+    now: i64,
+) -> Vec<ValidationError> {
+    let mut errors = validate_common(
+        &tx_request_body.tx_id,
+        &tx_request_body.requester_id,
+        tx_request_body.chain_id,
+        tx_request_body.deadline_timestamp,
+        chain_ids,
+        now,
+    );
+
+    if tx_request_body.source_file_path.is_empty() {
+        errors.push(ValidationError::new(
+            "source_file_path",
+            "must not be empty",
+        ));
+    }
+
+    errors
+}
+
+fn validate_common(
+    tx_id: &str,
+    requester_id: &str,
+    chain_id: i64,
+    deadline_timestamp: i64,
+    chain_ids: &[i64],
+    now: i64,
+) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+
+    if tx_id.is_empty() {
+        errors.push(ValidationError::new("tx_id", "must not be empty"));
+    }
+    if requester_id.is_empty() {
+        errors.push(ValidationError::new("requester_id", "must not be empty"));
+    }
+    if !chain_ids.contains(&chain_id) {
+        errors.push(ValidationError::new(
+            "chain_id",
+            format!("no network configured for chain_id {chain_id}"),
+        ));
+    }
+    if deadline_timestamp <= now {
+        errors.push(ValidationError::new(
+            "deadline_timestamp",
+            "must be in the future",
+        ));
+    }
+
+    errors
+}
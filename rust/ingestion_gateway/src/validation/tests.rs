@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use crate::validation::{ValidationError, parse_body, validate_blob, validate_standard};
+    use db_types::{BlobStorageType, Wei};
+    use tx_request::{blob_tx::BlobTxRequestBody, standard::StandardTxRequestBody};
+
+    const NOW: i64 = 1_800_000_000;
+
+    fn standard_body() -> StandardTxRequestBody {
+        StandardTxRequestBody {
+            tx_id: "tx-1".to_string(),
+            requester_id: "requester-1".to_string(),
+            chain_id: 31337,
+            calldata: "0xa9059cbb".to_string(),
+            to_address: "0x5FbDB2315678afecb367f032d93F642f64180aa3".to_string(),
+            value_wei: Wei::ZERO,
+            deadline_timestamp: NOW + 600,
+            pass_value_from_operator_wallet: false,
+            use_operator_wallet_id: None,
+            metadata: None,
+        }
+    }
+
+    fn blob_body() -> BlobTxRequestBody {
+        BlobTxRequestBody {
+            tx_id: "tx-1".to_string(),
+            requester_id: "requester-1".to_string(),
+            chain_id: 31337,
+            deadline_timestamp: NOW + 600,
+            storage_type: BlobStorageType::S3,
+            source_file_path: "blobs/tx-1.json".to_string(),
+            use_operator_wallet_id: None,
+        }
+    }
+
+    fn fields(errors: &[ValidationError]) -> Vec<&str> {
+        errors.iter().map(|error| error.field.as_str()).collect()
+    }
+
+    #[test]
+    fn accepts_valid_standard_request() {
+        assert!(validate_standard(&standard_body(), &[31337], NOW).is_empty());
+    }
+
+    #[test]
+    fn reports_every_invalid_standard_field() {
+        let mut body = standard_body();
+        body.tx_id = String::new();
+        body.chain_id = 1;
+        body.calldata = "0x12".to_string();
+        body.to_address = "not-an-address".to_string();
+        body.deadline_timestamp = NOW;
+
+        let errors = validate_standard(&body, &[31337], NOW);
+
+        assert_eq!(
+            fields(&errors),
+            vec![
+                "tx_id",
+                "chain_id",
+                "deadline_timestamp",
+                "to_address",
+                "calldata"
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_blob_request_without_source_file() {
+        let mut body = blob_body();
+        body.source_file_path = String::new();
+
+        assert_eq!(
+            fields(&validate_blob(&body, &[31337], NOW)),
+            vec!["source_file_path"]
+        );
+    }
+
+    #[test]
+    fn reports_unparseable_body() {
+        let error = parse_body::<StandardTxRequestBody>(r#"{"tx_id": "tx-1"}"#).unwrap_err();
+
+        assert_eq!(error.field, "body");
+        assert!(error.reason.contains("requester_id"));
+    }
+
+    #[test]
+    fn parses_uint256_value_wei_and_rejects_negative_values() {
+        let body = |value_wei: &str| {
+            let mut json = serde_json::to_value(standard_body()).unwrap();
+            json["value_wei"] = serde_json::from_str(value_wei).unwrap();
+            json.to_string()
+        };
+
+        let parsed =
+            parse_body::<StandardTxRequestBody>(&body(r#""100000000000000000000000""#)).unwrap();
+        assert_eq!(parsed.value_wei.to_string(), "100000000000000000000000");
+        assert_eq!(
+            parse_body::<StandardTxRequestBody>(&body("42"))
+                .unwrap()
+                .value_wei,
+            Wei::from(42)
+        );
+
+        let error = parse_body::<StandardTxRequestBody>(&body("-1")).unwrap_err();
+        assert!(error.reason.contains("must not be negative"));
+    }
+}
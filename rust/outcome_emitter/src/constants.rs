@@ -1,2 +1,4 @@
 pub const OUTCOME_EVENT_DETAIL_TYPE: &str = "aa.tx.completed";
+pub const LIFECYCLE_EVENT_DETAIL_TYPE: &str = "aa.tx.status-changed";
+pub const LOW_BALANCE_EVENT_DETAIL_TYPE: &str = "aa.wallet.low-balance";
 pub const OUTCOME_EVENT_SOURCE: &str = "ow.account-abstraction";
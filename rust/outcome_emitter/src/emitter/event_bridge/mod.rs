@@ -0,0 +1,256 @@
+#![cfg(feature = "aws")]
+
+use anyhow::anyhow;
+use aws_sdk_eventbridge::types::{PutEventsRequestEntry, PutEventsResultEntry};
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::{
+    constants::{
+        LIFECYCLE_EVENT_DETAIL_TYPE, LOW_BALANCE_EVENT_DETAIL_TYPE, OUTCOME_EVENT_DETAIL_TYPE,
+        OUTCOME_EVENT_SOURCE,
+    },
+    emitter::{OutcomeEmitter, PermanentFailure},
+    lifecycle::LifecycleEvent,
+    low_balance::LowBalanceEvent,
+    outcome::OutcomeEvent,
+    schema::OutcomeSchemaVersion,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Attempts of a PutEvents batch. The wait before each retry grows by `multiplier`.
+#[derive(Debug, Clone, Copy)]
+pub struct RetrySchedule {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub multiplier: u32,
+}
+
+impl RetrySchedule {
+    /// Wait before `attempt`, counted from 1. The first attempt goes out right away.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        match attempt {
+            0 | 1 => Duration::ZERO,
+            _ => self
+                .initial_backoff
+                .saturating_mul(self.multiplier.saturating_pow(attempt - 2)),
+        }
+    }
+}
+
+/// Entries a single PutEvents call accepts.
+pub const PUT_EVENTS_MAX_ENTRIES: usize = 10;
+
+/// Per-entry failures that may go through when the entry is sent again. Any other code means
+/// the entry itself is rejected.
+fn is_retryable_entry_error(error_code: &str) -> bool {
+    matches!(error_code, "InternalFailure" | "ThrottlingException")
+}
+
+/// Result of every entry of a PutEvents call. EventBridge answers with one result entry per
+/// request entry, in the same order; a missing one counts as failed.
+fn entry_results(
+    entry_count: usize,
+    result_entries: &[PutEventsResultEntry],
+) -> Vec<Result<(), (String, bool)>> {
+    (0..entry_count)
+        .map(|index| match result_entries.get(index) {
+            Some(result_entry) => match result_entry.error_code() {
+                Some(error_code) => Err((
+                    format!(
+                        "{error_code}: {}",
+                        result_entry.error_message().unwrap_or_default()
+                    ),
+                    is_retryable_entry_error(error_code),
+                )),
+                None => Ok(()),
+            },
+            None => Err(("No result entry returned".to_string(), true)),
+        })
+        .collect()
+}
+
+/// Error of a rejected entry. An entry rejected for a reason other than a passing one is a
+/// `PermanentFailure`.
+fn entry_error(detail_type: &str, error: &str, is_retryable: bool) -> anyhow::Error {
+    let message = format!("EventBridge rejected {detail_type}: {error}");
+    if is_retryable {
+        anyhow!(message)
+    } else {
+        PermanentFailure(message).into()
+    }
+}
+
+pub struct AwsEventBridgeOutcomeEmitter {
+    client: aws_sdk_eventbridge::Client,
+    event_bus_name: String,
+    retry_schedule: RetrySchedule,
+    outcome_schema_version: OutcomeSchemaVersion,
+}
+
+impl AwsEventBridgeOutcomeEmitter {
+    pub fn build(
+        client: &aws_sdk_eventbridge::Client,
+        event_bus_name: String,
+        outcome_schema_version: OutcomeSchemaVersion,
+    ) -> Self {
+        Self {
+            client: client.clone(),
+            event_bus_name,
+            retry_schedule: RetrySchedule {
+                max_attempts: 3,
+                initial_backoff: Duration::from_millis(200),
+                multiplier: 2,
+            },
+            outcome_schema_version,
+        }
+    }
+
+    fn entry(&self, detail_type: &str, detail: String) -> PutEventsRequestEntry {
+        PutEventsRequestEntry::builder()
+            .source(OUTCOME_EVENT_SOURCE)
+            .detail_type(detail_type)
+            .detail(detail)
+            .event_bus_name(self.event_bus_name.clone())
+            .build()
+    }
+
+    /// Sends the details in PutEvents calls of up to `PUT_EVENTS_MAX_ENTRIES`, then sends again
+    /// the entries that failed for a passing reason. A failed call or entry does not hold back
+    /// the others. The results follow the order of `details`.
+    async fn put_many(
+        &self,
+        detail_type: &str,
+        details: Vec<anyhow::Result<String>>,
+    ) -> Vec<anyhow::Result<()>> {
+        let mut entries = Vec::with_capacity(details.len());
+        let mut results: Vec<anyhow::Result<()>> = Vec::with_capacity(details.len());
+        for detail in details {
+            match detail {
+                Ok(detail) => {
+                    entries.push(Some(self.entry(detail_type, detail)));
+                    results.push(Ok(()));
+                }
+                Err(err) => {
+                    entries.push(None);
+                    results.push(Err(err));
+                }
+            }
+        }
+
+        let mut pending: Vec<usize> = (0..entries.len())
+            .filter(|&index| entries[index].is_some())
+            .collect();
+        for attempt in 1..=self.retry_schedule.max_attempts {
+            if pending.is_empty() {
+                break;
+            }
+            tokio::time::sleep(self.retry_schedule.backoff(attempt)).await;
+
+            let mut retryable = Vec::new();
+            for chunk in pending.chunks(PUT_EVENTS_MAX_ENTRIES) {
+                let chunk_entries = chunk
+                    .iter()
+                    .filter_map(|&index| entries[index].clone())
+                    .collect();
+                match self
+                    .client
+                    .put_events()
+                    .set_entries(Some(chunk_entries))
+                    .send()
+                    .await
+                {
+                    Ok(output) => {
+                        for (&index, result) in chunk
+                            .iter()
+                            .zip(entry_results(chunk.len(), output.entries()))
+                        {
+                            results[index] = match result {
+                                Ok(()) => Ok(()),
+                                Err((error, is_retryable)) => {
+                                    if is_retryable {
+                                        retryable.push(index);
+                                    }
+                                    Err(entry_error(detail_type, &error, is_retryable))
+                                }
+                            };
+                        }
+                    }
+                    Err(err) => {
+                        let error = format!("{err:?}");
+                        for &index in chunk {
+                            results[index] =
+                                Err(anyhow!("PutEvents of {detail_type} failed: {error}"));
+                            retryable.push(index);
+                        }
+                    }
+                }
+            }
+            pending = retryable;
+        }
+
+        results
+    }
+
+    async fn put_one(
+        &self,
+        detail_type: &str,
+        detail: anyhow::Result<String>,
+    ) -> anyhow::Result<()> {
+        self.put_many(detail_type, vec![detail])
+            .await
+            .pop()
+            .unwrap_or(Ok(()))
+    }
+}
+
+fn to_details(events: &[impl Serialize]) -> Vec<anyhow::Result<String>> {
+    events
+        .iter()
+        .map(|event| Ok(serde_json::to_string(event)?))
+        .collect()
+}
+
+impl OutcomeEmitter for AwsEventBridgeOutcomeEmitter {
+    async fn emit_outcome(&self, outcome_event: &OutcomeEvent) -> anyhow::Result<()> {
+        self.put_one(
+            OUTCOME_EVENT_DETAIL_TYPE,
+            self.outcome_schema_version.outcome_payload(outcome_event),
+        )
+        .await
+    }
+
+    async fn emit_lifecycle(&self, lifecycle_event: &LifecycleEvent) -> anyhow::Result<()> {
+        self.put_one(
+            LIFECYCLE_EVENT_DETAIL_TYPE,
+            Ok(serde_json::to_string(lifecycle_event)?),
+        )
+        .await
+    }
+
+    async fn emit_low_balance(&self, low_balance_event: &LowBalanceEvent) -> anyhow::Result<()> {
+        self.put_one(
+            LOW_BALANCE_EVENT_DETAIL_TYPE,
+            Ok(serde_json::to_string(low_balance_event)?),
+        )
+        .await
+    }
+
+    async fn emit_outcomes(&self, outcome_events: &[OutcomeEvent]) -> Vec<anyhow::Result<()>> {
+        let details = outcome_events
+            .iter()
+            .map(|outcome_event| self.outcome_schema_version.outcome_payload(outcome_event))
+            .collect();
+        self.put_many(OUTCOME_EVENT_DETAIL_TYPE, details).await
+    }
+
+    async fn emit_lifecycles(
+        &self,
+        lifecycle_events: &[LifecycleEvent],
+    ) -> Vec<anyhow::Result<()>> {
+        self.put_many(LIFECYCLE_EVENT_DETAIL_TYPE, to_details(lifecycle_events))
+            .await
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use crate::emitter::{
+        event_bridge::{RetrySchedule, entry_error, entry_results, is_retryable_entry_error},
+        is_permanent_failure,
+    };
+    use aws_sdk_eventbridge::types::PutEventsResultEntry;
+    use std::time::Duration;
+
+    fn accepted() -> PutEventsResultEntry {
+        PutEventsResultEntry::builder().event_id("event-1").build()
+    }
+
+    fn rejected(error_code: &str) -> PutEventsResultEntry {
+        PutEventsResultEntry::builder()
+            .error_code(error_code)
+            .error_message("rejected")
+            .build()
+    }
+
+    #[test]
+    fn only_internal_failures_and_throttling_are_retried() {
+        assert!(is_retryable_entry_error("InternalFailure"));
+        assert!(is_retryable_entry_error("ThrottlingException"));
+        assert!(!is_retryable_entry_error("MalformedDetail"));
+        assert!(!is_retryable_entry_error("NotAuthorizedForSourceException"));
+    }
+
+    #[test]
+    fn failed_entry_does_not_fail_the_others() {
+        let results = entry_results(3, &[accepted(), rejected("MalformedDetail"), accepted()]);
+
+        assert!(results[0].is_ok());
+        assert_eq!(
+            results[1],
+            Err(("MalformedDetail: rejected".to_string(), false))
+        );
+        assert!(results[2].is_ok());
+    }
+
+    #[test]
+    fn missing_result_entry_is_retried() {
+        let results = entry_results(2, &[rejected("ThrottlingException")]);
+
+        assert_eq!(
+            results,
+            vec![
+                Err(("ThrottlingException: rejected".to_string(), true)),
+                Err(("No result entry returned".to_string(), true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn non_retryable_entry_is_a_permanent_failure() {
+        let results = entry_results(
+            2,
+            &[rejected("MalformedDetail"), rejected("InternalFailure")],
+        );
+        let errors: Vec<_> = results
+            .into_iter()
+            .map(|result| {
+                let (error, is_retryable) = result.unwrap_err();
+                entry_error("Outcome", &error, is_retryable)
+            })
+            .collect();
+
+        assert!(is_permanent_failure(&errors[0]));
+        assert_eq!(
+            errors[0].to_string(),
+            "EventBridge rejected Outcome: MalformedDetail: rejected"
+        );
+        assert!(!is_permanent_failure(&errors[1]));
+    }
+
+    #[test]
+    fn backs_off_exponentially_after_first_attempt() {
+        let retry_schedule = RetrySchedule {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(200),
+            multiplier: 2,
+        };
+
+        assert_eq!(retry_schedule.backoff(1), Duration::ZERO);
+        assert_eq!(retry_schedule.backoff(2), Duration::from_millis(200));
+        assert_eq!(retry_schedule.backoff(3), Duration::from_millis(400));
+        assert_eq!(retry_schedule.backoff(4), Duration::from_millis(800));
+    }
+}
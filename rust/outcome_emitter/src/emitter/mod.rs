@@ -1,2 +1,86 @@
 #[cfg(feature = "aws")]
 pub mod event_bridge;
+#[cfg(feature = "aws")]
+pub mod routing;
+pub mod webhook;
+
+use crate::{lifecycle::LifecycleEvent, low_balance::LowBalanceEvent, outcome::OutcomeEvent};
+use anyhow::bail;
+use db_types::TxExecutionOutcome;
+use execution_attempt_db::types::ExecutionAttemptWithTxs;
+use std::fmt;
+
+/// An event the destination rejected for good. Sending it again cannot succeed, so the relay
+/// parks it instead of retrying.
+#[derive(Debug)]
+pub struct PermanentFailure(pub String);
+
+impl fmt::Display for PermanentFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for PermanentFailure {}
+
+pub fn is_permanent_failure(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<PermanentFailure>().is_some()
+}
+
+#[allow(async_fn_in_trait)]
+pub trait OutcomeEmitter {
+    async fn emit_outcome(&self, outcome_event: &OutcomeEvent) -> anyhow::Result<()>;
+
+    async fn emit_lifecycle(&self, lifecycle_event: &LifecycleEvent) -> anyhow::Result<()>;
+
+    async fn emit_low_balance(&self, low_balance_event: &LowBalanceEvent) -> anyhow::Result<()>;
+
+    /// Publishes every event even when some of them fail. The results follow the order of
+    /// `outcome_events`.
+    async fn emit_outcomes(&self, outcome_events: &[OutcomeEvent]) -> Vec<anyhow::Result<()>> {
+        let mut results = Vec::with_capacity(outcome_events.len());
+        for outcome_event in outcome_events {
+            results.push(self.emit_outcome(outcome_event).await);
+        }
+        results
+    }
+
+    /// Publishes every event even when some of them fail. The results follow the order of
+    /// `lifecycle_events`.
+    async fn emit_lifecycles(
+        &self,
+        lifecycle_events: &[LifecycleEvent],
+    ) -> Vec<anyhow::Result<()>> {
+        let mut results = Vec::with_capacity(lifecycle_events.len());
+        for lifecycle_event in lifecycle_events {
+            results.push(self.emit_lifecycle(lifecycle_event).await);
+        }
+        results
+    }
+
+    async fn emit_for_execution_attempt(
+        &self,
+        execution_attempt_with_txs: &ExecutionAttemptWithTxs,
+        outcome: &TxExecutionOutcome,
+        used_gas: Option<i64>,
+    ) -> anyhow::Result<()> {
+        let outcome_events =
+            OutcomeEvent::for_execution_attempt(execution_attempt_with_txs, outcome, used_gas);
+        let errors: Vec<String> = self
+            .emit_outcomes(&outcome_events)
+            .await
+            .into_iter()
+            .filter_map(|result| result.err().map(|err| err.to_string()))
+            .collect();
+
+        if !errors.is_empty() {
+            bail!(
+                "{} of {} outcome events were not published: {}",
+                errors.len(),
+                outcome_events.len(),
+                errors.join("; ")
+            );
+        }
+        Ok(())
+    }
+}
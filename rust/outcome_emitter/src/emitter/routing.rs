@@ -0,0 +1,187 @@
+#![cfg(feature = "aws")]
+
+use anyhow::anyhow;
+use std::collections::HashMap;
+use webhook_db::webhook_endpoints::WebhookEndpoint;
+
+use crate::{
+    emitter::{
+        OutcomeEmitter, PermanentFailure, event_bridge::AwsEventBridgeOutcomeEmitter,
+        is_permanent_failure, webhook::WebhookOutcomeEmitter,
+    },
+    lifecycle::LifecycleEvent,
+    low_balance::LowBalanceEvent,
+    outcome::OutcomeEvent,
+};
+
+/// Events that go to the requester's webhook when one is enabled.
+trait RoutedEvent: Clone {
+    fn requester_id(&self) -> &str;
+
+    async fn deliver(
+        webhook: &WebhookOutcomeEmitter,
+        webhook_endpoint: &WebhookEndpoint,
+        event: &Self,
+    ) -> anyhow::Result<()>;
+
+    async fn emit_all(
+        event_bridge: &AwsEventBridgeOutcomeEmitter,
+        events: &[Self],
+    ) -> Vec<anyhow::Result<()>>;
+}
+
+impl RoutedEvent for OutcomeEvent {
+    fn requester_id(&self) -> &str {
+        &self.requester_id
+    }
+
+    async fn deliver(
+        webhook: &WebhookOutcomeEmitter,
+        webhook_endpoint: &WebhookEndpoint,
+        outcome_event: &Self,
+    ) -> anyhow::Result<()> {
+        webhook
+            .deliver_outcome(webhook_endpoint, outcome_event)
+            .await
+    }
+
+    async fn emit_all(
+        event_bridge: &AwsEventBridgeOutcomeEmitter,
+        outcome_events: &[Self],
+    ) -> Vec<anyhow::Result<()>> {
+        event_bridge.emit_outcomes(outcome_events).await
+    }
+}
+
+impl RoutedEvent for LifecycleEvent {
+    fn requester_id(&self) -> &str {
+        &self.requester_id
+    }
+
+    async fn deliver(
+        webhook: &WebhookOutcomeEmitter,
+        webhook_endpoint: &WebhookEndpoint,
+        lifecycle_event: &Self,
+    ) -> anyhow::Result<()> {
+        webhook
+            .deliver_lifecycle(webhook_endpoint, lifecycle_event)
+            .await
+    }
+
+    async fn emit_all(
+        event_bridge: &AwsEventBridgeOutcomeEmitter,
+        lifecycle_events: &[Self],
+    ) -> Vec<anyhow::Result<()>> {
+        event_bridge.emit_lifecycles(lifecycle_events).await
+    }
+}
+
+/// Sends each outcome and lifecycle event to the requester's webhook when one is enabled, and
+/// to EventBridge otherwise. Operator events always go to EventBridge.
+pub struct RoutingOutcomeEmitter {
+    event_bridge: AwsEventBridgeOutcomeEmitter,
+    webhook: WebhookOutcomeEmitter,
+}
+
+impl RoutingOutcomeEmitter {
+    pub fn build(
+        event_bridge: AwsEventBridgeOutcomeEmitter,
+        webhook: WebhookOutcomeEmitter,
+    ) -> Self {
+        Self {
+            event_bridge,
+            webhook,
+        }
+    }
+}
+
+impl OutcomeEmitter for RoutingOutcomeEmitter {
+    async fn emit_outcome(&self, outcome_event: &OutcomeEvent) -> anyhow::Result<()> {
+        self.route(std::slice::from_ref(outcome_event))
+            .await
+            .pop()
+            .unwrap_or(Ok(()))
+    }
+
+    async fn emit_lifecycle(&self, lifecycle_event: &LifecycleEvent) -> anyhow::Result<()> {
+        self.route(std::slice::from_ref(lifecycle_event))
+            .await
+            .pop()
+            .unwrap_or(Ok(()))
+    }
+
+    async fn emit_low_balance(&self, low_balance_event: &LowBalanceEvent) -> anyhow::Result<()> {
+        self.event_bridge.emit_low_balance(low_balance_event).await
+    }
+
+    async fn emit_outcomes(&self, outcome_events: &[OutcomeEvent]) -> Vec<anyhow::Result<()>> {
+        self.route(outcome_events).await
+    }
+
+    async fn emit_lifecycles(
+        &self,
+        lifecycle_events: &[LifecycleEvent],
+    ) -> Vec<anyhow::Result<()>> {
+        self.route(lifecycle_events).await
+    }
+}
+
+impl RoutingOutcomeEmitter {
+    /// Webhook deliveries go one by one; the rest is sent to EventBridge in batches. The results
+    /// follow the order of `events`.
+    async fn route<E: RoutedEvent>(&self, events: &[E]) -> Vec<anyhow::Result<()>> {
+        let webhook_endpoints = self
+            .find_endpoints(events.iter().map(RoutedEvent::requester_id))
+            .await;
+        let mut results = Vec::with_capacity(events.len());
+        let mut event_bridge_indexes = Vec::new();
+        for (index, (event, webhook_endpoint)) in events.iter().zip(webhook_endpoints).enumerate() {
+            results.push(match webhook_endpoint {
+                Ok(Some(webhook_endpoint)) => {
+                    E::deliver(&self.webhook, &webhook_endpoint, event).await
+                }
+                Ok(None) => {
+                    event_bridge_indexes.push(index);
+                    Ok(())
+                }
+                Err(err) => Err(err),
+            });
+        }
+
+        let event_bridge_events: Vec<E> = event_bridge_indexes
+            .iter()
+            .map(|&index| events[index].clone())
+            .collect();
+        let event_bridge_results = E::emit_all(&self.event_bridge, &event_bridge_events).await;
+        for (index, result) in event_bridge_indexes.into_iter().zip(event_bridge_results) {
+            results[index] = result;
+        }
+        results
+    }
+
+    /// Enabled endpoint of each requester, looked up once per requester.
+    async fn find_endpoints<'a>(
+        &self,
+        requester_ids: impl Iterator<Item = &'a str>,
+    ) -> Vec<anyhow::Result<Option<WebhookEndpoint>>> {
+        let mut found: HashMap<&str, Result<Option<WebhookEndpoint>, (String, bool)>> =
+            HashMap::new();
+        let mut webhook_endpoints = Vec::new();
+        for requester_id in requester_ids {
+            if !found.contains_key(requester_id) {
+                let webhook_endpoint = self
+                    .webhook
+                    .find_endpoint(requester_id)
+                    .await
+                    .map_err(|err| (err.to_string(), is_permanent_failure(&err)));
+                found.insert(requester_id, webhook_endpoint);
+            }
+            webhook_endpoints.push(match &found[requester_id] {
+                Ok(webhook_endpoint) => Ok(webhook_endpoint.clone()),
+                Err((error, true)) => Err(PermanentFailure(error.clone()).into()),
+                Err((error, false)) => Err(anyhow!("Webhook endpoint lookup failed: {error}")),
+            });
+        }
+        webhook_endpoints
+    }
+}
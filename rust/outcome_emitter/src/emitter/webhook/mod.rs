@@ -0,0 +1,210 @@
+use crate::{
+    constants::{LIFECYCLE_EVENT_DETAIL_TYPE, OUTCOME_EVENT_DETAIL_TYPE},
+    emitter::{OutcomeEmitter, PermanentFailure},
+    lifecycle::LifecycleEvent,
+    low_balance::LowBalanceEvent,
+    outcome::OutcomeEvent,
+    schema::OutcomeSchemaVersion,
+};
+use anyhow::anyhow;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use webhook_db::{
+    webhook_deliveries::{NewWebhookDelivery, WebhookDeliveryRepo},
+    webhook_endpoints::{WebhookEndpoint, WebhookEndpointRepo, check_endpoint_url},
+};
+
+#[cfg(test)]
+mod tests;
+
+pub const WEBHOOK_EVENT_HEADER: &str = "X-Webhook-Event";
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+
+const WEBHOOK_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `sha256=` followed by the hex HMAC-SHA256 of `{timestamp}.{payload}`, keyed by the endpoint
+/// secret.
+pub fn sign_payload(secret: &str, timestamp: u64, payload: &str) -> anyhow::Result<String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())?;
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(payload.as_bytes());
+
+    Ok(format!(
+        "sha256={}",
+        hex::encode(mac.finalize().into_bytes())
+    ))
+}
+
+/// Client errors other than timeouts and rate limits won't go away on a later delivery.
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    !status.is_client_error()
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Posts events to the HTTPS endpoint registered by their requester, once per call. Retries are
+/// left to the outbox relay. Every attempt is recorded in `webhook_deliveries`.
+pub struct WebhookOutcomeEmitter {
+    client: reqwest::Client,
+    webhook_endpoint_repo: WebhookEndpointRepo,
+    webhook_delivery_repo: WebhookDeliveryRepo,
+    outcome_schema_version: OutcomeSchemaVersion,
+}
+
+impl WebhookOutcomeEmitter {
+    pub fn build(
+        webhook_endpoint_repo: WebhookEndpointRepo,
+        webhook_delivery_repo: WebhookDeliveryRepo,
+        outcome_schema_version: OutcomeSchemaVersion,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(WEBHOOK_REQUEST_TIMEOUT)
+                .build()?,
+            webhook_endpoint_repo,
+            webhook_delivery_repo,
+            outcome_schema_version,
+        })
+    }
+
+    /// Enabled endpoint of the requester. An endpoint without an https URL is a
+    /// `PermanentFailure`, as none of its events can be delivered.
+    pub async fn find_endpoint(
+        &self,
+        requester_id: &str,
+    ) -> anyhow::Result<Option<WebhookEndpoint>> {
+        let Some(webhook_endpoint) = self
+            .webhook_endpoint_repo
+            .find_enabled(requester_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+        check_endpoint_url(&webhook_endpoint.url).map_err(|err| {
+            PermanentFailure(format!(
+                "Webhook endpoint of requester {requester_id} is unusable: {err}"
+            ))
+        })?;
+
+        Ok(Some(webhook_endpoint))
+    }
+
+    pub async fn deliver_outcome(
+        &self,
+        webhook_endpoint: &WebhookEndpoint,
+        outcome_event: &OutcomeEvent,
+    ) -> anyhow::Result<()> {
+        self.deliver(
+            webhook_endpoint,
+            OUTCOME_EVENT_DETAIL_TYPE,
+            Some(&outcome_event.tx_request_id),
+            &self.outcome_schema_version.outcome_payload(outcome_event)?,
+        )
+        .await
+    }
+
+    pub async fn deliver_lifecycle(
+        &self,
+        webhook_endpoint: &WebhookEndpoint,
+        lifecycle_event: &LifecycleEvent,
+    ) -> anyhow::Result<()> {
+        self.deliver(
+            webhook_endpoint,
+            LIFECYCLE_EVENT_DETAIL_TYPE,
+            Some(&lifecycle_event.tx_request_id),
+            &serde_json::to_string(lifecycle_event)?,
+        )
+        .await
+    }
+
+    /// Posts the payload once. A 2xx answer delivers it; a client error that won't go away is a
+    /// `PermanentFailure`.
+    pub async fn deliver(
+        &self,
+        webhook_endpoint: &WebhookEndpoint,
+        event_type: &str,
+        tx_id: Option<&str>,
+        payload: &str,
+    ) -> anyhow::Result<()> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let response = self
+            .client
+            .post(&webhook_endpoint.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(WEBHOOK_EVENT_HEADER, event_type)
+            .header(WEBHOOK_TIMESTAMP_HEADER, timestamp.to_string())
+            .header(
+                WEBHOOK_SIGNATURE_HEADER,
+                sign_payload(&webhook_endpoint.secret, timestamp, payload)?,
+            )
+            .body(payload.to_string())
+            .send()
+            .await;
+
+        let (status_code, error, retryable) = match &response {
+            Ok(response) if response.status().is_success() => {
+                (Some(response.status()), None, false)
+            }
+            Ok(response) => (
+                Some(response.status()),
+                Some(format!("Endpoint answered {}", response.status())),
+                is_retryable(response.status()),
+            ),
+            Err(err) => (err.status(), Some(err.to_string()), true),
+        };
+
+        self.webhook_delivery_repo
+            .insert(&NewWebhookDelivery {
+                requester_id: webhook_endpoint.requester_id.clone(),
+                url: webhook_endpoint.url.clone(),
+                event_type: event_type.to_string(),
+                tx_id: tx_id.map(str::to_string),
+                payload: payload.to_string(),
+                status_code: status_code.map(|status_code| i32::from(status_code.as_u16())),
+                error: error.clone(),
+                delivered: error.is_none(),
+            })
+            .await?;
+
+        let Some(error) = error else {
+            return Ok(());
+        };
+        let message = format!(
+            "Webhook delivery of {event_type} to requester {} failed: {error}",
+            webhook_endpoint.requester_id
+        );
+        if retryable {
+            Err(anyhow!(message))
+        } else {
+            Err(PermanentFailure(message).into())
+        }
+    }
+}
+
+impl OutcomeEmitter for WebhookOutcomeEmitter {
+    /// Requesters without an enabled endpoint get nothing.
+    async fn emit_outcome(&self, outcome_event: &OutcomeEvent) -> anyhow::Result<()> {
+        match self.find_endpoint(&outcome_event.requester_id).await? {
+            Some(webhook_endpoint) => self.deliver_outcome(&webhook_endpoint, outcome_event).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn emit_lifecycle(&self, lifecycle_event: &LifecycleEvent) -> anyhow::Result<()> {
+        match self.find_endpoint(&lifecycle_event.requester_id).await? {
+            Some(webhook_endpoint) => {
+                self.deliver_lifecycle(&webhook_endpoint, lifecycle_event)
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Operator wallet balances are not any requester's business.
+    async fn emit_low_balance(&self, _low_balance_event: &LowBalanceEvent) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use crate::emitter::webhook::sign_payload;
+    use webhook_db::webhook_endpoints::check_endpoint_url;
+
+    #[test]
+    fn signs_timestamp_and_payload() {
+        let signature =
+            sign_payload("whsec_test", 1_700_000_000, r#"{"outcome":"SUCCEED"}"#).unwrap();
+
+        assert_eq!(
+            signature,
+            "sha256=8c5baa868e03c4df8f176f17591c448ee383225116971e5f34b16f4e795bb4df"
+        );
+    }
+
+    #[test]
+    fn signature_changes_with_timestamp() {
+        let payload = r#"{"outcome":"SUCCEED"}"#;
+
+        assert_ne!(
+            sign_payload("whsec_test", 1_700_000_000, payload).unwrap(),
+            sign_payload("whsec_test", 1_700_000_001, payload).unwrap()
+        );
+    }
+
+    #[test]
+    fn only_https_endpoints_are_accepted() {
+        assert!(check_endpoint_url("https://example.com/hooks/outcomes").is_ok());
+        assert!(check_endpoint_url("http://example.com/hooks/outcomes").is_err());
+        assert!(check_endpoint_url("example.com/hooks/outcomes").is_err());
+    }
+}
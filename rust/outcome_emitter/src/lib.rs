@@ -1,3 +1,7 @@
 pub mod constants;
 pub mod emitter;
+pub mod lifecycle;
+pub mod low_balance;
+pub mod outbox;
 pub mod outcome;
+pub mod schema;
@@ -0,0 +1,128 @@
+use db_types::TxStatus;
+use execution_attempt_db::types::ExecutionAttemptWithTxs;
+use serde::{Deserialize, Serialize};
+use tx_request_db::types::{NewTxRequest, TxRequest, TxRequestWithInput};
+use uuid::Uuid;
+
+#[cfg(test)]
+mod tests;
+
+/// A request moved to `tx_status`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LifecycleEvent {
+    /// Stays the same when the event is published again, so consumers can deduplicate.
+    pub event_id: Uuid,
+    pub tx_request_id: String,
+    pub requester_id: String,
+    pub tx_status: TxStatus,
+    /// 0 before the request is first sent, 1 for the first send, raised by every re-broadcast.
+    pub attempt: i32,
+    pub transaction_hash: Option<String>,
+    pub operator_wallet_address: Option<String>,
+    pub metadata: Option<String>,
+}
+
+impl LifecycleEvent {
+    /// `attempts` is the request's stored attempts once the status is written.
+    pub fn new(
+        tx_status: TxStatus,
+        tx_request_id: String,
+        requester_id: String,
+        attempts: i32,
+        metadata: Option<String>,
+    ) -> Self {
+        Self {
+            event_id: Uuid::new_v4(),
+            tx_request_id,
+            requester_id,
+            tx_status,
+            attempt: attempts,
+            transaction_hash: None,
+            operator_wallet_address: None,
+            metadata,
+        }
+    }
+
+    /// For a request being stored in its first status.
+    pub fn for_new_tx_request(new_tx_request: &NewTxRequest) -> Self {
+        Self::new(
+            new_tx_request.tx_status.clone(),
+            new_tx_request.tx_id.clone(),
+            new_tx_request.requester_id.clone(),
+            0,
+            new_tx_request.metadata.clone(),
+        )
+    }
+
+    pub fn for_tx_request(tx_request: &TxRequest, tx_status: TxStatus) -> Self {
+        Self::new(
+            tx_status,
+            tx_request.tx_id.clone(),
+            tx_request.requester_id.clone(),
+            tx_request.attempts,
+            tx_request.metadata.clone(),
+        )
+    }
+
+    pub fn for_tx_request_with_input(tx_request: &TxRequestWithInput, tx_status: TxStatus) -> Self {
+        Self::new(
+            tx_status,
+            tx_request.tx_id.clone(),
+            tx_request.requester_id.clone(),
+            tx_request.attempts,
+            tx_request.metadata.clone(),
+        )
+    }
+
+    /// One event per request of the attempt, sent by `operator_wallet_address`.
+    pub fn for_execution_attempt(
+        execution_attempt_with_txs: &ExecutionAttemptWithTxs,
+        tx_status: TxStatus,
+        operator_wallet_address: Option<String>,
+    ) -> Vec<Self> {
+        execution_attempt_with_txs
+            .tx_requests
+            .iter()
+            .map(|tx_request| {
+                Self::for_tx_request(tx_request, tx_status.clone()).with_transaction(
+                    execution_attempt_with_txs.execution_attempt.tx_hash.clone(),
+                    operator_wallet_address.clone(),
+                )
+            })
+            .collect()
+    }
+
+    /// One event per request, sent in `transaction_hash` by `operator_wallet_address`.
+    pub fn for_tx_requests(
+        tx_requests: &[TxRequestWithInput],
+        tx_status: TxStatus,
+        transaction_hash: Option<String>,
+        operator_wallet_address: Option<String>,
+    ) -> Vec<Self> {
+        tx_requests
+            .iter()
+            .map(|tx_request| {
+                Self::for_tx_request_with_input(tx_request, tx_status.clone())
+                    .with_transaction(transaction_hash.clone(), operator_wallet_address.clone())
+            })
+            .collect()
+    }
+
+    /// The transaction the request was sent in and the operator wallet that sent it.
+    pub fn with_transaction(
+        mut self,
+        transaction_hash: Option<String>,
+        operator_wallet_address: Option<String>,
+    ) -> Self {
+        self.transaction_hash = transaction_hash;
+        self.operator_wallet_address = operator_wallet_address;
+        self
+    }
+
+    /// For a re-broadcast built from the request before its attempts are raised along with the
+    /// status.
+    pub fn next_attempt(mut self) -> Self {
+        self.attempt += 1;
+        self
+    }
+}
@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use crate::lifecycle::LifecycleEvent;
+    use db_types::{TxStatus, TxType};
+    use time::OffsetDateTime;
+    use tx_request_db::types::TxRequest;
+
+    fn tx_request(attempts: i32) -> TxRequest {
+        TxRequest {
+            sequence_id: 1,
+            tx_id: "tx-1".to_string(),
+            requester_id: "requester-1".to_string(),
+            tx_type: TxType::STANDARD,
+            tx_status: TxStatus::LOCKED,
+            chain_id: 1,
+            use_operator_wallet_id: None,
+            attempts,
+            metadata: None,
+            created_at: OffsetDateTime::now_utc(),
+            updated_at: OffsetDateTime::now_utc(),
+        }
+    }
+
+    #[test]
+    fn first_send_reports_attempt_one() {
+        // The sender builds the event from the row the lock already bumped.
+        let lifecycle_event = LifecycleEvent::for_tx_request(&tx_request(1), TxStatus::BROADCASTED);
+
+        assert_eq!(lifecycle_event.attempt, 1);
+    }
+
+    #[test]
+    fn retry_reports_attempt_two() {
+        // The retry handler builds the event before the broadcast bumps the row.
+        let lifecycle_event =
+            LifecycleEvent::for_tx_request(&tx_request(1), TxStatus::BROADCASTED).next_attempt();
+
+        assert_eq!(lifecycle_event.attempt, 2);
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LowBalanceEvent {
+    pub operator_wallet_id: String,
+    pub wallet_address: String,
+    pub chain_id: i64,
+    pub balance_wei: String,
+    pub warning_level_wei: i64,
+    pub no_funds: bool,
+}
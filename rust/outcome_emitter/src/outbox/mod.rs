@@ -0,0 +1,234 @@
+use crate::{
+    constants::{LIFECYCLE_EVENT_DETAIL_TYPE, OUTCOME_EVENT_DETAIL_TYPE},
+    emitter::{OutcomeEmitter, PermanentFailure, is_permanent_failure},
+    lifecycle::LifecycleEvent,
+    outcome::OutcomeEvent,
+};
+use anyhow::anyhow;
+use outbox_db::outbox_events::{NewOutboxEvent, OutboxEvent, OutboxEventRepo};
+use serde::de::DeserializeOwned;
+
+#[cfg(test)]
+mod tests;
+
+/// Pending events published per scheduled relay run.
+pub const OUTBOX_RELAY_BATCH_SIZE: i64 = 100;
+/// Claims of an event before it is parked for manual review.
+pub const OUTBOX_MAX_ATTEMPTS: i32 = 10;
+/// Wait before an event that failed once is published again. It doubles with every attempt.
+pub const OUTBOX_RETRY_BACKOFF_SEC: f64 = 30.0;
+pub const OUTBOX_MAX_RETRY_BACKOFF_SEC: f64 = 3600.0;
+
+/// Wait before the next publish of an event that has failed `attempts` times.
+pub fn retry_backoff_sec(attempts: i32) -> f64 {
+    let doublings = attempts.clamp(1, 32) - 1;
+    (OUTBOX_RETRY_BACKOFF_SEC * 2f64.powi(doublings)).min(OUTBOX_MAX_RETRY_BACKOFF_SEC)
+}
+
+pub trait ToOutboxEvent {
+    fn to_outbox_event(&self) -> anyhow::Result<NewOutboxEvent>;
+}
+
+impl ToOutboxEvent for OutcomeEvent {
+    fn to_outbox_event(&self) -> anyhow::Result<NewOutboxEvent> {
+        Ok(NewOutboxEvent {
+            id: self.event_id,
+            event_type: OUTCOME_EVENT_DETAIL_TYPE.to_string(),
+            tx_id: Some(self.tx_request_id.clone()),
+            payload: serde_json::to_string(self)?,
+        })
+    }
+}
+
+impl ToOutboxEvent for LifecycleEvent {
+    fn to_outbox_event(&self) -> anyhow::Result<NewOutboxEvent> {
+        Ok(NewOutboxEvent {
+            id: self.event_id,
+            event_type: LIFECYCLE_EVENT_DETAIL_TYPE.to_string(),
+            tx_id: Some(self.tx_request_id.clone()),
+            payload: serde_json::to_string(self)?,
+        })
+    }
+}
+
+pub fn to_outbox_events(events: &[impl ToOutboxEvent]) -> anyhow::Result<Vec<NewOutboxEvent>> {
+    events.iter().map(ToOutboxEvent::to_outbox_event).collect()
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RelaySummary {
+    pub delivered: usize,
+    /// Failed events that stay pending.
+    pub failed: usize,
+    /// Failed events parked for manual review.
+    pub parked: usize,
+}
+
+/// Publishes outbox events through an emitter and marks them delivered. An event whose
+/// publishing fails stays pending, so it goes out at least once, and is held back for
+/// `retry_backoff_sec` before the next try, until it is parked after `OUTBOX_MAX_ATTEMPTS`
+/// claims. An event the emitter rejects as a `PermanentFailure` is
+/// parked at once.
+#[derive(Clone)]
+pub struct OutboxRelay {
+    outbox_event_repo: OutboxEventRepo,
+}
+
+impl OutboxRelay {
+    pub fn new(outbox_event_repo: OutboxEventRepo) -> Self {
+        Self { outbox_event_repo }
+    }
+
+    /// Publishes events right after the transaction that wrote them has committed.
+    pub async fn relay(
+        &self,
+        emitter: &impl OutcomeEmitter,
+        outbox_events: &[NewOutboxEvent],
+    ) -> anyhow::Result<RelaySummary> {
+        if outbox_events.is_empty() {
+            return Ok(RelaySummary::default());
+        }
+        let ids: Vec<_> = outbox_events.iter().map(|event| event.id).collect();
+        let claimed = self.outbox_event_repo.claim(&ids).await?;
+
+        self.publish_all(emitter, claimed).await
+    }
+
+    /// Publishes whatever is pending for the given requests, for writers that have no emitter
+    /// at hand.
+    pub async fn relay_for_tx_ids(
+        &self,
+        emitter: &impl OutcomeEmitter,
+        tx_ids: &[String],
+    ) -> anyhow::Result<RelaySummary> {
+        let claimed = self.outbox_event_repo.claim_by_tx_ids(tx_ids).await?;
+
+        self.publish_all(emitter, claimed).await
+    }
+
+    /// Publishes up to `limit` of the oldest pending events, whatever left them behind.
+    pub async fn relay_pending(
+        &self,
+        emitter: &impl OutcomeEmitter,
+        limit: i64,
+    ) -> anyhow::Result<RelaySummary> {
+        let claimed = self.outbox_event_repo.claim_pending(limit).await?;
+
+        self.publish_all(emitter, claimed).await
+    }
+
+    /// Publishes claimed events in `seq` order, consecutive events of the same type in one
+    /// call. Each event is marked on its own; one that failed stays pending for a later relay
+    /// while the events behind it go out, so the order only holds among events that don't fail.
+    async fn publish_all(
+        &self,
+        emitter: &impl OutcomeEmitter,
+        outbox_events: Vec<OutboxEvent>,
+    ) -> anyhow::Result<RelaySummary> {
+        let mut summary = RelaySummary::default();
+
+        for run in outbox_events.chunk_by(|a, b| a.event_type == b.event_type) {
+            let results = publish_run(emitter, run).await;
+            for (outbox_event, result) in run.iter().zip(results) {
+                match result {
+                    Ok(()) => {
+                        self.outbox_event_repo
+                            .mark_delivered(outbox_event.id)
+                            .await?;
+                        summary.delivered += 1;
+                    }
+                    Err(err) if is_permanent_failure(&err) => {
+                        self.outbox_event_repo
+                            .park(outbox_event.id, &err.to_string())
+                            .await?;
+                        summary.parked += 1;
+                    }
+                    Err(err) => {
+                        let parked = self
+                            .outbox_event_repo
+                            .record_failure(
+                                outbox_event.id,
+                                &err.to_string(),
+                                OUTBOX_MAX_ATTEMPTS,
+                                retry_backoff_sec(outbox_event.attempts),
+                            )
+                            .await?;
+                        if parked {
+                            summary.parked += 1;
+                        } else {
+                            summary.failed += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+/// Payloads of `outbox_events` that could be read, with the position of each and a result
+/// per event that is a `PermanentFailure` for the unreadable ones, since reading them again
+/// can't succeed.
+fn read_payloads<T: DeserializeOwned>(
+    outbox_events: &[OutboxEvent],
+) -> (Vec<usize>, Vec<T>, Vec<anyhow::Result<()>>) {
+    let mut indexes = Vec::with_capacity(outbox_events.len());
+    let mut events = Vec::with_capacity(outbox_events.len());
+    let mut results = Vec::with_capacity(outbox_events.len());
+    for (index, outbox_event) in outbox_events.iter().enumerate() {
+        match serde_json::from_str(&outbox_event.payload) {
+            Ok(event) => {
+                indexes.push(index);
+                events.push(event);
+                results.push(Ok(()));
+            }
+            Err(err) => results.push(Err(PermanentFailure(format!(
+                "Unreadable payload of outbox event {}: {err}",
+                outbox_event.id
+            ))
+            .into())),
+        }
+    }
+    (indexes, events, results)
+}
+
+/// Publishes events that all share one type.
+async fn publish_run(
+    emitter: &impl OutcomeEmitter,
+    outbox_events: &[OutboxEvent],
+) -> Vec<anyhow::Result<()>> {
+    let Some(first) = outbox_events.first() else {
+        return Vec::new();
+    };
+    let (indexes, emitted, mut results) = match first.event_type.as_str() {
+        OUTCOME_EVENT_DETAIL_TYPE => {
+            let (indexes, outcome_events, results) = read_payloads::<OutcomeEvent>(outbox_events);
+            (
+                indexes,
+                emitter.emit_outcomes(&outcome_events).await,
+                results,
+            )
+        }
+        LIFECYCLE_EVENT_DETAIL_TYPE => {
+            let (indexes, lifecycle_events, results) =
+                read_payloads::<LifecycleEvent>(outbox_events);
+            (
+                indexes,
+                emitter.emit_lifecycles(&lifecycle_events).await,
+                results,
+            )
+        }
+        event_type => {
+            return outbox_events
+                .iter()
+                .map(|_| Err(anyhow!("Unknown outbox event type {event_type}")))
+                .collect();
+        }
+    };
+
+    for (index, result) in indexes.into_iter().zip(emitted) {
+        results[index] = result;
+    }
+    results
+}
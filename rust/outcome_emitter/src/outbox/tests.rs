@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        constants::{LIFECYCLE_EVENT_DETAIL_TYPE, OUTCOME_EVENT_DETAIL_TYPE},
+        emitter::is_permanent_failure,
+        lifecycle::LifecycleEvent,
+        outbox::{ToOutboxEvent, read_payloads, retry_backoff_sec, to_outbox_events},
+        outcome::OutcomeEvent,
+    };
+    use db_types::{TxExecutionOutcome, TxStatus, Wei};
+    use outbox_db::outbox_events::OutboxEvent;
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    fn outcome_event(tx_request_id: &str) -> OutcomeEvent {
+        OutcomeEvent {
+            event_id: Uuid::new_v4(),
+            outcome: TxExecutionOutcome::SUCCEED,
+            tx_request_id: tx_request_id.to_string(),
+            requester_id: "requester-1".to_string(),
+            used_gas: Some(21_000),
+            value_wei: Some(Wei::from(1_000_000_000_000_000_000u64)),
+            transaction_hash: Some("0xabc".to_string()),
+            error: None,
+            metadata: Some("{\"order\":42}".to_string()),
+            occurred_at: Some(OffsetDateTime::now_utc()),
+            receipt: None,
+        }
+    }
+
+    #[test]
+    fn outbox_event_keeps_event_id_and_tx_id() {
+        let outcome_event = outcome_event("tx-1");
+
+        let outbox_event = outcome_event.to_outbox_event().unwrap();
+
+        assert_eq!(outbox_event.id, outcome_event.event_id);
+        assert_eq!(outbox_event.event_type, OUTCOME_EVENT_DETAIL_TYPE);
+        assert_eq!(outbox_event.tx_id.as_deref(), Some("tx-1"));
+    }
+
+    #[test]
+    fn outbox_payload_reads_back_as_the_same_event() {
+        let outcome_events = vec![outcome_event("tx-1"), outcome_event("tx-2")];
+
+        let outbox_events = to_outbox_events(&outcome_events).unwrap();
+
+        for (outcome_event, outbox_event) in outcome_events.iter().zip(&outbox_events) {
+            let read_back: OutcomeEvent = serde_json::from_str(&outbox_event.payload).unwrap();
+            assert_eq!(read_back.event_id, outcome_event.event_id);
+            assert_eq!(read_back.tx_request_id, outcome_event.tx_request_id);
+            assert_eq!(read_back.value_wei, outcome_event.value_wei);
+            assert_eq!(read_back.metadata, outcome_event.metadata);
+        }
+    }
+
+    #[test]
+    fn lifecycle_outbox_event_carries_attempt_hash_and_wallet() {
+        let lifecycle_event = LifecycleEvent::new(
+            TxStatus::BROADCASTED,
+            "tx-1".to_string(),
+            "requester-1".to_string(),
+            1,
+            None,
+        )
+        .with_transaction(Some("0xabc".to_string()), Some("0xwallet".to_string()))
+        .next_attempt();
+
+        let outbox_event = lifecycle_event.to_outbox_event().unwrap();
+        let read_back: LifecycleEvent = serde_json::from_str(&outbox_event.payload).unwrap();
+
+        assert_eq!(outbox_event.id, lifecycle_event.event_id);
+        assert_eq!(outbox_event.event_type, LIFECYCLE_EVENT_DETAIL_TYPE);
+        assert_eq!(outbox_event.tx_id.as_deref(), Some("tx-1"));
+        assert_eq!(read_back.tx_status, TxStatus::BROADCASTED);
+        assert_eq!(read_back.attempt, 2);
+        assert_eq!(read_back.transaction_hash.as_deref(), Some("0xabc"));
+        assert_eq!(
+            read_back.operator_wallet_address.as_deref(),
+            Some("0xwallet")
+        );
+    }
+
+    #[test]
+    fn retry_backoff_doubles_up_to_an_hour() {
+        assert_eq!(retry_backoff_sec(1), 30.0);
+        assert_eq!(retry_backoff_sec(2), 60.0);
+        assert_eq!(retry_backoff_sec(4), 240.0);
+        assert_eq!(retry_backoff_sec(8), 3600.0);
+        assert_eq!(retry_backoff_sec(1_000), 3600.0);
+    }
+
+    #[test]
+    fn unreadable_payload_is_a_permanent_failure() {
+        let outcome_event = outcome_event("tx-1");
+        let outbox_event = |payload: String| OutboxEvent {
+            id: Uuid::new_v4(),
+            event_type: OUTCOME_EVENT_DETAIL_TYPE.to_string(),
+            tx_id: Some("tx-1".to_string()),
+            payload,
+            attempts: 0,
+            last_error: None,
+            lease_expires_at: None,
+            delivered_at: None,
+            created_at: OffsetDateTime::now_utc(),
+            seq: 1,
+            parked_at: None,
+        };
+        let outbox_events = vec![
+            outbox_event("{\"not\":\"an outcome\"}".to_string()),
+            outbox_event(serde_json::to_string(&outcome_event).unwrap()),
+        ];
+
+        let (indexes, outcome_events, results) = read_payloads::<OutcomeEvent>(&outbox_events);
+
+        assert_eq!(indexes, vec![1]);
+        assert_eq!(outcome_events.len(), 1);
+        assert!(is_permanent_failure(results[0].as_ref().unwrap_err()));
+        assert!(results[1].is_ok());
+    }
+}
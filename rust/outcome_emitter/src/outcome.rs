@@ -1,12 +1,156 @@
-use db_types::TxExecutionOutcome;
+use db_types::{TxExecutionOutcome, Wei};
+use execution_attempt_db::types::ExecutionAttemptWithTxs;
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OutcomeEvent {
+    /// Stays the same when the event is published again, so consumers can deduplicate.
+    pub event_id: Uuid,
     pub outcome: TxExecutionOutcome,
     pub tx_request_id: String,
-    pub gas_fee: Option<i64>,
+    pub requester_id: String,
+    /// Gas units used by the transaction. Events written before the rename call it `gas_fee`.
+    #[serde(alias = "gas_fee")]
+    pub used_gas: Option<i64>,
+    /// value_wei of a standard request, as a decimal string.
+    pub value_wei: Option<Wei>,
     pub transaction_hash: Option<String>,
     pub error: Option<String>,
     pub metadata: Option<String>,
+    /// When the outcome was reached. Missing on events written before it was recorded.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub occurred_at: Option<OffsetDateTime>,
+    /// Set once the transaction has a receipt.
+    #[serde(default)]
+    pub receipt: Option<OutcomeReceipt>,
+}
+
+/// Cost and inclusion of the transaction, read from its receipt.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OutcomeReceipt {
+    pub effective_gas_price: Wei,
+    /// Gas and blob gas paid for the whole transaction, shared by every request of the batch.
+    pub fee_wei: Wei,
+    pub block_number: u64,
+    pub block_hash: String,
+}
+
+impl OutcomeEvent {
+    /// Outcome of a request with no transaction or value attached yet.
+    pub fn new(
+        outcome: TxExecutionOutcome,
+        tx_request_id: String,
+        requester_id: String,
+        metadata: Option<String>,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            event_id: Uuid::new_v4(),
+            outcome,
+            tx_request_id,
+            requester_id,
+            used_gas: None,
+            value_wei: None,
+            transaction_hash: None,
+            error,
+            metadata,
+            occurred_at: Some(OffsetDateTime::now_utc()),
+            receipt: None,
+        }
+    }
+
+    pub fn failed(
+        tx_request_id: String,
+        requester_id: String,
+        metadata: Option<String>,
+        error: Option<String>,
+    ) -> Self {
+        Self::new(
+            TxExecutionOutcome::FAILED,
+            tx_request_id,
+            requester_id,
+            metadata,
+            error,
+        )
+    }
+
+    /// For a request rejected before anything was sent.
+    pub fn invalid(
+        tx_request_id: String,
+        requester_id: String,
+        metadata: Option<String>,
+        error: String,
+    ) -> Self {
+        Self::new(
+            TxExecutionOutcome::INVALID,
+            tx_request_id,
+            requester_id,
+            metadata,
+            Some(error),
+        )
+    }
+
+    /// For a resubmission whose payload differs from the stored request with the same tx_id.
+    pub fn conflict(tx_request_id: String, requester_id: String, metadata: Option<String>) -> Self {
+        Self::new(
+            TxExecutionOutcome::CONFLICT,
+            tx_request_id,
+            requester_id,
+            metadata,
+            Some("tx_id already used for a different payload".to_string()),
+        )
+    }
+
+    /// One event per request of the attempt.
+    pub fn for_execution_attempt(
+        execution_attempt_with_txs: &ExecutionAttemptWithTxs,
+        outcome: &TxExecutionOutcome,
+        used_gas: Option<i64>,
+    ) -> Vec<Self> {
+        execution_attempt_with_txs
+            .tx_requests
+            .iter()
+            .map(|tx_request| Self {
+                event_id: Uuid::new_v4(),
+                outcome: outcome.clone(),
+                tx_request_id: tx_request.tx_id.clone(),
+                requester_id: tx_request.requester_id.clone(),
+                used_gas,
+                value_wei: execution_attempt_with_txs
+                    .value_wei_by_tx_id
+                    .get(&tx_request.tx_id)
+                    .copied(),
+                transaction_hash: execution_attempt_with_txs.execution_attempt.tx_hash.clone(),
+                error: execution_attempt_with_txs
+                    .execution_attempt
+                    .error_object
+                    .clone(),
+                metadata: tx_request.metadata.clone(),
+                occurred_at: Some(OffsetDateTime::now_utc()),
+                receipt: None,
+            })
+            .collect()
+    }
+
+    pub fn with_value_wei(mut self, value_wei: Option<Wei>) -> Self {
+        self.value_wei = value_wei;
+        self
+    }
+
+    pub fn with_transaction(
+        mut self,
+        used_gas: Option<i64>,
+        transaction_hash: Option<String>,
+    ) -> Self {
+        self.used_gas = used_gas;
+        self.transaction_hash = transaction_hash;
+        self
+    }
+
+    pub fn with_receipt(mut self, receipt: Option<OutcomeReceipt>) -> Self {
+        self.receipt = receipt;
+        self
+    }
 }
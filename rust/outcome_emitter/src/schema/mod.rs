@@ -0,0 +1,186 @@
+use crate::{
+    constants::{OUTCOME_EVENT_DETAIL_TYPE, OUTCOME_EVENT_SOURCE},
+    outcome::OutcomeEvent,
+};
+use anyhow::bail;
+use db_types::{ExecutionErrorObject, RevertReason, TxExecutionOutcome, Wei};
+use serde::{Deserialize, Serialize};
+use std::{env, str::FromStr};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[cfg(test)]
+mod tests;
+
+/// Env variable selecting the outcome payload version, `v1` or `v2`. `v1` when unset, so
+/// consumers keep the payload they parse until the deployment switches to `v2`.
+pub const OUTCOME_SCHEMA_VERSION_ENV: &str = "OUTCOME_SCHEMA_VERSION";
+pub const CLOUD_EVENTS_SPEC_VERSION: &str = "1.0";
+pub const OUTCOME_DATA_SCHEMA_V2: &str = "urn:ow:account-abstraction:aa.tx.completed:v2";
+
+/// Layout of the outcome events sent to EventBridge and webhooks.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutcomeSchemaVersion {
+    /// The flat, unversioned event the emitters sent at first.
+    #[default]
+    V1,
+    /// A CloudEvents 1.0 envelope around `OutcomeData`.
+    V2,
+}
+
+impl FromStr for OutcomeSchemaVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        match value.to_lowercase().as_str() {
+            "1" | "v1" => Ok(Self::V1),
+            "2" | "v2" => Ok(Self::V2),
+            _ => bail!("Unknown outcome schema version: {value}"),
+        }
+    }
+}
+
+impl OutcomeSchemaVersion {
+    pub fn from_env() -> anyhow::Result<Self> {
+        match env::var(OUTCOME_SCHEMA_VERSION_ENV) {
+            Ok(value) => value.parse(),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// The outcome event as it is published.
+    pub fn outcome_payload(&self, outcome_event: &OutcomeEvent) -> anyhow::Result<String> {
+        Ok(match self {
+            Self::V1 => serde_json::to_string(&OutcomeEventV1::from(outcome_event))?,
+            Self::V2 => serde_json::to_string(&OutcomeCloudEvent::from(outcome_event))?,
+        })
+    }
+}
+
+/// Version 1 of the outcome event. `gas_fee` holds gas units, not wei.
+#[derive(Debug, Serialize)]
+pub struct OutcomeEventV1<'a> {
+    pub event_id: &'a Uuid,
+    pub outcome: &'a TxExecutionOutcome,
+    pub tx_request_id: &'a str,
+    pub requester_id: &'a str,
+    pub gas_fee: Option<i64>,
+    pub value_wei: Option<Wei>,
+    pub transaction_hash: Option<&'a str>,
+    pub error: Option<&'a str>,
+    pub metadata: Option<&'a str>,
+}
+
+impl<'a> From<&'a OutcomeEvent> for OutcomeEventV1<'a> {
+    fn from(outcome_event: &'a OutcomeEvent) -> Self {
+        Self {
+            event_id: &outcome_event.event_id,
+            outcome: &outcome_event.outcome,
+            tx_request_id: &outcome_event.tx_request_id,
+            requester_id: &outcome_event.requester_id,
+            gas_fee: outcome_event.used_gas,
+            value_wei: outcome_event.value_wei,
+            transaction_hash: outcome_event.transaction_hash.as_deref(),
+            error: outcome_event.error.as_deref(),
+            metadata: outcome_event.metadata.as_deref(),
+        }
+    }
+}
+
+/// Version 2 of the outcome event, a CloudEvents 1.0 event in structured JSON mode.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OutcomeCloudEvent {
+    pub specversion: String,
+    /// The outcome event's `event_id`, the same on every publish.
+    pub id: Uuid,
+    pub source: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    /// tx_id of the request.
+    pub subject: String,
+    /// When the outcome was reached.
+    #[serde(with = "time::serde::rfc3339")]
+    pub time: OffsetDateTime,
+    pub datacontenttype: String,
+    pub dataschema: String,
+    pub data: OutcomeData,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OutcomeData {
+    pub outcome: TxExecutionOutcome,
+    pub requester_id: String,
+    /// value_wei of a standard request, as a decimal string.
+    pub value_wei: Option<Wei>,
+    pub transaction_hash: Option<String>,
+    /// Gas units used by the whole transaction.
+    pub used_gas: Option<i64>,
+    pub effective_gas_price_wei: Option<Wei>,
+    /// Gas and blob gas paid for the whole transaction, shared by every request of the batch.
+    pub fee_wei: Option<Wei>,
+    pub block_number: Option<u64>,
+    pub block_hash: Option<String>,
+    pub error: Option<OutcomeError>,
+    /// Metadata the requester sent with the request, unchanged.
+    pub metadata: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct OutcomeError {
+    /// `Expired`, `ExecutionFailed`, `Generic error` and the like.
+    pub error_type: String,
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<RevertReason>,
+}
+
+impl OutcomeError {
+    /// Reads the `error_object` of an execution attempt. Any other error text, such as the
+    /// reasons of an INVALID request, is kept as the message of a `Generic error`.
+    pub fn parse(error: &str) -> Self {
+        match serde_json::from_str::<ExecutionErrorObject>(error) {
+            Ok(error_object) => Self {
+                error_type: error_object.error_type,
+                message: error_object.error_body,
+                revert_reason: error_object.revert_reason,
+            },
+            Err(_) => Self {
+                error_type: "Generic error".to_string(),
+                message: Some(error.to_string()),
+                revert_reason: None,
+            },
+        }
+    }
+}
+
+impl From<&OutcomeEvent> for OutcomeCloudEvent {
+    fn from(outcome_event: &OutcomeEvent) -> Self {
+        let receipt = outcome_event.receipt.as_ref();
+
+        Self {
+            specversion: CLOUD_EVENTS_SPEC_VERSION.to_string(),
+            id: outcome_event.event_id,
+            source: OUTCOME_EVENT_SOURCE.to_string(),
+            event_type: OUTCOME_EVENT_DETAIL_TYPE.to_string(),
+            subject: outcome_event.tx_request_id.clone(),
+            time: outcome_event
+                .occurred_at
+                .unwrap_or_else(OffsetDateTime::now_utc),
+            datacontenttype: "application/json".to_string(),
+            dataschema: OUTCOME_DATA_SCHEMA_V2.to_string(),
+            data: OutcomeData {
+                outcome: outcome_event.outcome.clone(),
+                requester_id: outcome_event.requester_id.clone(),
+                value_wei: outcome_event.value_wei,
+                transaction_hash: outcome_event.transaction_hash.clone(),
+                used_gas: outcome_event.used_gas,
+                effective_gas_price_wei: receipt.map(|receipt| receipt.effective_gas_price),
+                fee_wei: receipt.map(|receipt| receipt.fee_wei),
+                block_number: receipt.map(|receipt| receipt.block_number),
+                block_hash: receipt.map(|receipt| receipt.block_hash.clone()),
+                error: outcome_event.error.as_deref().map(OutcomeError::parse),
+                metadata: outcome_event.metadata.clone(),
+            },
+        }
+    }
+}
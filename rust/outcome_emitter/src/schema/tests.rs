@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        outcome::{OutcomeEvent, OutcomeReceipt},
+        schema::{OutcomeError, OutcomeSchemaVersion},
+    };
+    use db_types::{RevertReason, TxExecutionOutcome, Wei};
+    use serde_json::Value;
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    fn outcome_event() -> OutcomeEvent {
+        OutcomeEvent {
+            event_id: Uuid::new_v4(),
+            outcome: TxExecutionOutcome::FAILED,
+            tx_request_id: "tx-1".to_string(),
+            requester_id: "requester-1".to_string(),
+            used_gas: Some(21_000),
+            value_wei: Some(Wei::from(5u64)),
+            transaction_hash: Some("0xabc".to_string()),
+            error: Some(
+                r#"{"error_type":"ExecutionFailed","error_body":"batch","revert_reason":{"kind":"Error","message":"nope"}}"#
+                    .to_string(),
+            ),
+            metadata: Some("{\"order\":42}".to_string()),
+            occurred_at: Some(OffsetDateTime::from_unix_timestamp(1_790_856_000).unwrap()),
+            receipt: Some(OutcomeReceipt {
+                effective_gas_price: Wei::from(2_000_000_000u64),
+                fee_wei: Wei::from(42_000_000_000_000u64),
+                block_number: 123,
+                block_hash: "0xblock".to_string(),
+            }),
+        }
+    }
+
+    fn payload(version: OutcomeSchemaVersion, outcome_event: &OutcomeEvent) -> Value {
+        serde_json::from_str(&version.outcome_payload(outcome_event).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn v1_keeps_the_original_fields() {
+        let outcome_event = outcome_event();
+
+        let payload = payload(OutcomeSchemaVersion::V1, &outcome_event);
+
+        let fields: Vec<&str> = payload
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                "error",
+                "event_id",
+                "gas_fee",
+                "metadata",
+                "outcome",
+                "requester_id",
+                "transaction_hash",
+                "tx_request_id",
+                "value_wei",
+            ]
+        );
+        assert_eq!(payload["gas_fee"], 21_000);
+    }
+
+    #[test]
+    fn v2_is_a_cloud_event_about_the_request() {
+        let outcome_event = outcome_event();
+
+        let payload = payload(OutcomeSchemaVersion::V2, &outcome_event);
+
+        assert_eq!(payload["specversion"], "1.0");
+        assert_eq!(payload["id"], outcome_event.event_id.to_string());
+        assert_eq!(payload["source"], "ow.account-abstraction");
+        assert_eq!(payload["type"], "aa.tx.completed");
+        assert_eq!(payload["subject"], "tx-1");
+        assert_eq!(payload["time"], "2026-10-01T12:00:00Z");
+        assert_eq!(payload["data"]["requester_id"], "requester-1");
+        assert_eq!(payload["data"]["used_gas"], 21_000);
+        assert_eq!(payload["data"]["effective_gas_price_wei"], "2000000000");
+        assert_eq!(payload["data"]["fee_wei"], "42000000000000");
+        assert_eq!(payload["data"]["block_number"], 123);
+        assert_eq!(payload["data"]["block_hash"], "0xblock");
+        assert_eq!(payload["data"]["error"]["error_type"], "ExecutionFailed");
+        assert_eq!(payload["data"]["error"]["revert_reason"]["message"], "nope");
+    }
+
+    #[test]
+    fn v2_leaves_receipt_fields_empty_without_a_receipt() {
+        let outcome_event = outcome_event().with_receipt(None);
+
+        let payload = payload(OutcomeSchemaVersion::V2, &outcome_event);
+
+        assert!(payload["data"]["fee_wei"].is_null());
+        assert!(payload["data"]["block_number"].is_null());
+    }
+
+    #[test]
+    fn plain_error_text_becomes_a_generic_error() {
+        assert_eq!(
+            OutcomeError::parse("value_wei must not be negative"),
+            OutcomeError {
+                error_type: "Generic error".to_string(),
+                message: Some("value_wei must not be negative".to_string()),
+                revert_reason: None,
+            }
+        );
+        assert_eq!(
+            OutcomeError::parse(r#"{"error_type":"Expired","error_body":null}"#),
+            OutcomeError {
+                error_type: "Expired".to_string(),
+                message: None,
+                revert_reason: None,
+            }
+        );
+        assert_ne!(
+            OutcomeError::parse(
+                r#"{"error_type":"ExecutionFailed","error_body":null,"revert_reason":{"kind":"Error","message":"x"}}"#
+            )
+            .revert_reason,
+            None::<RevertReason>
+        );
+    }
+
+    #[test]
+    fn reads_events_written_before_the_rename() {
+        let outcome_event: OutcomeEvent = serde_json::from_str(
+            r#"{"event_id":"6f1c2a4e-2f0e-4a55-9a57-3b7e6f2c1d10","outcome":"SUCCEED","tx_request_id":"tx-1","requester_id":"requester-1","gas_fee":21000,"value_wei":null,"transaction_hash":"0xabc","error":null,"metadata":null}"#,
+        )
+        .unwrap();
+
+        assert_eq!(outcome_event.used_gas, Some(21_000));
+        assert_eq!(outcome_event.occurred_at, None::<OffsetDateTime>);
+        assert_eq!(outcome_event.receipt, None);
+    }
+
+    #[test]
+    fn parses_versions() {
+        assert_eq!(
+            "v1".parse::<OutcomeSchemaVersion>().unwrap(),
+            OutcomeSchemaVersion::V1
+        );
+        assert_eq!(
+            "2".parse::<OutcomeSchemaVersion>().unwrap(),
+            OutcomeSchemaVersion::V2
+        );
+        assert!("v3".parse::<OutcomeSchemaVersion>().is_err());
+    }
+
+    #[test]
+    fn defaults_to_v1() {
+        assert_eq!(OutcomeSchemaVersion::default(), OutcomeSchemaVersion::V1);
+    }
+}
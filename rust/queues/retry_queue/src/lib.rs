@@ -3,6 +3,11 @@ use serde::{Deserialize, Serialize};
 #[derive(Deserialize, Serialize, Debug)]
 pub struct RetryQueueMessageBody {
     pub execution_attempt_id: String,
+    #[serde(default)]
+    pub cancel_nonce: bool,
+    /// Unused nonce of a pipelined wallet that blocks the referenced execution attempt.
+    #[serde(default)]
+    pub gap_nonce: Option<i64>,
 }
 
 #[derive(Debug)]
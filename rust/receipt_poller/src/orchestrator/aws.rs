@@ -2,22 +2,39 @@
 
 use std::{collections::HashMap, str::FromStr};
 
-use crate::{Config, receipt::ReceiptReader};
+use crate::{
+    Config,
+    receipt::{OutcomeWithGas, ReceiptReader, match_item_results},
+};
+use anyhow::bail;
 use aws_lambda_events::{eventbridge::EventBridgeEvent, sqs::SqsEvent};
-use db_types::{TxExecutionOutcome, TxStatus};
+use db_types::{ExecutionErrorObject, TxExecutionOutcome, TxStatus, TxType};
 use execution_attempt_db::{
-    execution_attempts::ExecutionAttemptRepo,
+    execution_attempts::{ExecutionAttempt, ExecutionAttemptRepo},
     types::{ExecutionAttemptWithTxs, OutcomePropagationInput},
 };
+use execution_attempt_item_db::execution_attempt_items::ExecutionAttemptItemRepo;
 use lambda_runtime::{LambdaEvent, tracing};
 use network_db::networks::{Network, NetworkRepo};
 use operator_wallet_db::operator_wallets::OperatorWalletRepo;
-use outcome_emitter::emitter::event_bridge::AwsEventBridgeOutcomeEmitter;
+use outbox_db::outbox_events::{NewOutboxEvent, OutboxEventRepo};
+use outcome_emitter::{
+    emitter::{
+        OutcomeEmitter, event_bridge::AwsEventBridgeOutcomeEmitter, routing::RoutingOutcomeEmitter,
+        webhook::WebhookOutcomeEmitter,
+    },
+    lifecycle::LifecycleEvent,
+    low_balance::LowBalanceEvent,
+    outbox::{OUTBOX_RELAY_BATCH_SIZE, OutboxRelay, to_outbox_events},
+    outcome::{OutcomeEvent, OutcomeReceipt},
+};
 use receipt_poller_queue::ReceiptPollerEvent;
 use retry_queue::RetryQueueMessageBody;
 use serde::Deserialize;
 use sqs_queue::{message_body::ToJsonString, queue::SqsQueue};
-use wallet_pool::manager::WalletPoolManager;
+use uuid::Uuid;
+use wallet_pool::manager::{WalletPoolManager, is_pipelined};
+use webhook_db::{webhook_deliveries::WebhookDeliveryRepo, webhook_endpoints::WebhookEndpointRepo};
 
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
@@ -28,10 +45,13 @@ pub enum LambdaTrigger {
 
 pub struct AwsLambdaOrchestrator {
     execution_attempt_repo: ExecutionAttemptRepo,
+    execution_attempt_item_repo: ExecutionAttemptItemRepo,
+    operator_wallet_repo: OperatorWalletRepo,
     receipt_reader: ReceiptReader,
     wallet_pool: WalletPoolManager,
     retry_queue: SqsQueue,
-    outcome_emitter: AwsEventBridgeOutcomeEmitter,
+    outcome_emitter: RoutingOutcomeEmitter,
+    outbox_relay: OutboxRelay,
     networks_by_chain_id: HashMap<i64, Network>,
 }
 
@@ -43,11 +63,12 @@ impl AwsLambdaOrchestrator {
         let config = Config::build()?;
         let network_repo = NetworkRepo::new(pool.clone());
         let execution_attempt_repo = ExecutionAttemptRepo::new(pool.clone());
+        let execution_attempt_item_repo = ExecutionAttemptItemRepo::new(pool.clone());
         let operator_wallet_repo = OperatorWalletRepo::new(pool.clone());
         let networks = network_repo.select_all().await?;
 
         let receipt_reader = ReceiptReader::build(&networks).await?;
-        let wallet_pool = WalletPoolManager::build(operator_wallet_repo, &networks);
+        let wallet_pool = WalletPoolManager::build(operator_wallet_repo.clone(), &networks);
         let sqs_client = aws_sdk_sqs::Client::new(&aws_config);
         let event_bridge_client = aws_sdk_eventbridge::Client::new(&aws_config);
         let retry_queue = SqsQueue::build(
@@ -55,11 +76,21 @@ impl AwsLambdaOrchestrator {
             &config.retry_queue_url,
             &config.retry_queue_message_group_id,
         )?;
-        let outcome_emitter = AwsEventBridgeOutcomeEmitter::build(
-            &event_bridge_client,
-            config.outcome_event_bus_name,
+        let outcome_emitter = RoutingOutcomeEmitter::build(
+            AwsEventBridgeOutcomeEmitter::build(
+                &event_bridge_client,
+                config.outcome_event_bus_name,
+                config.outcome_schema_version,
+            ),
+            WebhookOutcomeEmitter::build(
+                WebhookEndpointRepo::new(pool.clone()),
+                WebhookDeliveryRepo::new(pool.clone()),
+                config.outcome_schema_version,
+            )?,
         );
 
+        let outbox_relay = OutboxRelay::new(OutboxEventRepo::new(pool.clone()));
+
         let mut networks_by_chain_id = HashMap::new();
 
         for network in networks {
@@ -68,10 +99,13 @@ impl AwsLambdaOrchestrator {
 
         Ok(Self {
             execution_attempt_repo,
+            execution_attempt_item_repo,
+            operator_wallet_repo,
             receipt_reader,
             wallet_pool,
             retry_queue,
             outcome_emitter,
+            outbox_relay,
             networks_by_chain_id,
         })
     }
@@ -95,15 +129,120 @@ impl AwsLambdaOrchestrator {
         }
         Ok(())
     }
+    /// Runs every scheduled step even when an earlier one fails, so one bad attempt or an
+    /// unreachable RPC doesn't hold up the relay and the wallet sweeps. Fails with the errors of
+    /// all failed steps at the end.
     pub async fn scheduler_event_handler(&self) -> anyhow::Result<()> {
+        let step_results = [
+            (
+                "resolve old executions",
+                self.resolve_old_executions().await,
+            ),
+            ("relay pending outbox events", self.relay_pending().await),
+            (
+                "sweep expired leases",
+                self.wallet_pool.sweep_expired_leases().await,
+            ),
+            (
+                "reconcile idle wallets",
+                self.wallet_pool.reconcile_idle_wallets().await,
+            ),
+            ("sweep balances", self.sweep_balances().await),
+        ];
+
+        let mut failed_steps = Vec::new();
+        for (step, result) in step_results {
+            if let Err(err) = result {
+                tracing::error!("Scheduler step '{}' failed: {:?}", step, err);
+                failed_steps.push(format!("{step}: {err}"));
+            }
+        }
+        if !failed_steps.is_empty() {
+            bail!("Scheduler steps failed: {}", failed_steps.join("; "));
+        }
+        Ok(())
+    }
+
+    /// Resolves every old attempt, logging the ones that fail instead of stopping at them.
+    async fn resolve_old_executions(&self) -> anyhow::Result<()> {
         let old_executions = self.execution_attempt_repo.find_old_unresolved().await?;
+        let total = old_executions.len();
 
+        let mut failed = 0;
         for execution_attempt_with_txs in old_executions {
-            self.resolve_execution_attempt(&execution_attempt_with_txs)
-                .await?;
+            if let Err(err) = self
+                .resolve_execution_attempt(&execution_attempt_with_txs)
+                .await
+            {
+                tracing::error!(
+                    "Failed to resolve execution attempt {}: {:?}",
+                    execution_attempt_with_txs.execution_attempt.id,
+                    err
+                );
+                failed += 1;
+            }
+        }
+        if failed > 0 {
+            bail!("{failed} of {total} execution attempts failed to resolve");
         }
         Ok(())
     }
+
+    async fn relay_pending(&self) -> anyhow::Result<()> {
+        let relay_summary = self
+            .outbox_relay
+            .relay_pending(&self.outcome_emitter, OUTBOX_RELAY_BATCH_SIZE)
+            .await?;
+        if relay_summary.failed > 0 {
+            tracing::warn!(
+                "{} outbox events failed to publish and stay pending",
+                relay_summary.failed
+            );
+        }
+        if relay_summary.parked > 0 {
+            tracing::error!(
+                "{} outbox events ran out of attempts and were parked",
+                relay_summary.parked
+            );
+        }
+        Ok(())
+    }
+
+    async fn sweep_balances(&self) -> anyhow::Result<()> {
+        for low_balance_wallet in self.wallet_pool.sweep_balances().await? {
+            tracing::warn!(
+                "Operator wallet {} balance {} is below warning level {}",
+                low_balance_wallet.operator_wallet.id,
+                low_balance_wallet.balance,
+                low_balance_wallet.warning_level
+            );
+            let operator_wallet_id = low_balance_wallet.operator_wallet.id;
+            let emitted = self
+                .outcome_emitter
+                .emit_low_balance(&LowBalanceEvent {
+                    operator_wallet_id: operator_wallet_id.to_string(),
+                    wallet_address: low_balance_wallet.operator_wallet.wallet_address,
+                    chain_id: low_balance_wallet.operator_wallet.chain_id,
+                    balance_wei: low_balance_wallet.balance.to_string(),
+                    warning_level_wei: low_balance_wallet.warning_level,
+                    no_funds: low_balance_wallet.operator_wallet.no_funds,
+                })
+                .await;
+            if let Err(err) = emitted {
+                tracing::error!(
+                    "Failed to emit low balance event for wallet {}: {:?}",
+                    operator_wallet_id,
+                    err
+                );
+                // Lets the next sweep warn again.
+                self.operator_wallet_repo
+                    .set_low_balance_warned(operator_wallet_id, false)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
     pub async fn function_handler(
         &self,
         event: LambdaEvent<LambdaTrigger>,
@@ -125,6 +264,12 @@ impl AwsLambdaOrchestrator {
         &self,
         execution_attempt_with_txs: &ExecutionAttemptWithTxs,
     ) -> anyhow::Result<()> {
+        if execution_attempt_with_txs.execution_attempt.tx_type == TxType::CANCELLATION {
+            return self
+                .resolve_cancellation(&execution_attempt_with_txs.execution_attempt)
+                .await;
+        }
+
         if let Some(outcome_with_gas) = self
             .receipt_reader
             .check_execution(&execution_attempt_with_txs.execution_attempt)
@@ -132,49 +277,85 @@ impl AwsLambdaOrchestrator {
         {
             match outcome_with_gas.outcome {
                 TxExecutionOutcome::SUCCEED => {
+                    let not_executed_tx_ids = self
+                        .record_item_results(execution_attempt_with_txs, &outcome_with_gas)
+                        .await?;
+                    let (not_executed, executed) =
+                        split_by_tx_ids(execution_attempt_with_txs, &not_executed_tx_ids);
+                    let mut outbox_events = self
+                        .status_outbox_events(
+                            &executed,
+                            TxStatus::EXECUTED,
+                            Some(&outcome_with_gas.outcome),
+                            outcome_with_gas.used_gas,
+                            outcome_with_gas.receipt.as_ref(),
+                        )
+                        .await?;
+                    if !not_executed.tx_requests.is_empty() {
+                        outbox_events.extend(
+                            self.not_executed_outbox_events(not_executed, &outcome_with_gas)
+                                .await?,
+                        );
+                    }
                     let propagation_input = OutcomePropagationInput {
                         execution_attempt_id: execution_attempt_with_txs.execution_attempt.id,
                         outcome: outcome_with_gas.outcome.clone(),
                         tx_requests_status: TxStatus::EXECUTED,
+                        failed_tx_ids: not_executed_tx_ids,
                         retryable: None,
                         used_gas: outcome_with_gas.used_gas,
+                        outbox_events,
                     };
                     self.execution_attempt_repo
                         .propagate_outcome(&propagation_input)
                         .await?;
 
-                    self.wallet_pool
-                        .release_used(
-                            execution_attempt_with_txs
-                                .execution_attempt
-                                .operator_wallet_id,
-                        )
+                    self.release_used_nonce(&execution_attempt_with_txs.execution_attempt)
                         .await?;
-                    self.outcome_emitter
-                        .emit_for_execution_attempt(
-                            &execution_attempt_with_txs,
-                            &outcome_with_gas.outcome,
-                            outcome_with_gas.used_gas,
-                        )
+
+                    self.outbox_relay
+                        .relay(&self.outcome_emitter, &propagation_input.outbox_events)
                         .await?;
                 }
                 TxExecutionOutcome::FAILED => {
+                    self.record_item_results(execution_attempt_with_txs, &outcome_with_gas)
+                        .await?;
                     if execution_attempt_with_txs.tx_requests.len() > 1 {
                         let propagation_input = OutcomePropagationInput {
                             execution_attempt_id: execution_attempt_with_txs.execution_attempt.id,
                             outcome: outcome_with_gas.outcome.clone(),
                             tx_requests_status: TxStatus::RETRIED,
+                            failed_tx_ids: Vec::new(),
                             retryable: Some(true),
                             used_gas: outcome_with_gas.used_gas,
+                            outbox_events: self
+                                .status_outbox_events(
+                                    execution_attempt_with_txs,
+                                    TxStatus::RETRIED,
+                                    None,
+                                    outcome_with_gas.used_gas,
+                                    outcome_with_gas.receipt.as_ref(),
+                                )
+                                .await?,
                         };
                         self.execution_attempt_repo
                             .propagate_outcome(&propagation_input)
                             .await?;
+
+                        self.release_used_nonce(&execution_attempt_with_txs.execution_attempt)
+                            .await?;
+
+                        self.outbox_relay
+                            .relay(&self.outcome_emitter, &propagation_input.outbox_events)
+                            .await?;
+
                         let message_body = &RetryQueueMessageBody {
                             execution_attempt_id: execution_attempt_with_txs
                                 .execution_attempt
                                 .id
                                 .to_string(),
+                            cancel_nonce: false,
+                            gap_nonce: None,
                         };
                         let message_body_string = message_body.to_json_string()?;
                         self.retry_queue.send_new(&message_body_string).await?;
@@ -183,23 +364,38 @@ impl AwsLambdaOrchestrator {
                             execution_attempt_id: execution_attempt_with_txs.execution_attempt.id,
                             outcome: outcome_with_gas.outcome.clone(),
                             tx_requests_status: TxStatus::FAILED,
+                            failed_tx_ids: Vec::new(),
                             retryable: Some(false),
                             used_gas: outcome_with_gas.used_gas,
+                            outbox_events: self
+                                .status_outbox_events(
+                                    execution_attempt_with_txs,
+                                    TxStatus::FAILED,
+                                    Some(&outcome_with_gas.outcome),
+                                    outcome_with_gas.used_gas,
+                                    outcome_with_gas.receipt.as_ref(),
+                                )
+                                .await?,
                         };
                         self.execution_attempt_repo
                             .propagate_outcome(&propagation_input)
                             .await?;
 
-                        self.outcome_emitter
-                            .emit_for_execution_attempt(
-                                &execution_attempt_with_txs,
-                                &outcome_with_gas.outcome,
-                                outcome_with_gas.used_gas,
-                            )
+                        self.release_used_nonce(&execution_attempt_with_txs.execution_attempt)
+                            .await?;
+
+                        self.outbox_relay
+                            .relay(&self.outcome_emitter, &propagation_input.outbox_events)
                             .await?;
                     }
                 }
                 TxExecutionOutcome::STUCK | TxExecutionOutcome::DROPPED => {
+                    if self
+                        .is_behind_lower_nonce(&execution_attempt_with_txs.execution_attempt)
+                        .await?
+                    {
+                        return Ok(());
+                    }
                     let execution_atttempts = execution_attempt_with_txs.tx_requests[0].attempts;
                     let max_attempts = self
                         .networks_by_chain_id
@@ -211,46 +407,325 @@ impl AwsLambdaOrchestrator {
                             execution_attempt_id: execution_attempt_with_txs.execution_attempt.id,
                             outcome: outcome_with_gas.outcome.clone(),
                             tx_requests_status: TxStatus::FAILED,
+                            failed_tx_ids: Vec::new(),
                             retryable: Some(false),
                             used_gas: outcome_with_gas.used_gas,
+                            outbox_events: self
+                                .status_outbox_events(
+                                    execution_attempt_with_txs,
+                                    TxStatus::FAILED,
+                                    Some(&outcome_with_gas.outcome),
+                                    outcome_with_gas.used_gas,
+                                    outcome_with_gas.receipt.as_ref(),
+                                )
+                                .await?,
                         };
                         self.execution_attempt_repo
                             .propagate_outcome(&propagation_input)
                             .await?;
 
-                        self.outcome_emitter
-                            .emit_for_execution_attempt(
-                                &execution_attempt_with_txs,
-                                &outcome_with_gas.outcome,
-                                outcome_with_gas.used_gas,
-                            )
+                        self.outbox_relay
+                            .relay(&self.outcome_emitter, &propagation_input.outbox_events)
                             .await?;
+
+                        self.send_cancellation_message(
+                            &execution_attempt_with_txs.execution_attempt.id,
+                        )
+                        .await?;
                     } else {
                         let propagation_input = OutcomePropagationInput {
                             execution_attempt_id: execution_attempt_with_txs.execution_attempt.id,
                             outcome: outcome_with_gas.outcome.clone(),
                             tx_requests_status: TxStatus::RETRIED,
+                            failed_tx_ids: Vec::new(),
                             retryable: Some(true),
                             used_gas: outcome_with_gas.used_gas,
+                            outbox_events: self
+                                .status_outbox_events(
+                                    execution_attempt_with_txs,
+                                    TxStatus::RETRIED,
+                                    None,
+                                    outcome_with_gas.used_gas,
+                                    outcome_with_gas.receipt.as_ref(),
+                                )
+                                .await?,
                         };
 
                         self.execution_attempt_repo
                             .propagate_outcome(&propagation_input)
                             .await?;
 
+                        self.outbox_relay
+                            .relay(&self.outcome_emitter, &propagation_input.outbox_events)
+                            .await?;
+
                         let message_body = &RetryQueueMessageBody {
                             execution_attempt_id: execution_attempt_with_txs
                                 .execution_attempt
                                 .id
                                 .to_string(),
+                            cancel_nonce: false,
+                            gap_nonce: None,
                         };
                         let message_body_string = message_body.to_json_string()?;
                         self.retry_queue.send_new(&message_body_string).await?;
                     }
                 }
-                TxExecutionOutcome::REVERTED => return Ok(()),
+                TxExecutionOutcome::REVERTED
+                | TxExecutionOutcome::INVALID
+                | TxExecutionOutcome::CONFLICT => return Ok(()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Stores which requests of the attempt ran according to the receipt's item logs and
+    /// returns the tx_ids that did not.
+    async fn record_item_results(
+        &self,
+        execution_attempt_with_txs: &ExecutionAttemptWithTxs,
+        outcome_with_gas: &OutcomeWithGas,
+    ) -> anyhow::Result<Vec<String>> {
+        let tx_ids: Vec<String> = execution_attempt_with_txs
+            .tx_requests
+            .iter()
+            .map(|tx_request| tx_request.tx_id.clone())
+            .collect();
+        let item_results = match_item_results(&tx_ids, &outcome_with_gas.item_logs)?;
+
+        self.execution_attempt_item_repo
+            .record_results(
+                execution_attempt_with_txs.execution_attempt.id,
+                &item_results,
+            )
+            .await?;
+
+        Ok(item_results
+            .into_iter()
+            .filter(|item_result| !item_result.executed)
+            .map(|item_result| item_result.tx_id)
+            .collect())
+    }
+
+    /// Events of the attempt's requests moving to `tx_status`, followed by their outcome events
+    /// when the status is final.
+    async fn status_outbox_events(
+        &self,
+        execution_attempt_with_txs: &ExecutionAttemptWithTxs,
+        tx_status: TxStatus,
+        outcome: Option<&TxExecutionOutcome>,
+        used_gas: Option<i64>,
+        receipt: Option<&OutcomeReceipt>,
+    ) -> anyhow::Result<Vec<NewOutboxEvent>> {
+        let operator_wallet = self
+            .operator_wallet_repo
+            .find_by_id(
+                execution_attempt_with_txs
+                    .execution_attempt
+                    .operator_wallet_id,
+            )
+            .await?;
+        let mut outbox_events = to_outbox_events(&LifecycleEvent::for_execution_attempt(
+            execution_attempt_with_txs,
+            tx_status,
+            Some(operator_wallet.wallet_address),
+        ))?;
+        if let Some(outcome) = outcome {
+            let outcome_events: Vec<OutcomeEvent> =
+                OutcomeEvent::for_execution_attempt(execution_attempt_with_txs, outcome, used_gas)
+                    .into_iter()
+                    .map(|outcome_event| outcome_event.with_receipt(receipt.cloned()))
+                    .collect();
+            outbox_events.extend(to_outbox_events(&outcome_events)?);
+        }
+
+        Ok(outbox_events)
+    }
+
+    /// A successful receipt without an item's log means that request never ran, so it is
+    /// reported as failed while the rest of the batch is reported as executed.
+    async fn not_executed_outbox_events(
+        &self,
+        mut not_executed: ExecutionAttemptWithTxs,
+        outcome_with_gas: &OutcomeWithGas,
+    ) -> anyhow::Result<Vec<NewOutboxEvent>> {
+        tracing::warn!(
+            "Execution attempt {} succeeded without item logs for: {:?}",
+            not_executed.execution_attempt.id,
+            not_executed
+                .tx_requests
+                .iter()
+                .map(|tx_request| &tx_request.tx_id)
+                .collect::<Vec<_>>()
+        );
+
+        not_executed.execution_attempt.error_object = Some(
+            ExecutionErrorObject {
+                error_type: "NotExecuted".to_string(),
+                error_body: Some("No Executed or BlobSent log found for the request".to_string()),
+                revert_reason: None,
+            }
+            .to_json_string()?,
+        );
+        self.status_outbox_events(
+            &not_executed,
+            TxStatus::FAILED,
+            Some(&TxExecutionOutcome::FAILED),
+            outcome_with_gas.used_gas,
+            outcome_with_gas.receipt.as_ref(),
+        )
+        .await
+    }
+
+    async fn resolve_cancellation(
+        &self,
+        execution_attempt: &ExecutionAttempt,
+    ) -> anyhow::Result<()> {
+        let Some(outcome_with_gas) = self
+            .receipt_reader
+            .check_execution(execution_attempt)
+            .await?
+        else {
+            return Ok(());
+        };
+
+        // Cancellations have no tx requests, so only the attempt itself is updated.
+        let propagation_input = OutcomePropagationInput {
+            execution_attempt_id: execution_attempt.id,
+            outcome: outcome_with_gas.outcome.clone(),
+            tx_requests_status: TxStatus::FAILED,
+            failed_tx_ids: Vec::new(),
+            retryable: Some(false),
+            used_gas: outcome_with_gas.used_gas,
+            outbox_events: Vec::new(),
+        };
+
+        match outcome_with_gas.outcome {
+            TxExecutionOutcome::SUCCEED | TxExecutionOutcome::FAILED => {
+                self.execution_attempt_repo
+                    .propagate_outcome(&propagation_input)
+                    .await?;
+                self.release_used_nonce(execution_attempt).await?;
+            }
+            TxExecutionOutcome::STUCK | TxExecutionOutcome::DROPPED => {
+                if self.is_behind_lower_nonce(execution_attempt).await? {
+                    return Ok(());
+                }
+                self.execution_attempt_repo
+                    .propagate_outcome(&OutcomePropagationInput {
+                        retryable: Some(true),
+                        ..propagation_input
+                    })
+                    .await?;
+                self.send_cancellation_message(&execution_attempt.id)
+                    .await?;
             }
+            TxExecutionOutcome::REVERTED
+            | TxExecutionOutcome::INVALID
+            | TxExecutionOutcome::CONFLICT => return Ok(()),
+        }
+        Ok(())
+    }
+
+    async fn release_used_nonce(&self, execution_attempt: &ExecutionAttempt) -> anyhow::Result<()> {
+        let nonce = execution_attempt.nonce_used.ok_or(anyhow::anyhow!(
+            "Resolved execution attempt should have nonce"
+        ))?;
+        let lease_owner = self
+            .execution_attempt_repo
+            .find_lease_owner(&execution_attempt.id)
+            .await?;
+        self.wallet_pool
+            .release_used(
+                execution_attempt.operator_wallet_id,
+                lease_owner.as_deref(),
+                nonce,
+            )
+            .await
+    }
+
+    /// Pipelined wallets mine their nonces in order, so an attempt waiting behind a lower
+    /// nonce is neither stuck nor dropped yet and is left for a later poll. When no
+    /// transaction holds the blocking nonce, a cancellation is requested to fill the gap.
+    async fn is_behind_lower_nonce(
+        &self,
+        execution_attempt: &ExecutionAttempt,
+    ) -> anyhow::Result<bool> {
+        let network = self
+            .networks_by_chain_id
+            .get(&execution_attempt.chain_id)
+            .ok_or(anyhow::anyhow!("Network not found"))?;
+        if !is_pipelined(network) {
+            return Ok(false);
+        }
+        let Some(nonce_used) = execution_attempt.nonce_used else {
+            return Ok(false);
+        };
+
+        let wallet = self
+            .wallet_pool
+            .get_by_id(execution_attempt.operator_wallet_id)
+            .await?;
+        let latest_nonce = i64::try_from(wallet.get_latest_nonce().await?)?;
+        if nonce_used <= latest_nonce {
+            return Ok(false);
+        }
+
+        if !self
+            .wallet_pool
+            .is_nonce_reserved(execution_attempt.operator_wallet_id, latest_nonce)
+            .await?
+        {
+            tracing::warn!(
+                "Nonce {latest_nonce} of wallet {} has no transaction and blocks execution attempt {}, requesting gap fill",
+                execution_attempt.operator_wallet_id,
+                execution_attempt.id
+            );
+            let message_body = &RetryQueueMessageBody {
+                execution_attempt_id: execution_attempt.id.to_string(),
+                cancel_nonce: true,
+                gap_nonce: Some(latest_nonce),
+            };
+            self.retry_queue
+                .send_new(&message_body.to_json_string()?)
+                .await?;
         }
+
+        Ok(true)
+    }
+
+    async fn send_cancellation_message(&self, execution_attempt_id: &Uuid) -> anyhow::Result<()> {
+        let message_body = &RetryQueueMessageBody {
+            execution_attempt_id: execution_attempt_id.to_string(),
+            cancel_nonce: true,
+            gap_nonce: None,
+        };
+        self.retry_queue
+            .send_new(&message_body.to_json_string()?)
+            .await?;
         Ok(())
     }
 }
+
+/// Splits the attempt's requests into those in `tx_ids` and the rest.
+fn split_by_tx_ids(
+    execution_attempt_with_txs: &ExecutionAttemptWithTxs,
+    tx_ids: &[String],
+) -> (ExecutionAttemptWithTxs, ExecutionAttemptWithTxs) {
+    let (matching, rest): (Vec<_>, Vec<_>) = execution_attempt_with_txs
+        .tx_requests
+        .iter()
+        .cloned()
+        .partition(|tx_request| tx_ids.contains(&tx_request.tx_id));
+
+    (
+        ExecutionAttemptWithTxs {
+            tx_requests: matching,
+            ..execution_attempt_with_txs.clone()
+        },
+        ExecutionAttemptWithTxs {
+            tx_requests: rest,
+            ..execution_attempt_with_txs.clone()
+        },
+    )
+}
@@ -0,0 +1,224 @@
+use alloy::{
+    network::ReceiptResponse,
+    primitives::{Address, B256, FixedBytes, U256, keccak256},
+    providers::{
+        Provider, ProviderBuilder,
+        fillers::{BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller},
+    },
+    rpc::types::Log,
+};
+use anyhow::bail;
+use db_types::{TxExecutionOutcome, Wei};
+use execution_attempt_db::execution_attempts::ExecutionAttempt;
+use execution_attempt_item_db::execution_attempt_items::ExecutionAttemptItemResult;
+use network_db::networks::Network;
+use outcome_emitter::outcome::OutcomeReceipt;
+use seoa_contract::contract::SEOA;
+use sqlx::types::time::OffsetDateTime;
+use std::str::FromStr;
+use std::{collections::HashMap, time::Duration};
+
+#[cfg(test)]
+mod tests;
+
+type HardlyTypedProvider = FillProvider<
+    JoinFill<
+        alloy::providers::Identity,
+        JoinFill<GasFiller, JoinFill<BlobGasFiller, JoinFill<NonceFiller, ChainIdFiller>>>,
+    >,
+    alloy::providers::RootProvider,
+>;
+
+#[derive(Debug, Clone)]
+pub struct OutcomeWithGas {
+    pub outcome: TxExecutionOutcome,
+    pub used_gas: Option<i64>,
+    /// Executed/BlobSent logs of the sEOA contract found in the receipt. Empty without a receipt.
+    pub item_logs: Vec<ItemLog>,
+    pub receipt: Option<OutcomeReceipt>,
+}
+
+/// One Executed or BlobSent log. The contract emits one per batch item, keyed by its salt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemLog {
+    pub salt: B256,
+    pub success: bool,
+    pub log_index: Option<u64>,
+}
+
+pub struct ReceiptReader {
+    providers_by_chain_id: HashMap<i64, HardlyTypedProvider>,
+    tx_max_age_by_chain_id: HashMap<i64, i64>,
+    contract_address_by_chain_id: HashMap<i64, Address>,
+}
+
+impl ReceiptReader {
+    pub async fn build(networks: &Vec<Network>) -> anyhow::Result<Self> {
+        let mut providers_by_chain_id = HashMap::new();
+        let mut tx_max_age_by_chain_id = HashMap::new();
+        let mut contract_address_by_chain_id = HashMap::new();
+        for network in networks {
+            let provider = ProviderBuilder::new().connect_http(network.rpc_url.parse()?);
+
+            providers_by_chain_id.insert(network.chain_id, provider);
+            tx_max_age_by_chain_id.insert(network.chain_id, network.tx_max_age_sec);
+            contract_address_by_chain_id.insert(
+                network.chain_id,
+                Address::from_str(network.contract_address.as_str())?,
+            );
+        }
+
+        Ok(Self {
+            tx_max_age_by_chain_id,
+            providers_by_chain_id,
+            contract_address_by_chain_id,
+        })
+    }
+
+    pub async fn check_execution(
+        &self,
+        execution_attempt: &ExecutionAttempt,
+    ) -> anyhow::Result<Option<OutcomeWithGas>> {
+        let Some(tx_hash) = execution_attempt.tx_hash.clone() else {
+            return Ok(None);
+        };
+        if execution_attempt.outcome.is_some() {
+            // already resolved by different worker/execution
+            return Ok(None);
+        }
+        let tx_hash = FixedBytes::<32>::from_str(tx_hash.as_str())?;
+
+        let Some(provider) = self.providers_by_chain_id.get(&execution_attempt.chain_id) else {
+            bail!(
+                "Provider not found for chain_id: {}. Tx_hash: {}",
+                &execution_attempt.chain_id,
+                tx_hash
+            );
+        };
+
+        if let Some(receipt) = provider.get_transaction_receipt(tx_hash).await? {
+            let used_gas = Some(i64::try_from(receipt.gas_used())?);
+            let item_logs = match self
+                .contract_address_by_chain_id
+                .get(&execution_attempt.chain_id)
+            {
+                Some(contract_address) => decode_item_logs(receipt.logs(), *contract_address),
+                None => Vec::new(),
+            };
+            let outcome_receipt = outcome_receipt(&receipt);
+            if receipt.status() == true {
+                return Ok(Some(OutcomeWithGas {
+                    outcome: TxExecutionOutcome::SUCCEED,
+                    used_gas,
+                    item_logs,
+                    receipt: outcome_receipt,
+                }));
+            } else {
+                return Ok(Some(OutcomeWithGas {
+                    outcome: TxExecutionOutcome::FAILED,
+                    used_gas,
+                    item_logs,
+                    receipt: outcome_receipt,
+                }));
+            }
+        } else {
+            let tx_max_age = Duration::from_secs(u64::try_from(
+                self.tx_max_age_by_chain_id
+                    .get(&execution_attempt.chain_id)
+                    .expect(
+                        &format!(
+                            "execution attempt with unrecognized chain id: {}",
+                            execution_attempt.chain_id,
+                        )
+                        .to_string(),
+                    )
+                    .clone(),
+            )?);
+            if execution_attempt.created_at + tx_max_age < OffsetDateTime::now_utc() {
+                if let Some(_) = provider.get_transaction_by_hash(tx_hash).await? {
+                    return Ok(Some(OutcomeWithGas {
+                        outcome: TxExecutionOutcome::STUCK,
+                        used_gas: None,
+                        item_logs: Vec::new(),
+                        receipt: None,
+                    }));
+                } else {
+                    return Ok(Some(OutcomeWithGas {
+                        outcome: TxExecutionOutcome::DROPPED,
+                        used_gas: None,
+                        item_logs: Vec::new(),
+                        receipt: None,
+                    }));
+                }
+            } else {
+                return Ok(None);
+            }
+        }
+    }
+}
+
+/// Price and fee paid by the transaction, blob gas included. None while the receipt has no block.
+pub fn outcome_receipt(receipt: &impl ReceiptResponse) -> Option<OutcomeReceipt> {
+    let effective_gas_price = U256::from(receipt.effective_gas_price());
+    let blob_fee = match (receipt.blob_gas_used(), receipt.blob_gas_price()) {
+        (Some(blob_gas_used), Some(blob_gas_price)) => {
+            U256::from(blob_gas_used) * U256::from(blob_gas_price)
+        }
+        _ => U256::ZERO,
+    };
+
+    Some(OutcomeReceipt {
+        effective_gas_price: Wei(effective_gas_price),
+        fee_wei: Wei(U256::from(receipt.gas_used()) * effective_gas_price + blob_fee),
+        block_number: receipt.block_number()?,
+        block_hash: receipt.block_hash()?.to_string(),
+    })
+}
+
+/// Decodes the Executed and BlobSent logs emitted by the sEOA contract at `contract_address`.
+/// Logs of other contracts called by the batch are skipped.
+pub fn decode_item_logs(logs: &[Log], contract_address: Address) -> Vec<ItemLog> {
+    logs.iter()
+        .filter(|log| log.address() == contract_address)
+        .filter_map(|log| {
+            if let Ok(executed) = log.log_decode::<SEOA::Executed>() {
+                return Some(ItemLog {
+                    salt: executed.inner.data.salt,
+                    success: executed.inner.data.success,
+                    log_index: log.log_index,
+                });
+            }
+            log.log_decode::<SEOA::BlobSent>()
+                .ok()
+                .map(|blob_sent| ItemLog {
+                    salt: blob_sent.inner.data.salt,
+                    success: blob_sent.inner.data.success,
+                    log_index: log.log_index,
+                })
+        })
+        .collect()
+}
+
+/// Matches each tx_id to the log carrying its `keccak256(tx_id)` salt. A tx_id without a
+/// successful log did not run, even when the receipt itself succeeded.
+pub fn match_item_results(
+    tx_ids: &[String],
+    item_logs: &[ItemLog],
+) -> anyhow::Result<Vec<ExecutionAttemptItemResult>> {
+    let mut item_results = Vec::with_capacity(tx_ids.len());
+    for tx_id in tx_ids {
+        let salt = keccak256(tx_id.as_bytes());
+        let item_log = item_logs
+            .iter()
+            .find(|item_log| item_log.salt == salt && item_log.success);
+        item_results.push(ExecutionAttemptItemResult {
+            tx_id: tx_id.clone(),
+            executed: item_log.is_some(),
+            log_index: item_log
+                .and_then(|item_log| item_log.log_index)
+                .map(i64::try_from)
+                .transpose()?,
+        });
+    }
+    Ok(item_results)
+}
@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod tests {
+    use crate::receipt::{ItemLog, decode_item_logs, match_item_results, outcome_receipt};
+    use alloy::{
+        consensus::{Receipt, ReceiptEnvelope, ReceiptWithBloom},
+        primitives::{Address, B256, Bloom, Bytes, LogData, keccak256},
+        rpc::types::{Log, TransactionReceipt},
+        sol_types::SolEvent,
+    };
+    use db_types::Wei;
+    use execution_attempt_item_db::execution_attempt_items::ExecutionAttemptItemResult;
+    use seoa_contract::contract::SEOA;
+
+    const CONTRACT_ADDRESS: Address = Address::repeat_byte(0x11);
+    const TARGET_ADDRESS: Address = Address::repeat_byte(0x22);
+
+    fn log(address: Address, data: LogData, log_index: u64) -> Log {
+        Log {
+            inner: alloy::primitives::Log { address, data },
+            log_index: Some(log_index),
+            ..Default::default()
+        }
+    }
+
+    fn executed_log(tx_id: &str, log_index: u64) -> Log {
+        let event = SEOA::Executed {
+            salt: keccak256(tx_id.as_bytes()),
+            submitter: Address::repeat_byte(0x33),
+            success: true,
+        };
+        log(CONTRACT_ADDRESS, event.encode_log_data(), log_index)
+    }
+
+    #[test]
+    fn decodes_executed_and_blob_sent_logs_of_the_contract_only() {
+        let blob_sent = SEOA::BlobSent {
+            salt: keccak256("blob-1".as_bytes()),
+            commitment: keccak256(Bytes::from_static(&[1, 2, 3])),
+            success: true,
+        };
+        let logs = vec![
+            executed_log("tx-1", 3),
+            log(TARGET_ADDRESS, executed_log("tx-2", 4).inner.data, 4),
+            log(CONTRACT_ADDRESS, LogData::default(), 5),
+            log(CONTRACT_ADDRESS, blob_sent.encode_log_data(), 6),
+        ];
+
+        assert_eq!(
+            decode_item_logs(&logs, CONTRACT_ADDRESS),
+            vec![
+                ItemLog {
+                    salt: keccak256("tx-1".as_bytes()),
+                    success: true,
+                    log_index: Some(3),
+                },
+                ItemLog {
+                    salt: keccak256("blob-1".as_bytes()),
+                    success: true,
+                    log_index: Some(6),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_logs_to_tx_ids_by_salt() {
+        let item_logs = decode_item_logs(
+            &[executed_log("tx-2", 8), executed_log("tx-1", 7)],
+            CONTRACT_ADDRESS,
+        );
+        let tx_ids = vec!["tx-1".to_string(), "tx-2".to_string(), "tx-3".to_string()];
+
+        assert_eq!(
+            match_item_results(&tx_ids, &item_logs).unwrap(),
+            vec![
+                ExecutionAttemptItemResult {
+                    tx_id: "tx-1".to_string(),
+                    executed: true,
+                    log_index: Some(7),
+                },
+                ExecutionAttemptItemResult {
+                    tx_id: "tx-2".to_string(),
+                    executed: true,
+                    log_index: Some(8),
+                },
+                ExecutionAttemptItemResult {
+                    tx_id: "tx-3".to_string(),
+                    executed: false,
+                    log_index: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unsuccessful_log_does_not_count_as_executed() {
+        let item_logs = vec![ItemLog {
+            salt: keccak256("tx-1".as_bytes()),
+            success: false,
+            log_index: Some(2),
+        }];
+
+        assert_eq!(
+            match_item_results(&["tx-1".to_string()], &item_logs).unwrap(),
+            vec![ExecutionAttemptItemResult {
+                tx_id: "tx-1".to_string(),
+                executed: false,
+                log_index: None,
+            }]
+        );
+    }
+
+    fn transaction_receipt(
+        block_number: Option<u64>,
+        blob_gas: Option<(u64, u128)>,
+    ) -> TransactionReceipt {
+        TransactionReceipt {
+            inner: ReceiptEnvelope::Eip4844(ReceiptWithBloom {
+                receipt: Receipt {
+                    status: true.into(),
+                    cumulative_gas_used: 50_000,
+                    logs: Vec::new(),
+                },
+                logs_bloom: Bloom::default(),
+            }),
+            transaction_hash: B256::repeat_byte(0x44),
+            transaction_index: Some(0),
+            block_hash: block_number.map(|_| B256::repeat_byte(0x55)),
+            block_number,
+            gas_used: 50_000,
+            effective_gas_price: 3_000_000_000,
+            blob_gas_used: blob_gas.map(|(blob_gas_used, _)| blob_gas_used),
+            blob_gas_price: blob_gas.map(|(_, blob_gas_price)| blob_gas_price),
+            from: Address::repeat_byte(0x33),
+            to: Some(CONTRACT_ADDRESS),
+            contract_address: None,
+        }
+    }
+
+    #[test]
+    fn fee_includes_blob_gas() {
+        let receipt = outcome_receipt(&transaction_receipt(Some(7), Some((131_072, 2)))).unwrap();
+
+        assert_eq!(receipt.effective_gas_price, Wei::from(3_000_000_000u64));
+        assert_eq!(receipt.fee_wei, Wei::from(150_000_000_262_144u64));
+        assert_eq!(receipt.block_number, 7);
+        assert_eq!(receipt.block_hash, B256::repeat_byte(0x55).to_string());
+    }
+
+    #[test]
+    fn receipt_without_block_has_no_outcome_receipt() {
+        assert_eq!(outcome_receipt(&transaction_receipt(None, None)), None);
+    }
+}
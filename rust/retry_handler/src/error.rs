@@ -1,9 +1,12 @@
 use crate::orchestrator::aws::AwsLambdaOrchestrator;
+use blob_tx_sender::error::BlobExecutionErrorHandler;
 use execution_attempt_db::execution_attempts::ExecutionAttemptRepo;
 use execution_attempt_item_db::execution_attempt_items::ExecutionAttemptItemRepo;
-use outcome_emitter::emitter::event_bridge::AwsEventBridgeOutcomeEmitter;
+use outcome_emitter::{emitter::OutcomeEmitter, outbox::OutboxRelay};
+use seoa_contract::contract::ContractManager;
 use sqs_queue::queue::SqsQueue;
 use standard_tx_sender::error::ExecutionErrorHandler;
+use target_abi_db::target_abis::TargetAbiRepo;
 use tx_request_db::repo::TxRequestRepo;
 
 impl ExecutionErrorHandler for AwsLambdaOrchestrator {
@@ -19,11 +22,49 @@ impl ExecutionErrorHandler for AwsLambdaOrchestrator {
         &self.tx_request_repo
     }
 
+    fn target_abi_repo(&self) -> &TargetAbiRepo {
+        &self.target_abi_repo
+    }
+
+    fn retry_queue(&self) -> &SqsQueue {
+        &self.retry_queue
+    }
+
+    fn outcome_emitter(&self) -> &impl OutcomeEmitter {
+        &self.outcome_emitter
+    }
+
+    fn outbox_relay(&self) -> &OutboxRelay {
+        &self.outbox_relay
+    }
+}
+
+impl BlobExecutionErrorHandler for AwsLambdaOrchestrator {
+    fn execution_attempt_repo(&self) -> &ExecutionAttemptRepo {
+        &self.execution_attempt_repo
+    }
+
+    fn execution_attempt_item_repo(&self) -> &ExecutionAttemptItemRepo {
+        &self.execution_attempt_item_repo
+    }
+
+    fn tx_request_repo(&self) -> &TxRequestRepo {
+        &self.tx_request_repo
+    }
+
     fn retry_queue(&self) -> &SqsQueue {
         &self.retry_queue
     }
 
-    fn outcome_emitter(&self) -> &AwsEventBridgeOutcomeEmitter {
+    fn outcome_emitter(&self) -> &impl OutcomeEmitter {
         &self.outcome_emitter
     }
+
+    fn outbox_relay(&self) -> &OutboxRelay {
+        &self.outbox_relay
+    }
+
+    fn contract_manager(&self) -> &ContractManager {
+        &self.contract_manager
+    }
 }
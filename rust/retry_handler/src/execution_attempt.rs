@@ -0,0 +1,87 @@
+use anyhow::bail;
+use db_types::{ExecutionErrorObject, TxExecutionOutcome, TxType, Wei};
+use execution_attempt_db::execution_attempts::NewExecutionAttempt;
+use seoa_contract::{contract::CANCELLATION_GAS_LIMIT, transaction::CancellationTxContext};
+use sqs_queue::message_body::ToJsonString;
+use uuid::Uuid;
+
+pub trait ExecutionAttemptFromCancellation {
+    fn cancellation_sent(
+        tx_context: &CancellationTxContext,
+        operator_wallet_id: Uuid,
+        lease_owner: Option<String>,
+        source_execution_attempt_id: Uuid,
+    ) -> anyhow::Result<NewExecutionAttempt>;
+
+    fn cancellation_failed(
+        tx_context: &CancellationTxContext,
+        operator_wallet_id: Uuid,
+        source_execution_attempt_id: Uuid,
+        error_object: ExecutionErrorObject,
+    ) -> anyhow::Result<NewExecutionAttempt>;
+}
+
+impl ExecutionAttemptFromCancellation for NewExecutionAttempt {
+    fn cancellation_sent(
+        tx_context: &CancellationTxContext,
+        operator_wallet_id: Uuid,
+        lease_owner: Option<String>,
+        source_execution_attempt_id: Uuid,
+    ) -> anyhow::Result<Self> {
+        if tx_context.tx_hash.is_none() {
+            bail!("Can't build sent cancellation without tx_hash");
+        };
+
+        Ok(Self {
+            tx_hash: tx_context.tx_hash.clone(),
+            lease_owner,
+            ..build_cancellation(tx_context, operator_wallet_id, source_execution_attempt_id)?
+        })
+    }
+
+    fn cancellation_failed(
+        tx_context: &CancellationTxContext,
+        operator_wallet_id: Uuid,
+        source_execution_attempt_id: Uuid,
+        error_object: ExecutionErrorObject,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            outcome: Some(TxExecutionOutcome::REVERTED),
+            error_object: Some(error_object.to_json_string()?),
+            retryable: Some(false),
+            ..build_cancellation(tx_context, operator_wallet_id, source_execution_attempt_id)?
+        })
+    }
+}
+
+fn build_cancellation(
+    tx_context: &CancellationTxContext,
+    operator_wallet_id: Uuid,
+    source_execution_attempt_id: Uuid,
+) -> anyhow::Result<NewExecutionAttempt> {
+    let Some(fees) = tx_context.fees else {
+        bail!("Can't build cancellation without fees");
+    };
+
+    Ok(NewExecutionAttempt {
+        chain_id: tx_context.chain_id,
+        operator_wallet_id,
+        nonce_used: Some(i64::try_from(tx_context.nonce)?),
+        tx_value: Wei::ZERO,
+        tx_type: TxType::CANCELLATION,
+        tx_hash: None,
+        gas_limit: Some(i64::try_from(CANCELLATION_GAS_LIMIT)?),
+        used_gas: None,
+        max_fee_per_gas: Some(i64::try_from(fees.max_fee_per_gas)?),
+        max_priority_fee: Some(i64::try_from(fees.max_priority_fee_per_gas)?),
+        max_fee_per_blob_gas: tx_context
+            .max_fee_per_blob_gas
+            .map(i64::try_from)
+            .transpose()?,
+        outcome: None,
+        error_object: None,
+        retryable: None,
+        source_execution_attempt_id: Some(source_execution_attempt_id),
+        lease_owner: None,
+    })
+}
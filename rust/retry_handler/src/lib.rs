@@ -1,8 +1,10 @@
 #![recursion_limit = "256"]
 pub mod error;
+pub mod execution_attempt;
 pub mod orchestrator;
 pub mod transaction;
 
+use outcome_emitter::schema::OutcomeSchemaVersion;
 use std::env;
 
 pub struct Config {
@@ -10,8 +12,10 @@ pub struct Config {
     pub receipt_poller_queue_url: String,
     pub receipt_poller_queue_message_group_id: String,
     pub outcome_event_bus_name: String,
+    pub outcome_schema_version: OutcomeSchemaVersion,
     pub retry_queue_message_group_id: String,
     pub retry_queue_url: String,
+    pub blob_storage_bucket_name: String,
 }
 
 impl Config {
@@ -21,15 +25,19 @@ impl Config {
             Self::get_env_var("RECEIPT_POLLER_QUEUE_MESSAGE_GROUP_ID");
         let receipt_poller_queue_url = Self::get_env_var("RECEIPT_POLLER_QUEUE_URL");
         let outcome_event_bus_name = Self::get_env_var("OUTCOME_EVENT_BUS_NAME");
+        let outcome_schema_version = OutcomeSchemaVersion::from_env()?;
         let retry_queue_message_group_id = Self::get_env_var("RETRY_QUEUE_MESSAGE_GROUP_ID");
         let retry_queue_url = Self::get_env_var("RETRY_QUEUE_URL");
+        let blob_storage_bucket_name = Self::get_env_var("BLOB_STORAGE_BUCKET_NAME");
         Ok(Self {
             database_url,
             receipt_poller_queue_url,
             receipt_poller_queue_message_group_id,
             outcome_event_bus_name,
+            outcome_schema_version,
             retry_queue_message_group_id,
             retry_queue_url,
+            blob_storage_bucket_name,
         })
     }
 
@@ -1,10 +1,20 @@
 #![cfg(feature = "aws")]
 use crate::{
     Config,
-    transaction::{FeeBufferExt, IntoExecuteBatchTxContext, calculate_batch_tx_value},
+    execution_attempt::ExecutionAttemptFromCancellation,
+    transaction::{
+        BlobReplacementFeeExt, FeeCeilingExceeded, IntoBlobBatchTxContext,
+        IntoCancellationTxContext, IntoExecuteBatchTxContext, ReplacementFeeExt,
+        ReplacementFeePolicy, RevertedBatchPlan, load_blob_batch_with_sidecars,
+        plan_reverted_batch,
+    },
 };
 use aws_lambda_events::sqs::{SqsBatchResponse, SqsEvent};
-use db_types::{TxExecutionOutcome, TxStatus};
+use blob_storage::storage::s3::S3BlobStorageManager;
+use blob_tx_sender::{
+    error::BlobExecutionErrorHandler, execution_attempt::ExecutionAttemptFromBlobSuccessful,
+};
+use db_types::{ExecutionErrorObject, TxExecutionOutcome, TxStatus, TxType};
 use execution_attempt_db::{
     execution_attempts::{ExecutionAttempt, ExecutionAttemptRepo, NewExecutionAttempt},
     types::{ExecutionAttemptWithTxInputs, OutcomePropagationInput},
@@ -13,34 +23,56 @@ use execution_attempt_item_db::execution_attempt_items::ExecutionAttemptItemRepo
 use lambda_runtime::{LambdaEvent, tracing};
 use network_db::networks::{Network, NetworkRepo};
 use operator_wallet_db::operator_wallets::OperatorWalletRepo;
-use outcome_emitter::{emitter::event_bridge::AwsEventBridgeOutcomeEmitter, outcome::OutcomeEvent};
+use outbox_db::outbox_events::OutboxEventRepo;
+use outcome_emitter::{
+    emitter::{
+        event_bridge::AwsEventBridgeOutcomeEmitter, routing::RoutingOutcomeEmitter,
+        webhook::WebhookOutcomeEmitter,
+    },
+    lifecycle::LifecycleEvent,
+    outbox::{OutboxRelay, to_outbox_events},
+    outcome::OutcomeEvent,
+};
 use receipt_poller_queue::ReceiptPollerQueueMessageBody;
 use retry_queue::RetryEvent;
 use seoa_contract::{
-    contract::{ContractManager, sEOA::ExecuteInput},
-    transaction::{ExecuteBatchTxContext, IntoExecuteInput},
+    contract::ContractManager,
+    transaction::{BlobBatchTxContext, ExecuteBatchTxContext},
 };
 use sqs_queue::{message_body::ToJsonString, queue::SqsQueue};
 use standard_tx_sender::{
-    error::ExecutionErrorHandler, execution_attempt::ExecutionAttemptFromStandardSuccessful,
+    error::ExecutionErrorHandler,
+    execution_attempt::ExecutionAttemptFromStandardSuccessful,
+    transaction::{
+        IsolatedItems, build_execute_batch_context, isolate_failing_items, simulate_items,
+    },
 };
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::{HashMap, VecDeque},
+    str::FromStr,
+};
+use target_abi_db::target_abis::TargetAbiRepo;
 use tx_request_db::repo::TxRequestRepo;
 use uuid::Uuid;
 use wallet_assignment_db::wallet_assignments::WalletAssignmentRepo;
 use wallet_pool::{manager::WalletPoolManager, wallet::Wallet};
+use webhook_db::{webhook_deliveries::WebhookDeliveryRepo, webhook_endpoints::WebhookEndpointRepo};
 
 pub struct AwsLambdaOrchestrator {
     pub wallet_assignment_repo: WalletAssignmentRepo,
     pub tx_request_repo: TxRequestRepo,
+    pub target_abi_repo: TargetAbiRepo,
     pub execution_attempt_repo: ExecutionAttemptRepo,
     pub execution_attempt_item_repo: ExecutionAttemptItemRepo,
+    pub operator_wallet_repo: OperatorWalletRepo,
     pub wallet_pool_manager: WalletPoolManager,
     pub contract_manager: ContractManager,
     pub networks_by_chain_id: HashMap<i64, Network>,
     pub receipt_poller_queue: SqsQueue,
     pub retry_queue: SqsQueue,
-    pub outcome_emitter: AwsEventBridgeOutcomeEmitter,
+    pub outcome_emitter: RoutingOutcomeEmitter,
+    pub outbox_relay: OutboxRelay,
+    pub blob_storage_manager: S3BlobStorageManager,
 }
 
 impl AwsLambdaOrchestrator {
@@ -56,10 +88,11 @@ impl AwsLambdaOrchestrator {
         let execution_attempt_repo = ExecutionAttemptRepo::new(pool.clone());
         let execution_attempt_item_repo = ExecutionAttemptItemRepo::new(pool.clone());
         let tx_request_repo = TxRequestRepo::new(pool.clone());
+        let target_abi_repo = TargetAbiRepo::new(pool.clone());
         let operator_wallet_repo = OperatorWalletRepo::new(pool.clone());
         let network_repo = NetworkRepo::new(pool.clone());
         let networks = network_repo.select_all().await?;
-        let wallet_pool_manager = WalletPoolManager::build(operator_wallet_repo, &networks);
+        let wallet_pool_manager = WalletPoolManager::build(operator_wallet_repo.clone(), &networks);
         let contract_manager = ContractManager::build(&networks).await?;
         let mut networks_by_chain_id = HashMap::new();
         let sqs_client = aws_sdk_sqs::Client::new(&aws_config);
@@ -76,11 +109,24 @@ impl AwsLambdaOrchestrator {
         )?;
 
         let event_bridge_client = aws_sdk_eventbridge::Client::new(&aws_config);
-        let outcome_emitter = AwsEventBridgeOutcomeEmitter::build(
-            &event_bridge_client,
-            config.outcome_event_bus_name,
+        let outcome_emitter = RoutingOutcomeEmitter::build(
+            AwsEventBridgeOutcomeEmitter::build(
+                &event_bridge_client,
+                config.outcome_event_bus_name,
+                config.outcome_schema_version,
+            ),
+            WebhookOutcomeEmitter::build(
+                WebhookEndpointRepo::new(pool.clone()),
+                WebhookDeliveryRepo::new(pool.clone()),
+                config.outcome_schema_version,
+            )?,
         );
 
+        let outbox_relay = OutboxRelay::new(OutboxEventRepo::new(pool.clone()));
+
+        let blob_storage_manager =
+            S3BlobStorageManager::build(aws_config, &config.blob_storage_bucket_name);
+
         for network in networks {
             networks_by_chain_id.insert(network.chain_id, network.clone());
         }
@@ -92,10 +138,14 @@ impl AwsLambdaOrchestrator {
             contract_manager,
             networks_by_chain_id,
             execution_attempt_item_repo,
+            operator_wallet_repo,
             tx_request_repo,
+            target_abi_repo,
             receipt_poller_queue,
             outcome_emitter,
+            outbox_relay,
             retry_queue,
+            blob_storage_manager,
         })
     }
 
@@ -111,6 +161,23 @@ impl AwsLambdaOrchestrator {
         tracing::info!("Executing...");
 
         for queue_message in event.messages {
+            if let Some(gap_nonce) = queue_message.body.gap_nonce {
+                let blocked_execution_attempt = self
+                    .execution_attempt_repo
+                    .find_by_id(&Uuid::from_str(
+                        queue_message.body.execution_attempt_id.as_str(),
+                    )?)
+                    .await?;
+                self.fill_nonce_gap(
+                    &blocked_execution_attempt,
+                    gap_nonce,
+                    &mut sqs_batch_response,
+                    &queue_message.message_id,
+                )
+                .await?;
+                continue;
+            }
+
             let Some(execution_attempt) = self
                 .execution_attempt_repo
                 .select_and_lock_for_retry(Uuid::from_str(
@@ -125,10 +192,25 @@ impl AwsLambdaOrchestrator {
                 continue;
             };
 
+            if queue_message.body.cancel_nonce {
+                self.cancel_stuck_nonce(
+                    &execution_attempt,
+                    &mut sqs_batch_response,
+                    &queue_message.message_id,
+                )
+                .await?;
+                continue;
+            }
+
             if let Some(ref outcome) = execution_attempt.execution_attempt.outcome {
                 match outcome {
                     TxExecutionOutcome::STUCK | TxExecutionOutcome::DROPPED => {
-                        self.retry_stuck_or_dropped(&execution_attempt).await?
+                        self.retry_stuck_or_dropped(
+                            &execution_attempt,
+                            &mut sqs_batch_response,
+                            &queue_message.message_id,
+                        )
+                        .await?
                     }
                     TxExecutionOutcome::REVERTED => {
                         self.retry_reverted(
@@ -138,7 +220,10 @@ impl AwsLambdaOrchestrator {
                         )
                         .await?
                     }
-                    TxExecutionOutcome::FAILED | TxExecutionOutcome::SUCCEED => continue,
+                    TxExecutionOutcome::FAILED
+                    | TxExecutionOutcome::SUCCEED
+                    | TxExecutionOutcome::INVALID
+                    | TxExecutionOutcome::CONFLICT => continue,
                 }
             }
         }
@@ -149,6 +234,8 @@ impl AwsLambdaOrchestrator {
     async fn retry_stuck_or_dropped(
         &self,
         retried_execution_attempt: &ExecutionAttemptWithTxInputs,
+        sqs_batch_response: &mut SqsBatchResponse,
+        queue_message_id: &str,
     ) -> anyhow::Result<()> {
         let network = self
             .networks_by_chain_id
@@ -174,9 +261,36 @@ impl AwsLambdaOrchestrator {
         )?;
 
         if latest_nonce == retried_execution_nonce {
+            if retried_execution_attempt.execution_attempt.tx_type == TxType::BLOB {
+                return self
+                    .retry_stuck_or_dropped_blob(
+                        retried_execution_attempt,
+                        network,
+                        &wallet,
+                        sqs_batch_response,
+                        queue_message_id,
+                    )
+                    .await;
+            }
+
             let mut tx_context = retried_execution_attempt.into_execute_batch_context()?;
 
-            tx_context.apply_fee_buffer(u128::try_from(network.gas_estimation_buffer_ppm)?)?;
+            let live_fees = self
+                .contract_manager
+                .estimate_eip1559_fees(tx_context.chain_id)
+                .await?;
+            if let Err(err) = tx_context
+                .apply_replacement_fees(&ReplacementFeePolicy::standard(network)?, live_fees)
+            {
+                return self
+                    .defer_if_above_fee_ceiling(
+                        err,
+                        retried_execution_attempt,
+                        sqs_batch_response,
+                        queue_message_id,
+                    )
+                    .await;
+            }
 
             match self
                 .contract_manager
@@ -184,10 +298,15 @@ impl AwsLambdaOrchestrator {
                 .await
             {
                 Ok(_) => {
+                    let lease_owner = self
+                        .execution_attempt_repo
+                        .find_lease_owner(&retried_execution_attempt.execution_attempt.id)
+                        .await?;
                     let new_execution_attempt = self
                         .save_successful_tx(
                             &tx_context,
                             &wallet,
+                            lease_owner,
                             &retried_execution_attempt.execution_attempt.id,
                         )
                         .await?;
@@ -200,27 +319,463 @@ impl AwsLambdaOrchestrator {
                 }
                 Err(err) => {
                     tracing::error!("{err:?}");
-                    self.handle_error(&tx_context, &wallet, err).await?;
+                    let failed_execution_attempt =
+                        self.handle_error(&tx_context, &wallet, err).await?;
+                    self.execution_attempt_repo
+                        .set_source_execution_attempt_id(
+                            &failed_execution_attempt.id,
+                            &retried_execution_attempt.execution_attempt.id,
+                        )
+                        .await?;
                 }
             };
         } else {
+            self.resolve_mined_replacement(retried_execution_attempt, &wallet)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// The nonce of the retried attempt was mined, possibly by an attempt it replaced. That
+    /// attempt is handed back to the receipt poller, which propagates its outcome and releases
+    /// the nonce. Without one, the nonce was mined elsewhere and the wallet is released.
+    async fn resolve_mined_replacement(
+        &self,
+        retried_execution_attempt: &ExecutionAttemptWithTxInputs,
+        wallet: &Wallet,
+    ) -> anyhow::Result<()> {
+        let retried_execution_attempt_id = retried_execution_attempt.execution_attempt.id;
+        for replaced_execution_attempt in self
+            .execution_attempt_repo
+            .select_replaced(&retried_execution_attempt_id)
+            .await?
+        {
+            let Some(tx_hash) = &replaced_execution_attempt.tx_hash else {
+                continue;
+            };
+            if !wallet.is_mined(tx_hash).await? {
+                continue;
+            }
+
+            tracing::info!(
+                "Execution attempt {} was mined in place of its replacement {}",
+                replaced_execution_attempt.id,
+                retried_execution_attempt_id
+            );
+            if self
+                .execution_attempt_repo
+                .reopen(&replaced_execution_attempt.id)
+                .await?
+            {
+                let receipt_poller_queue_message_body = ReceiptPollerQueueMessageBody {
+                    execution_attempt_id: replaced_execution_attempt.id.to_string(),
+                    batch_size: u8::try_from(retried_execution_attempt.tx_requests.len())?,
+                };
+                self.receipt_poller_queue
+                    .send_new(&receipt_poller_queue_message_body.to_json_string()?)
+                    .await?;
+            }
+            return Ok(());
+        }
+
+        tracing::warn!(
+            "No stuck transaction found for execution attempt: {retried_execution_attempt:?}"
+        );
+        self.wallet_pool_manager
+            .release_mined_elsewhere(
+                wallet,
+                self.execution_attempt_repo
+                    .find_lease_owner(&retried_execution_attempt_id)
+                    .await?
+                    .as_deref(),
+            )
+            .await
+    }
+
+    async fn retry_stuck_or_dropped_blob(
+        &self,
+        retried_execution_attempt: &ExecutionAttemptWithTxInputs,
+        network: &Network,
+        wallet: &Wallet,
+        sqs_batch_response: &mut SqsBatchResponse,
+        queue_message_id: &str,
+    ) -> anyhow::Result<()> {
+        let mut tx_context = retried_execution_attempt
+            .into_blob_batch_context(&self.blob_storage_manager)
+            .await?;
+
+        let replacement_fee_policy = ReplacementFeePolicy::blob(network)?;
+        let live_fees = self
+            .contract_manager
+            .estimate_eip1559_fees(tx_context.chain_id)
+            .await?;
+        let live_max_fee_per_blob_gas = self
+            .contract_manager
+            .estimate_max_fee_per_blob_gas(tx_context.chain_id)
+            .await?;
+        if let Err(err) = tx_context
+            .apply_replacement_fees(&replacement_fee_policy, live_fees)
+            .and_then(|_| {
+                tx_context
+                    .apply_blob_replacement_fee(&replacement_fee_policy, live_max_fee_per_blob_gas)
+            })
+        {
+            return self
+                .defer_if_above_fee_ceiling(
+                    err,
+                    retried_execution_attempt,
+                    sqs_batch_response,
+                    queue_message_id,
+                )
+                .await;
+        }
+
+        match self
+            .contract_manager
+            .send_blob_batch(&mut tx_context, wallet)
+            .await
+        {
+            Ok(_) => {
+                let lease_owner = self
+                    .execution_attempt_repo
+                    .find_lease_owner(&retried_execution_attempt.execution_attempt.id)
+                    .await?;
+                let new_execution_attempt = self
+                    .save_successful_blob_tx(
+                        &tx_context,
+                        wallet,
+                        lease_owner,
+                        &retried_execution_attempt.execution_attempt.id,
+                    )
+                    .await?;
+
+                self.send_blob_receipt_poller_queue_message(
+                    &tx_context,
+                    &new_execution_attempt.id.to_string(),
+                )
+                .await?;
+            }
+            Err(err) => {
+                tracing::error!("{err:?}");
+                if let Some(failed_execution_attempt) =
+                    self.handle_blob_error(&tx_context, wallet, err).await?
+                {
+                    self.execution_attempt_repo
+                        .set_source_execution_attempt_id(
+                            &failed_execution_attempt.id,
+                            &retried_execution_attempt.execution_attempt.id,
+                        )
+                        .await?;
+                }
+            }
+        };
+
+        Ok(())
+    }
+
+    async fn cancel_stuck_nonce(
+        &self,
+        stuck_execution_attempt: &ExecutionAttemptWithTxInputs,
+        sqs_batch_response: &mut SqsBatchResponse,
+        queue_message_id: &str,
+    ) -> anyhow::Result<()> {
+        let stuck_execution_attempt_id = stuck_execution_attempt.execution_attempt.id;
+        let network = self
+            .networks_by_chain_id
+            .get(&stuck_execution_attempt.execution_attempt.chain_id)
+            .ok_or(anyhow::anyhow!("Network not found"))?;
+        let wallet = self
+            .wallet_pool_manager
+            .get_by_id(stuck_execution_attempt.execution_attempt.operator_wallet_id)
+            .await?;
+
+        let mut tx_context = stuck_execution_attempt
+            .execution_attempt
+            .into_cancellation_context()?;
+
+        if wallet.get_latest_nonce().await? > tx_context.nonce {
             tracing::warn!(
-                "No stuck transaction found for execution attempt: {retried_execution_attempt:?}"
+                "Nonce {} of wallet {} was already mined, releasing wallet without cancellation",
+                tx_context.nonce,
+                wallet.db_record.id
             );
+            self.wallet_pool_manager
+                .release_mined_elsewhere(
+                    &wallet,
+                    self.execution_attempt_repo
+                        .find_lease_owner(&stuck_execution_attempt_id)
+                        .await?
+                        .as_deref(),
+                )
+                .await?;
+            return Ok(());
+        }
+
+        let live_fees = self
+            .contract_manager
+            .estimate_eip1559_fees(tx_context.chain_id)
+            .await?;
+        let replacement_fees = match tx_context.max_fee_per_blob_gas {
+            Some(_) => {
+                let replacement_fee_policy = ReplacementFeePolicy::blob(network)?;
+                let live_max_fee_per_blob_gas = self
+                    .contract_manager
+                    .estimate_max_fee_per_blob_gas(tx_context.chain_id)
+                    .await?;
+                tx_context
+                    .apply_replacement_fees(&replacement_fee_policy, live_fees)
+                    .and_then(|_| {
+                        tx_context.apply_blob_replacement_fee(
+                            &replacement_fee_policy,
+                            live_max_fee_per_blob_gas,
+                        )
+                    })
+            }
+            None => tx_context
+                .apply_replacement_fees(&ReplacementFeePolicy::standard(network)?, live_fees),
+        };
+        if let Err(err) = replacement_fees {
+            return self
+                .defer_if_above_fee_ceiling(
+                    err,
+                    stuck_execution_attempt,
+                    sqs_batch_response,
+                    queue_message_id,
+                )
+                .await;
         }
 
+        match self
+            .contract_manager
+            .send_cancellation(&mut tx_context, &wallet)
+            .await
+        {
+            Ok(_) => {
+                let cancellation_execution_attempt = self
+                    .execution_attempt_repo
+                    .insert(&NewExecutionAttempt::cancellation_sent(
+                        &tx_context,
+                        wallet.db_record.id,
+                        self.execution_attempt_repo
+                            .find_lease_owner(&stuck_execution_attempt_id)
+                            .await?,
+                        stuck_execution_attempt_id,
+                    )?)
+                    .await?;
+
+                let receipt_poller_queue_message_body = ReceiptPollerQueueMessageBody {
+                    execution_attempt_id: cancellation_execution_attempt.id.to_string(),
+                    batch_size: 0,
+                };
+                self.receipt_poller_queue
+                    .send_new(&receipt_poller_queue_message_body.to_json_string()?)
+                    .await?;
+            }
+            Err(err) => {
+                tracing::error!("{err:?}");
+                self.execution_attempt_repo
+                    .insert(&NewExecutionAttempt::cancellation_failed(
+                        &tx_context,
+                        wallet.db_record.id,
+                        stuck_execution_attempt_id,
+                        ExecutionErrorObject {
+                            error_type: "Cancellation error".to_string(),
+                            error_body: Some(err.to_string()),
+                            revert_reason: None,
+                        },
+                    )?)
+                    .await?;
+                // The stuck nonce still blocks the wallet, so the message is redelivered.
+                self.execution_attempt_repo
+                    .release_retry_lock(&stuck_execution_attempt_id)
+                    .await?;
+                sqs_batch_response.add_failure(queue_message_id);
+            }
+        };
+
         Ok(())
     }
 
+    /// Sends a cancellation on an unused nonce of a pipelined wallet, so the attempts queued
+    /// above it can be mined.
+    async fn fill_nonce_gap(
+        &self,
+        blocked_execution_attempt: &ExecutionAttempt,
+        gap_nonce: i64,
+        sqs_batch_response: &mut SqsBatchResponse,
+        queue_message_id: &str,
+    ) -> anyhow::Result<()> {
+        let wallet = self
+            .wallet_pool_manager
+            .get_by_id(blocked_execution_attempt.operator_wallet_id)
+            .await?;
+
+        if wallet.get_latest_nonce().await? > u64::try_from(gap_nonce)? {
+            tracing::info!(
+                "Nonce {gap_nonce} of wallet {} was already mined",
+                wallet.db_record.id
+            );
+            return Ok(());
+        }
+        // Built before the reservation, so an RPC error does not leave the gap nonce reserved.
+        let mut tx_context = blocked_execution_attempt.into_cancellation_context()?;
+        tx_context.nonce = u64::try_from(gap_nonce)?;
+        tx_context.fees = Some(
+            self.contract_manager
+                .estimate_eip1559_fees(tx_context.chain_id)
+                .await?,
+        );
+        if tx_context.max_fee_per_blob_gas.is_some() {
+            tx_context.max_fee_per_blob_gas = Some(
+                self.contract_manager
+                    .estimate_max_fee_per_blob_gas(tx_context.chain_id)
+                    .await?,
+            );
+        }
+
+        if !self
+            .wallet_pool_manager
+            .reserve_gap_nonce(wallet.db_record.id, gap_nonce)
+            .await?
+        {
+            tracing::info!(
+                "Nonce {gap_nonce} of wallet {} was reserved in the meantime",
+                wallet.db_record.id
+            );
+            return Ok(());
+        }
+
+        match self
+            .contract_manager
+            .send_cancellation(&mut tx_context, &wallet)
+            .await
+        {
+            Ok(_) => {
+                let cancellation_execution_attempt = self
+                    .execution_attempt_repo
+                    .insert(&NewExecutionAttempt::cancellation_sent(
+                        &tx_context,
+                        wallet.db_record.id,
+                        None,
+                        blocked_execution_attempt.id,
+                    )?)
+                    .await?;
+                self.wallet_pool_manager
+                    .mark_gap_nonce_broadcasted(wallet.db_record.id, gap_nonce)
+                    .await?;
+
+                let receipt_poller_queue_message_body = ReceiptPollerQueueMessageBody {
+                    execution_attempt_id: cancellation_execution_attempt.id.to_string(),
+                    batch_size: 0,
+                };
+                self.receipt_poller_queue
+                    .send_new(&receipt_poller_queue_message_body.to_json_string()?)
+                    .await?;
+            }
+            Err(err) => {
+                tracing::error!("{err:?}");
+                self.execution_attempt_repo
+                    .insert(&NewExecutionAttempt::cancellation_failed(
+                        &tx_context,
+                        wallet.db_record.id,
+                        blocked_execution_attempt.id,
+                        ExecutionErrorObject {
+                            error_type: "Cancellation error".to_string(),
+                            error_body: Some(err.to_string()),
+                            revert_reason: None,
+                        },
+                    )?)
+                    .await?;
+                self.wallet_pool_manager
+                    .release_gap_nonce(wallet.db_record.id, gap_nonce)
+                    .await?;
+                sqs_batch_response.add_failure(queue_message_id);
+            }
+        };
+
+        Ok(())
+    }
+
+    async fn defer_if_above_fee_ceiling(
+        &self,
+        error: anyhow::Error,
+        retried_execution_attempt: &ExecutionAttemptWithTxInputs,
+        sqs_batch_response: &mut SqsBatchResponse,
+        queue_message_id: &str,
+    ) -> anyhow::Result<()> {
+        let Some(ceiling_error) = error.downcast_ref::<FeeCeilingExceeded>() else {
+            return Err(error);
+        };
+
+        tracing::warn!(
+            "Deferring replacement of execution attempt {}: {ceiling_error}",
+            retried_execution_attempt.execution_attempt.id
+        );
+        self.execution_attempt_repo
+            .release_retry_lock(&retried_execution_attempt.execution_attempt.id)
+            .await?;
+        sqs_batch_response.add_failure(queue_message_id);
+
+        Ok(())
+    }
+
+    async fn save_successful_blob_tx(
+        &self,
+        tx_context: &BlobBatchTxContext,
+        wallet: &Wallet,
+        lease_owner: Option<String>,
+        retried_execution_attempt_id: &Uuid,
+    ) -> anyhow::Result<ExecutionAttempt> {
+        let execution_attempt_input = NewExecutionAttempt::blob_successful(
+            tx_context,
+            wallet.db_record.id,
+            lease_owner,
+            Some(*retried_execution_attempt_id),
+        )?;
+
+        let new_execution_attempt = self
+            .execution_attempt_repo
+            .insert(&execution_attempt_input)
+            .await?;
+
+        self.execution_attempt_item_repo
+            .insert_many(new_execution_attempt.id, &tx_context.get_tx_ids())
+            .await?;
+
+        let outbox_events = to_outbox_events(
+            &LifecycleEvent::for_tx_requests(
+                &tx_context.tx_requests,
+                TxStatus::BROADCASTED,
+                new_execution_attempt.tx_hash.clone(),
+                Some(wallet.db_record.wallet_address.clone()),
+            )
+            .into_iter()
+            .map(LifecycleEvent::next_attempt)
+            .collect::<Vec<_>>(),
+        )?;
+        self.tx_request_repo
+            .mark_many_as_broadcasted_and_bump_attempts(&tx_context.get_tx_ids(), &outbox_events)
+            .await?;
+
+        self.outbox_relay
+            .relay(&self.outcome_emitter, &outbox_events)
+            .await?;
+
+        Ok(new_execution_attempt)
+    }
+
     async fn save_successful_tx(
         &self,
         tx_context: &ExecuteBatchTxContext,
         wallet: &Wallet,
+        lease_owner: Option<String>,
         retried_execution_attempt_id: &Uuid,
     ) -> anyhow::Result<ExecutionAttempt> {
         let execution_attempt_input = NewExecutionAttempt::standard_successful(
             &tx_context,
             wallet.db_record.id,
+            lease_owner,
             Some(retried_execution_attempt_id.clone()),
         )?;
 
@@ -233,8 +788,23 @@ impl AwsLambdaOrchestrator {
             .insert_many(new_execution_attempt.id, &tx_context.get_tx_ids())
             .await?;
 
+        let outbox_events = to_outbox_events(
+            &LifecycleEvent::for_tx_requests(
+                &tx_context.tx_requests,
+                TxStatus::BROADCASTED,
+                new_execution_attempt.tx_hash.clone(),
+                Some(wallet.db_record.wallet_address.clone()),
+            )
+            .into_iter()
+            .map(LifecycleEvent::next_attempt)
+            .collect::<Vec<_>>(),
+        )?;
         self.tx_request_repo
-            .mark_many_as_broadcasted_and_bump_attempts(&tx_context.get_tx_ids())
+            .mark_many_as_broadcasted_and_bump_attempts(&tx_context.get_tx_ids(), &outbox_events)
+            .await?;
+
+        self.outbox_relay
+            .relay(&self.outcome_emitter, &outbox_events)
             .await?;
 
         Ok(new_execution_attempt)
@@ -256,54 +826,363 @@ impl AwsLambdaOrchestrator {
         Ok(())
     }
 
+    async fn send_blob_receipt_poller_queue_message(
+        &self,
+        tx_context: &BlobBatchTxContext,
+        execution_attempt_id: &str,
+    ) -> anyhow::Result<()> {
+        let receipt_poller_queue_message_body = ReceiptPollerQueueMessageBody {
+            execution_attempt_id: execution_attempt_id.to_string(),
+            batch_size: u8::try_from(tx_context.tx_requests.len())?,
+        };
+
+        self.receipt_poller_queue
+            .send_new(&receipt_poller_queue_message_body.to_json_string()?)
+            .await?;
+        Ok(())
+    }
+
     fn split_into_execute_batch_context(
         &self,
         execution_attempt: &ExecutionAttemptWithTxInputs,
     ) -> anyhow::Result<Vec<ExecuteBatchTxContext>> {
-        let mut execute_batch_contexts = Vec::new();
-
         let use_operator_wallet_id = execution_attempt.tx_requests[0].use_operator_wallet_id;
 
         let mid = execution_attempt.tx_requests.len().div_ceil(2);
 
         let (tx_request_batch_a, tx_request_batch_b) = execution_attempt.tx_requests.split_at(mid);
 
-        let context_a = ExecuteBatchTxContext {
-            chain_id: execution_attempt.execution_attempt.chain_id,
-            use_operator_wallet_id,
-            execute_batch_input: tx_request_batch_a
-                .iter()
-                .map(|tx_request| tx_request.into_execute_input())
-                .collect::<anyhow::Result<Vec<ExecuteInput>>>()?,
-            batch_tx_value: calculate_batch_tx_value(&tx_request_batch_a.to_vec())?,
-            tx_requests: tx_request_batch_a.to_vec(),
-            successfully_simulated: false,
-            assigned_nonce: None,
-            fees: None,
-            gas_limit: None,
-            tx_hash: None,
+        Ok(vec![
+            build_execute_batch_context(
+                execution_attempt.execution_attempt.chain_id,
+                use_operator_wallet_id,
+                tx_request_batch_a,
+            )?,
+            build_execute_batch_context(
+                execution_attempt.execution_attempt.chain_id,
+                use_operator_wallet_id,
+                tx_request_batch_b,
+            )?,
+        ])
+    }
+
+    async fn split_into_blob_batch_context(
+        &self,
+        execution_attempt: &ExecutionAttemptWithTxInputs,
+    ) -> anyhow::Result<Vec<BlobBatchTxContext>> {
+        let mid = execution_attempt.tx_requests.len().div_ceil(2);
+
+        let (tx_request_batch_a, tx_request_batch_b) = execution_attempt.tx_requests.split_at(mid);
+
+        let mut blob_batch_contexts = Vec::new();
+        for tx_request_batch in [tx_request_batch_a, tx_request_batch_b] {
+            blob_batch_contexts.push(BlobBatchTxContext {
+                chain_id: execution_attempt.execution_attempt.chain_id,
+                blob_batch_with_sidecar_vec: load_blob_batch_with_sidecars(
+                    tx_request_batch,
+                    &self.blob_storage_manager,
+                )
+                .await?,
+                use_operator_wallet_id: None,
+                tx_requests: tx_request_batch.to_vec(),
+                successfully_simulated: false,
+                assigned_nonce: None,
+                fees: None,
+                max_fee_per_blob_gas: None,
+                gas_limit: None,
+                tx_hash: None,
+            });
+        }
+
+        Ok(blob_batch_contexts)
+    }
+
+    async fn retry_reverted_blob(
+        &self,
+        retried_execution_attempt: &ExecutionAttemptWithTxInputs,
+        sqs_batch_response: &mut SqsBatchResponse,
+        queue_message_id: &str,
+    ) -> anyhow::Result<()> {
+        let mut split_blob_batch_context: VecDeque<BlobBatchTxContext> = self
+            .split_into_blob_batch_context(retried_execution_attempt)
+            .await?
+            .into();
+
+        // The items of a half that are not already used go back in as a new batch
+        while let Some(mut tx_context) = split_blob_batch_context.pop_front() {
+            let Some(mut wallet) = self
+                .wallet_pool_manager
+                .acquire(tx_context.chain_id, None, tx_context.get_requester_id())
+                .await?
+            else {
+                sqs_batch_response.add_failure(queue_message_id);
+                continue;
+            };
+
+            self.wallet_assignment_repo
+                .new_assignments(&tx_context.get_tx_ids(), wallet.db_record.id)
+                .await?;
+
+            if let Err(err) = self
+                .contract_manager
+                .simulate_send_blob_batch_tx(&mut tx_context, &mut wallet)
+                .await
+            {
+                let rest = self
+                    .fail_retried_blob_batch(&tx_context, &wallet, err, retried_execution_attempt)
+                    .await?;
+                split_blob_batch_context.extend(rest);
+                continue;
+            };
+
+            match self
+                .contract_manager
+                .send_blob_batch(&mut tx_context, &wallet)
+                .await
+            {
+                Ok(_) => {
+                    let new_execution_attempt = self
+                        .save_successful_blob_tx(
+                            &tx_context,
+                            &wallet,
+                            wallet.db_record.lease_owner.clone(),
+                            &retried_execution_attempt.execution_attempt.id,
+                        )
+                        .await?;
+                    self.wallet_pool_manager
+                        .release_sent(&wallet, tx_context.assigned_nonce)
+                        .await?;
+                    self.send_blob_receipt_poller_queue_message(
+                        &tx_context,
+                        &new_execution_attempt.id.to_string(),
+                    )
+                    .await?;
+                }
+                Err(err) => {
+                    let rest = self
+                        .fail_retried_blob_batch(
+                            &tx_context,
+                            &wallet,
+                            err,
+                            retried_execution_attempt,
+                        )
+                        .await?;
+                    split_blob_batch_context.extend(rest);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Releases the unused wallet and records the failed attempt as a retry of the reverted one.
+    /// Returns the items that can still be sent when only some of the batch was already used.
+    async fn fail_retried_blob_batch(
+        &self,
+        tx_context: &BlobBatchTxContext,
+        wallet: &Wallet,
+        err: anyhow::Error,
+        retried_execution_attempt: &ExecutionAttemptWithTxInputs,
+    ) -> anyhow::Result<Option<BlobBatchTxContext>> {
+        tracing::error!("{err:?}");
+        self.wallet_pool_manager
+            .release_unused(wallet.db_record.id)
+            .await?;
+        let failed_blob_batch = self
+            .handle_new_blob_batch_error(tx_context, wallet, err)
+            .await?;
+        self.execution_attempt_repo
+            .set_source_execution_attempt_id(
+                &failed_blob_batch.execution_attempt.id,
+                &retried_execution_attempt.execution_attempt.id,
+            )
+            .await?;
+
+        Ok(failed_blob_batch.rest)
+    }
+
+    /// Simulates every item of the reverted batch on its own. Items that revert are failed with
+    /// their decoded reason, items whose salt is already used with `AlreadyUsed`, and the rest is
+    /// re-sent as one batch. When no item reverts on its
+    /// own, only their combination does, and the batch is halved instead.
+    async fn retry_reverted_standard(
+        &self,
+        retried_execution_attempt: &ExecutionAttemptWithTxInputs,
+        sqs_batch_response: &mut SqsBatchResponse,
+        queue_message_id: &String,
+    ) -> anyhow::Result<()> {
+        let chain_id = retried_execution_attempt.execution_attempt.chain_id;
+        let batch_context =
+            build_execute_batch_context(chain_id, None, &retried_execution_attempt.tx_requests)?;
+
+        let Some(wallet) = self
+            .wallet_pool_manager
+            .acquire(chain_id, None, batch_context.get_requester_id())
+            .await?
+        else {
+            sqs_batch_response.add_failure(queue_message_id);
+            return Ok(());
         };
 
-        let context_b = ExecuteBatchTxContext {
-            chain_id: execution_attempt.execution_attempt.chain_id,
-            use_operator_wallet_id,
-            execute_batch_input: tx_request_batch_b
-                .iter()
-                .map(|tx_request| tx_request.into_execute_input())
-                .collect::<anyhow::Result<Vec<ExecuteInput>>>()?,
-            batch_tx_value: calculate_batch_tx_value(&tx_request_batch_b.to_vec())?,
-            tx_requests: tx_request_batch_b.to_vec(),
-            successfully_simulated: false,
-            assigned_nonce: None,
-            fees: None,
-            gas_limit: None,
-            tx_hash: None,
+        let isolated_items = match self.simulate_items(&batch_context, &wallet).await {
+            Ok(isolated_items) => isolated_items,
+            Err(err) => {
+                self.wallet_pool_manager
+                    .release_unused(wallet.db_record.id)
+                    .await?;
+                return Err(err);
+            }
+        };
+
+        let (unsendable, healthy_tx_requests) = match plan_reverted_batch(&isolated_items) {
+            RevertedBatchPlan::Halve => {
+                self.wallet_pool_manager
+                    .release_unused(wallet.db_record.id)
+                    .await?;
+                return self
+                    .retry_reverted_by_halving(
+                        retried_execution_attempt,
+                        sqs_batch_response,
+                        queue_message_id,
+                    )
+                    .await;
+            }
+            RevertedBatchPlan::Resend {
+                unsendable,
+                healthy,
+            } => (unsendable, healthy),
+        };
+
+        for (tx_request, error_object) in unsendable {
+            let failed_execution_attempt = self
+                .fail_isolated_item(&tx_request, &wallet, error_object)
+                .await?;
+            self.execution_attempt_repo
+                .set_source_execution_attempt_id(
+                    &failed_execution_attempt.id,
+                    &retried_execution_attempt.execution_attempt.id,
+                )
+                .await?;
+        }
+
+        if healthy_tx_requests.is_empty() {
+            self.wallet_pool_manager
+                .release_unused(wallet.db_record.id)
+                .await?;
+            return Ok(());
+        }
+
+        let tx_context = build_execute_batch_context(chain_id, None, &healthy_tx_requests)?;
+        self.send_retried_batch(tx_context, wallet, retried_execution_attempt)
+            .await
+    }
+
+    async fn simulate_items(
+        &self,
+        batch_context: &ExecuteBatchTxContext,
+        wallet: &Wallet,
+    ) -> anyhow::Result<IsolatedItems> {
+        let target_abis = self.load_target_abis(batch_context).await?;
+        let item_simulations =
+            simulate_items(&self.contract_manager, batch_context, wallet, &target_abis).await?;
+
+        isolate_failing_items(&batch_context.tx_requests, item_simulations)
+    }
+
+    async fn retry_reverted_by_halving(
+        &self,
+        retried_execution_attempt: &ExecutionAttemptWithTxInputs,
+        sqs_batch_response: &mut SqsBatchResponse,
+        queue_message_id: &String,
+    ) -> anyhow::Result<()> {
+        let split_execute_batch_context =
+            self.split_into_execute_batch_context(retried_execution_attempt)?;
+
+        for tx_context in split_execute_batch_context {
+            let Some(wallet) = self
+                .wallet_pool_manager
+                .acquire(tx_context.chain_id, None, tx_context.get_requester_id())
+                .await?
+            else {
+                sqs_batch_response.add_failure(queue_message_id);
+                continue;
+            };
+
+            self.send_retried_batch(tx_context, wallet, retried_execution_attempt)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn send_retried_batch(
+        &self,
+        mut tx_context: ExecuteBatchTxContext,
+        mut wallet: Wallet,
+        retried_execution_attempt: &ExecutionAttemptWithTxInputs,
+    ) -> anyhow::Result<()> {
+        self.wallet_assignment_repo
+            .new_assignments(&tx_context.get_tx_ids(), wallet.db_record.id)
+            .await?;
+
+        if let Err(err) = self
+            .contract_manager
+            .simulate_send_batch_tx(&mut tx_context, &mut wallet)
+            .await
+        {
+            tracing::error!("{err:?}");
+            self.wallet_pool_manager
+                .release_unused(wallet.db_record.id)
+                .await?;
+            let failed_execution_attempt = self.handle_error(&tx_context, &wallet, err).await?;
+            self.execution_attempt_repo
+                .set_source_execution_attempt_id(
+                    &failed_execution_attempt.id,
+                    &retried_execution_attempt.execution_attempt.id,
+                )
+                .await?;
+            return Ok(());
         };
 
-        execute_batch_contexts.push(context_a);
-        execute_batch_contexts.push(context_b);
+        match self
+            .contract_manager
+            .send_batch(&mut tx_context, &wallet)
+            .await
+        {
+            Ok(_) => {
+                let new_execution_attempt = self
+                    .save_successful_tx(
+                        &tx_context,
+                        &wallet,
+                        wallet.db_record.lease_owner.clone(),
+                        &retried_execution_attempt.execution_attempt.id,
+                    )
+                    .await?;
+                self.wallet_pool_manager
+                    .release_sent(&wallet, tx_context.assigned_nonce)
+                    .await?;
+                self.send_receipt_poller_queue_message(
+                    &tx_context,
+                    &new_execution_attempt.id.to_string(),
+                )
+                .await?;
+            }
+            Err(err) => {
+                tracing::error!("{err:?}");
+                self.wallet_pool_manager
+                    .release_unused(wallet.db_record.id)
+                    .await?;
+                let failed_execution_attempt = self.handle_error(&tx_context, &wallet, err).await?;
+                self.execution_attempt_repo
+                    .set_source_execution_attempt_id(
+                        &failed_execution_attempt.id,
+                        &retried_execution_attempt.execution_attempt.id,
+                    )
+                    .await?;
+            }
+        }
 
-        Ok(execute_batch_contexts)
+        Ok(())
     }
 
     async fn retry_reverted(
@@ -319,131 +1198,91 @@ impl AwsLambdaOrchestrator {
             tracing::warn!(
                 "Can't handle reverted execution with use_operator_wallet_id. Marking as FAILED..."
             );
-            self.execution_attempt_repo
-                .propagate_outcome(&OutcomePropagationInput {
-                    execution_attempt_id: retried_execution_attempt.execution_attempt.id,
-                    outcome: TxExecutionOutcome::FAILED,
-                    tx_requests_status: TxStatus::FAILED,
-                    retryable: Some(false),
-                    used_gas: retried_execution_attempt.execution_attempt.used_gas,
-                })
-                .await?;
-            for tx_request in retried_execution_attempt.tx_requests.clone() {
-                self.outcome_emitter
-                    .emit_outcome(&OutcomeEvent {
-                        outcome: TxExecutionOutcome::FAILED,
-                        tx_request_id: tx_request.tx_id,
-                        gas_fee: retried_execution_attempt.execution_attempt.used_gas,
-                        transaction_hash: retried_execution_attempt
-                            .execution_attempt
-                            .tx_hash
-                            .clone(),
-                        error: retried_execution_attempt
-                            .execution_attempt
-                            .error_object
-                            .clone(),
-                        metadata: tx_request.metadata,
-                    })
-                    .await?;
-            }
+            self.fail_reverted(retried_execution_attempt).await?;
             return Ok(());
         }
-        if retried_execution_attempt.tx_requests.len() > 1 {
-            let original_execution_id = retried_execution_attempt.execution_attempt.id;
-            let split_execute_batch_context =
-                self.split_into_execute_batch_context(retried_execution_attempt)?;
-
-            for mut tx_context in split_execute_batch_context {
-                let Some(mut wallet) = self
-                    .wallet_pool_manager
-                    .acquire(tx_context.chain_id, None)
-                    .await?
-                else {
-                    sqs_batch_response.add_failure(queue_message_id);
-                    continue;
-                };
-
-                let wallet_assignment_ids = self
-                    .wallet_assignment_repo
-                    .new_assignments(&tx_context.get_tx_ids(), wallet.db_record.id)
-                    .await?;
-
-                match self
-                    .contract_manager
-                    .simulate_send_batch_tx(&mut tx_context, &mut wallet)
-                    .await
-                {
-                    Ok(_) => {}
-                    Err(err) => {
-                        tracing::error!("{err:?}");
-                        self.wallet_pool_manager
-                            .release_unused(wallet.db_record.id)
-                            .await?;
-                        let failed_execution_attempt =
-                            self.handle_error(&tx_context, &wallet, err).await?;
-                        self.execution_attempt_repo
-                            .set_source_execution_attempt_id(
-                                &failed_execution_attempt.id,
-                                &retried_execution_attempt.execution_attempt.id,
-                            )
-                            .await?;
-                        continue;
-                    }
-                };
-                match self
-                    .contract_manager
-                    .send_batch(&mut tx_context, &wallet)
-                    .await
-                {
-                    Ok(_) => {
-                        let new_execution_attempt = self
-                            .save_successful_tx(&tx_context, &wallet, &original_execution_id)
-                            .await?;
-                        self.send_receipt_poller_queue_message(
-                            &tx_context,
-                            &new_execution_attempt.id.to_string(),
-                        )
-                        .await?;
-                    }
-                    Err(err) => {
-                        tracing::error!("{err:?}");
-                        self.handle_error(&tx_context, &wallet, err).await?;
-                    }
-                }
-            }
+        if retried_execution_attempt.tx_requests.len() > 1
+            && retried_execution_attempt.execution_attempt.tx_type == TxType::BLOB
+        {
+            self.retry_reverted_blob(
+                retried_execution_attempt,
+                sqs_batch_response,
+                queue_message_id,
+            )
+            .await?;
+        } else if retried_execution_attempt.tx_requests.len() > 1 {
+            self.retry_reverted_standard(
+                retried_execution_attempt,
+                sqs_batch_response,
+                queue_message_id,
+            )
+            .await?;
         } else {
             tracing::warn!(
                 "Can't handle reverted execution with only one tx. Marking as FAILED..."
             );
-            self.execution_attempt_repo
-                .propagate_outcome(&OutcomePropagationInput {
-                    execution_attempt_id: retried_execution_attempt.execution_attempt.id,
-                    outcome: TxExecutionOutcome::FAILED,
-                    tx_requests_status: TxStatus::FAILED,
-                    retryable: Some(false),
-                    used_gas: retried_execution_attempt.execution_attempt.used_gas,
-                })
-                .await?;
-
-            for tx_request in retried_execution_attempt.tx_requests.clone() {
-                self.outcome_emitter
-                    .emit_outcome(&OutcomeEvent {
-                        outcome: TxExecutionOutcome::FAILED,
-                        tx_request_id: tx_request.tx_id,
-                        gas_fee: retried_execution_attempt.execution_attempt.used_gas,
-                        transaction_hash: retried_execution_attempt
-                            .execution_attempt
-                            .tx_hash
-                            .clone(),
-                        error: retried_execution_attempt
-                            .execution_attempt
-                            .error_object
-                            .clone(),
-                        metadata: tx_request.metadata,
-                    })
-                    .await?;
-            }
+            self.fail_reverted(retried_execution_attempt).await?;
         }
         Ok(())
     }
+
+    /// Sets the attempt and its requests to FAILED together with their outcome events.
+    async fn fail_reverted(
+        &self,
+        retried_execution_attempt: &ExecutionAttemptWithTxInputs,
+    ) -> anyhow::Result<()> {
+        let outcome_events: Vec<OutcomeEvent> = retried_execution_attempt
+            .tx_requests
+            .iter()
+            .map(|tx_request| {
+                OutcomeEvent::failed(
+                    tx_request.tx_id.clone(),
+                    tx_request.requester_id.clone(),
+                    tx_request.metadata.clone(),
+                    retried_execution_attempt
+                        .execution_attempt
+                        .error_object
+                        .clone(),
+                )
+                .with_value_wei(tx_request.value_wei())
+                .with_transaction(
+                    retried_execution_attempt.execution_attempt.used_gas,
+                    retried_execution_attempt.execution_attempt.tx_hash.clone(),
+                )
+            })
+            .collect();
+        let operator_wallet = self
+            .operator_wallet_repo
+            .find_by_id(
+                retried_execution_attempt
+                    .execution_attempt
+                    .operator_wallet_id,
+            )
+            .await?;
+        let mut outbox_events = to_outbox_events(&LifecycleEvent::for_tx_requests(
+            &retried_execution_attempt.tx_requests,
+            TxStatus::FAILED,
+            retried_execution_attempt.execution_attempt.tx_hash.clone(),
+            Some(operator_wallet.wallet_address),
+        ))?;
+        outbox_events.extend(to_outbox_events(&outcome_events)?);
+        let propagation_input = OutcomePropagationInput {
+            execution_attempt_id: retried_execution_attempt.execution_attempt.id,
+            outcome: TxExecutionOutcome::FAILED,
+            tx_requests_status: TxStatus::FAILED,
+            failed_tx_ids: Vec::new(),
+            retryable: Some(false),
+            used_gas: retried_execution_attempt.execution_attempt.used_gas,
+            outbox_events,
+        };
+        self.execution_attempt_repo
+            .propagate_outcome(&propagation_input)
+            .await?;
+
+        self.outbox_relay
+            .relay(&self.outcome_emitter, &propagation_input.outbox_events)
+            .await?;
+
+        Ok(())
+    }
 }
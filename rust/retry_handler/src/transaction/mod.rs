@@ -0,0 +1,400 @@
+use alloy::eips::eip1559::Eip1559Estimation;
+use anyhow::{anyhow, bail};
+use blob_storage::storage::s3::S3BlobStorageManager;
+use db_types::{ExecutionErrorObject, TxType, Wei};
+use execution_attempt_db::{
+    execution_attempts::ExecutionAttempt, types::ExecutionAttemptWithTxInputs,
+};
+use network_db::networks::Network;
+use seoa_contract::transaction::{
+    BlobBatchInputWithSidecar, BlobBatchTxContext, CancellationTxContext, ExecuteBatchTxContext,
+    IntoBlobBatchInput, IntoExecuteInput,
+};
+use standard_tx_sender::transaction::IsolatedItems;
+use tx_input_types::TxInput;
+use tx_request_db::types::TxRequestWithInput;
+
+#[cfg(test)]
+mod tests;
+
+const BUFFER_DENOMINATOR: u128 = 1_000_000;
+
+pub trait IntoExecuteBatchTxContext {
+    fn into_execute_batch_context(&self) -> anyhow::Result<ExecuteBatchTxContext>;
+}
+
+impl IntoExecuteBatchTxContext for ExecutionAttemptWithTxInputs {
+    fn into_execute_batch_context(&self) -> anyhow::Result<ExecuteBatchTxContext> {
+        let fees = fees_from_execution_attempt(&self.execution_attempt)?;
+
+        let execute_batch_input = self
+            .tx_requests
+            .iter()
+            .map(|tx_request| tx_request.into_execute_input().unwrap())
+            .collect();
+
+        Ok(ExecuteBatchTxContext {
+            chain_id: self.execution_attempt.chain_id,
+            execute_batch_input,
+            use_operator_wallet_id: None,
+            batch_tx_value: calculate_batch_tx_value(&self.tx_requests)?,
+            tx_requests: self.tx_requests.clone(),
+            successfully_simulated: false,
+            assigned_nonce: try_option_i64_to_option_u64(self.execution_attempt.nonce_used)?,
+            fees: Some(fees),
+            gas_limit: try_option_i64_to_option_u64(self.execution_attempt.gas_limit)?,
+            tx_hash: self.execution_attempt.tx_hash.clone(),
+        })
+    }
+}
+
+#[allow(async_fn_in_trait)]
+pub trait IntoBlobBatchTxContext {
+    async fn into_blob_batch_context(
+        &self,
+        blob_storage_manager: &S3BlobStorageManager,
+    ) -> anyhow::Result<BlobBatchTxContext>;
+}
+
+impl IntoBlobBatchTxContext for ExecutionAttemptWithTxInputs {
+    async fn into_blob_batch_context(
+        &self,
+        blob_storage_manager: &S3BlobStorageManager,
+    ) -> anyhow::Result<BlobBatchTxContext> {
+        let fees = fees_from_execution_attempt(&self.execution_attempt)?;
+        let max_fee_per_blob_gas = u128::try_from(
+            self.execution_attempt
+                .max_fee_per_blob_gas
+                .ok_or(anyhow::anyhow!("Can't parse, missing max_fee_per_blob_gas"))?,
+        )?;
+
+        Ok(BlobBatchTxContext {
+            chain_id: self.execution_attempt.chain_id,
+            blob_batch_with_sidecar_vec: load_blob_batch_with_sidecars(
+                &self.tx_requests,
+                blob_storage_manager,
+            )
+            .await?,
+            use_operator_wallet_id: None,
+            tx_requests: self.tx_requests.clone(),
+            successfully_simulated: false,
+            assigned_nonce: try_option_i64_to_option_u64(self.execution_attempt.nonce_used)?,
+            fees: Some(fees),
+            max_fee_per_blob_gas: Some(max_fee_per_blob_gas),
+            gas_limit: try_option_i64_to_option_u64(self.execution_attempt.gas_limit)?,
+            tx_hash: self.execution_attempt.tx_hash.clone(),
+        })
+    }
+}
+
+pub trait IntoCancellationTxContext {
+    fn into_cancellation_context(&self) -> anyhow::Result<CancellationTxContext>;
+}
+
+impl IntoCancellationTxContext for ExecutionAttempt {
+    fn into_cancellation_context(&self) -> anyhow::Result<CancellationTxContext> {
+        let nonce = u64::try_from(self.nonce_used.ok_or(anyhow::anyhow!(
+            "Can't cancel execution attempt without nonce"
+        ))?)?;
+
+        // Blob attempts, and cancellations sent for them, hold a blob at their nonce.
+        if self.tx_type == TxType::BLOB && self.max_fee_per_blob_gas.is_none() {
+            bail!("Can't cancel blob execution attempt without max_fee_per_blob_gas");
+        }
+        let max_fee_per_blob_gas = self.max_fee_per_blob_gas.map(u128::try_from).transpose()?;
+
+        Ok(CancellationTxContext {
+            chain_id: self.chain_id,
+            nonce,
+            fees: Some(fees_from_execution_attempt(self)?),
+            max_fee_per_blob_gas,
+            tx_hash: None,
+        })
+    }
+}
+
+pub async fn load_blob_batch_with_sidecars(
+    tx_requests: &[TxRequestWithInput],
+    blob_storage_manager: &S3BlobStorageManager,
+) -> anyhow::Result<Vec<BlobBatchInputWithSidecar>> {
+    let mut blob_batch_with_sidecar_vec = Vec::new();
+    for tx_request in tx_requests {
+        let TxInput::Blob(blob_tx_input) = &tx_request.tx_input else {
+            bail!("Can't build blob batch context for STANDARD input");
+        };
+        let blob_input_json_file = blob_storage_manager
+            .read_json_file(blob_tx_input.source_file_path.clone())
+            .await?;
+        blob_batch_with_sidecar_vec.push(BlobBatchInputWithSidecar {
+            blob_batch_input: tx_request.into_blob_batch_input()?,
+            sidecar: blob_input_json_file.blob_sidecar,
+        });
+    }
+
+    Ok(blob_batch_with_sidecar_vec)
+}
+
+fn fees_from_execution_attempt(
+    execution_attempt: &ExecutionAttempt,
+) -> anyhow::Result<Eip1559Estimation> {
+    let max_fee_per_gas = u128::try_from(
+        execution_attempt
+            .max_fee_per_gas
+            .ok_or(anyhow::anyhow!("Can't parse, missing max_fee_per_gas"))?,
+    )?;
+    let max_priority_fee_per_gas = u128::try_from(execution_attempt.max_priority_fee.ok_or(
+        anyhow::anyhow!("Can't parse, missing max_priority_fee_per_gas"),
+    )?)?;
+
+    Ok(Eip1559Estimation {
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+    })
+}
+
+fn try_option_i64_to_option_u64(input: Option<i64>) -> anyhow::Result<Option<u64>> {
+    let Some(output_i64) = input else {
+        bail!("Can't parse None value");
+    };
+
+    let output = Some(u64::try_from(output_i64)?);
+
+    Ok(output)
+}
+
+pub fn calculate_batch_tx_value(tx_requests: &Vec<TxRequestWithInput>) -> anyhow::Result<Wei> {
+    let mut batch_tx_value = Wei::ZERO;
+
+    for tx_request in tx_requests {
+        let tx_input = match tx_request.tx_input.clone() {
+            TxInput::Blob(_) => bail!("Can't calculate batch tx value for BLOB input"),
+            TxInput::Standard(input) => input,
+        };
+        batch_tx_value = batch_tx_value
+            .checked_add(tx_input.value_wei)
+            .ok_or(anyhow!("Batch tx value overflows uint256"))?;
+    }
+
+    Ok(batch_tx_value)
+}
+
+/// Geth's legacy pool rejects a same-nonce replacement unless both fee caps grow by 10%.
+const MIN_REPLACEMENT_BUMP_PPM: u128 = 100_000;
+/// Blob transactions live in a separate pool that requires every fee cap to double.
+const MIN_BLOB_REPLACEMENT_BUMP_PPM: u128 = 1_000_000;
+
+#[derive(Debug)]
+pub struct FeeCeilingExceeded {
+    pub required: u128,
+    pub ceiling: u128,
+}
+
+impl std::fmt::Display for FeeCeilingExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Replacement requires fee cap {}, above network ceiling {}",
+            self.required, self.ceiling
+        )
+    }
+}
+
+impl std::error::Error for FeeCeilingExceeded {}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReplacementFeePolicy {
+    pub min_bump_ppm: u128,
+    pub max_fee_per_gas_ceiling: Option<u128>,
+    pub max_fee_per_blob_gas_ceiling: Option<u128>,
+}
+
+impl ReplacementFeePolicy {
+    pub fn standard(network: &Network) -> anyhow::Result<Self> {
+        Self::build(network, MIN_REPLACEMENT_BUMP_PPM)
+    }
+
+    pub fn blob(network: &Network) -> anyhow::Result<Self> {
+        Self::build(network, MIN_BLOB_REPLACEMENT_BUMP_PPM)
+    }
+
+    fn build(network: &Network, min_bump_ppm: u128) -> anyhow::Result<Self> {
+        Ok(Self {
+            min_bump_ppm,
+            max_fee_per_gas_ceiling: network
+                .max_fee_per_gas_ceiling
+                .map(u128::try_from)
+                .transpose()?,
+            max_fee_per_blob_gas_ceiling: network
+                .max_fee_per_blob_gas_ceiling
+                .map(u128::try_from)
+                .transpose()?,
+        })
+    }
+
+    /// Takes the higher of the live estimate and the minimum bump over the previous attempt,
+    /// then caps max_fee_per_gas at the network ceiling.
+    pub fn replacement_fees(
+        &self,
+        previous: Eip1559Estimation,
+        live: Eip1559Estimation,
+    ) -> anyhow::Result<Eip1559Estimation> {
+        let min_max_fee_per_gas = bump(previous.max_fee_per_gas, self.min_bump_ppm);
+        let min_max_priority_fee_per_gas =
+            bump(previous.max_priority_fee_per_gas, self.min_bump_ppm);
+
+        let mut max_priority_fee_per_gas = live
+            .max_priority_fee_per_gas
+            .max(min_max_priority_fee_per_gas);
+        let mut max_fee_per_gas = live
+            .max_fee_per_gas
+            .max(min_max_fee_per_gas)
+            .max(max_priority_fee_per_gas);
+
+        if let Some(ceiling) = self.max_fee_per_gas_ceiling {
+            let required = min_max_fee_per_gas.max(min_max_priority_fee_per_gas);
+            if required > ceiling {
+                return Err(FeeCeilingExceeded { required, ceiling }.into());
+            }
+            max_fee_per_gas = max_fee_per_gas.min(ceiling);
+            max_priority_fee_per_gas = max_priority_fee_per_gas.min(max_fee_per_gas);
+        }
+
+        Ok(Eip1559Estimation {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        })
+    }
+
+    pub fn replacement_max_fee_per_blob_gas(
+        &self,
+        previous: u128,
+        live: u128,
+    ) -> anyhow::Result<u128> {
+        let required = bump(previous, self.min_bump_ppm);
+        let max_fee_per_blob_gas = live.max(required);
+
+        match self.max_fee_per_blob_gas_ceiling {
+            Some(ceiling) if required > ceiling => {
+                Err(FeeCeilingExceeded { required, ceiling }.into())
+            }
+            Some(ceiling) => Ok(max_fee_per_blob_gas.min(ceiling)),
+            None => Ok(max_fee_per_blob_gas),
+        }
+    }
+}
+
+fn bump(value: u128, bump_ppm: u128) -> u128 {
+    value + (value * bump_ppm).div_ceil(BUFFER_DENOMINATOR)
+}
+
+/// Tx contexts whose EIP-1559 fees can be replaced.
+pub trait TxFees {
+    fn fees_mut(&mut self) -> &mut Option<Eip1559Estimation>;
+}
+
+impl TxFees for ExecuteBatchTxContext {
+    fn fees_mut(&mut self) -> &mut Option<Eip1559Estimation> {
+        &mut self.fees
+    }
+}
+
+impl TxFees for BlobBatchTxContext {
+    fn fees_mut(&mut self) -> &mut Option<Eip1559Estimation> {
+        &mut self.fees
+    }
+}
+
+impl TxFees for CancellationTxContext {
+    fn fees_mut(&mut self) -> &mut Option<Eip1559Estimation> {
+        &mut self.fees
+    }
+}
+
+pub trait ReplacementFeeExt {
+    fn apply_replacement_fees(
+        &mut self,
+        policy: &ReplacementFeePolicy,
+        live_fees: Eip1559Estimation,
+    ) -> anyhow::Result<()>
+    where
+        Self: Sized;
+}
+
+impl<T: TxFees> ReplacementFeeExt for T {
+    fn apply_replacement_fees(
+        &mut self,
+        policy: &ReplacementFeePolicy,
+        live_fees: Eip1559Estimation,
+    ) -> anyhow::Result<()> {
+        let fees = self.fees_mut();
+        let previous_fees = fees.ok_or(anyhow::anyhow!("Can't replace tx with undefined fees"))?;
+        *fees = Some(policy.replacement_fees(previous_fees, live_fees)?);
+        Ok(())
+    }
+}
+
+pub trait BlobReplacementFeeExt {
+    fn apply_blob_replacement_fee(
+        &mut self,
+        policy: &ReplacementFeePolicy,
+        live_max_fee_per_blob_gas: u128,
+    ) -> anyhow::Result<()>
+    where
+        Self: Sized;
+}
+
+impl BlobReplacementFeeExt for CancellationTxContext {
+    fn apply_blob_replacement_fee(
+        &mut self,
+        policy: &ReplacementFeePolicy,
+        live_max_fee_per_blob_gas: u128,
+    ) -> anyhow::Result<()> {
+        if let Some(previous_max_fee_per_blob_gas) = self.max_fee_per_blob_gas {
+            self.max_fee_per_blob_gas = Some(policy.replacement_max_fee_per_blob_gas(
+                previous_max_fee_per_blob_gas,
+                live_max_fee_per_blob_gas,
+            )?);
+        }
+        Ok(())
+    }
+}
+
+impl BlobReplacementFeeExt for BlobBatchTxContext {
+    fn apply_blob_replacement_fee(
+        &mut self,
+        policy: &ReplacementFeePolicy,
+        live_max_fee_per_blob_gas: u128,
+    ) -> anyhow::Result<()> {
+        let previous_max_fee_per_blob_gas = self.max_fee_per_blob_gas.ok_or(anyhow::anyhow!(
+            "Can't replace tx with undefined max_fee_per_blob_gas"
+        ))?;
+        self.max_fee_per_blob_gas = Some(policy.replacement_max_fee_per_blob_gas(
+            previous_max_fee_per_blob_gas,
+            live_max_fee_per_blob_gas,
+        )?);
+        Ok(())
+    }
+}
+
+/// What to do with a reverted standard batch once its items were simulated on their own.
+#[derive(Debug)]
+pub enum RevertedBatchPlan {
+    /// No item fails on its own, only their combination does.
+    Halve,
+    /// Fail the `unsendable` items and send the `healthy` ones again as one batch.
+    Resend {
+        unsendable: Vec<(TxRequestWithInput, ExecutionErrorObject)>,
+        healthy: Vec<TxRequestWithInput>,
+    },
+}
+
+pub fn plan_reverted_batch(isolated_items: &IsolatedItems) -> RevertedBatchPlan {
+    if isolated_items.all_healthy() {
+        return RevertedBatchPlan::Halve;
+    }
+
+    RevertedBatchPlan::Resend {
+        unsendable: isolated_items.unsendable(),
+        healthy: isolated_items.healthy_tx_requests(),
+    }
+}
@@ -0,0 +1,275 @@
+#[cfg(test)]
+mod tests {
+    use crate::transaction::{
+        BlobReplacementFeeExt, FeeCeilingExceeded, IntoCancellationTxContext, ReplacementFeeExt,
+        ReplacementFeePolicy, RevertedBatchPlan, plan_reverted_batch,
+    };
+    use alloy::eips::eip1559::Eip1559Estimation;
+    use db_types::{TxStatus, TxType, Wei};
+    use execution_attempt_db::execution_attempts::ExecutionAttempt;
+    use seoa_contract::contract::ItemSimulation;
+    use sqlx::types::time::OffsetDateTime;
+    use standard_tx_input_db::standard_tx_inputs::StandardTxInput;
+    use standard_tx_sender::transaction::isolate_failing_items;
+    use tx_input_types::TxInput;
+    use tx_request_db::types::TxRequestWithInput;
+    use uuid::Uuid;
+
+    fn policy(ceiling: Option<u128>) -> ReplacementFeePolicy {
+        ReplacementFeePolicy {
+            min_bump_ppm: 100_000,
+            max_fee_per_gas_ceiling: ceiling,
+            max_fee_per_blob_gas_ceiling: ceiling,
+        }
+    }
+
+    fn fees(max_fee_per_gas: u128, max_priority_fee_per_gas: u128) -> Eip1559Estimation {
+        Eip1559Estimation {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        }
+    }
+
+    #[test]
+    fn bumps_previous_fees_when_live_estimate_is_lower() {
+        let result = policy(None)
+            .replacement_fees(fees(1_000, 100), fees(500, 50))
+            .unwrap();
+
+        assert_eq!(result, fees(1_100, 110));
+    }
+
+    #[test]
+    fn follows_live_estimate_when_above_minimum_bump() {
+        let result = policy(None)
+            .replacement_fees(fees(1_000, 100), fees(3_000, 200))
+            .unwrap();
+
+        assert_eq!(result, fees(3_000, 200));
+    }
+
+    #[test]
+    fn rounds_minimum_bump_up() {
+        let result = policy(None)
+            .replacement_fees(fees(15, 1), fees(0, 0))
+            .unwrap();
+
+        assert_eq!(result, fees(17, 2));
+    }
+
+    #[test]
+    fn caps_live_estimate_at_ceiling() {
+        let result = policy(Some(2_000))
+            .replacement_fees(fees(1_000, 100), fees(3_000, 2_500))
+            .unwrap();
+
+        assert_eq!(result, fees(2_000, 2_000));
+    }
+
+    #[test]
+    fn fails_if_minimum_bump_exceeds_ceiling() {
+        let err = policy(Some(1_050))
+            .replacement_fees(fees(1_000, 100), fees(500, 50))
+            .unwrap_err();
+
+        let ceiling_err = err.downcast_ref::<FeeCeilingExceeded>().unwrap();
+        assert_eq!(ceiling_err.required, 1_100);
+        assert_eq!(ceiling_err.ceiling, 1_050);
+    }
+
+    #[test]
+    fn bumps_and_caps_blob_fee() {
+        let blob_policy = ReplacementFeePolicy {
+            min_bump_ppm: 1_000_000,
+            ..policy(Some(5_000))
+        };
+
+        assert_eq!(
+            blob_policy
+                .replacement_max_fee_per_blob_gas(1_000, 10)
+                .unwrap(),
+            2_000
+        );
+        assert_eq!(
+            blob_policy
+                .replacement_max_fee_per_blob_gas(1_000, 9_000)
+                .unwrap(),
+            5_000
+        );
+        assert!(
+            blob_policy
+                .replacement_max_fee_per_blob_gas(3_000, 10)
+                .is_err()
+        );
+    }
+
+    fn stuck_attempt(tx_type: TxType, max_fee_per_blob_gas: Option<i64>) -> ExecutionAttempt {
+        ExecutionAttempt {
+            id: Uuid::new_v4(),
+            chain_id: 1,
+            operator_wallet_id: Uuid::new_v4(),
+            nonce_used: Some(7),
+            tx_value: Wei::ZERO,
+            tx_type,
+            tx_hash: Some("0xabc".to_string()),
+            gas_limit: Some(100_000),
+            used_gas: None,
+            max_fee_per_gas: Some(1_000),
+            max_priority_fee: Some(100),
+            max_fee_per_blob_gas,
+            outcome: None,
+            error_object: None,
+            created_at: OffsetDateTime::UNIX_EPOCH,
+            updated_at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn standard_cancellation_reuses_nonce_and_fees_without_blob_fee() {
+        let tx_context = stuck_attempt(TxType::STANDARD, None)
+            .into_cancellation_context()
+            .unwrap();
+
+        assert_eq!(tx_context.chain_id, 1);
+        assert_eq!(tx_context.nonce, 7);
+        assert_eq!(tx_context.fees, Some(fees(1_000, 100)));
+        assert_eq!(tx_context.max_fee_per_blob_gas, None);
+        assert_eq!(tx_context.tx_hash, None);
+    }
+
+    #[test]
+    fn blob_cancellation_keeps_blob_fee_of_stuck_attempt() {
+        let tx_context = stuck_attempt(TxType::BLOB, Some(3_000))
+            .into_cancellation_context()
+            .unwrap();
+
+        assert_eq!(tx_context.max_fee_per_blob_gas, Some(3_000));
+        assert!(
+            stuck_attempt(TxType::BLOB, None)
+                .into_cancellation_context()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn cancellation_of_a_blob_cancellation_stays_a_blob_transaction() {
+        let tx_context = stuck_attempt(TxType::CANCELLATION, Some(6_000))
+            .into_cancellation_context()
+            .unwrap();
+
+        assert_eq!(tx_context.max_fee_per_blob_gas, Some(6_000));
+    }
+
+    #[test]
+    fn blob_cancellation_doubles_every_fee_cap() {
+        let blob_policy = ReplacementFeePolicy {
+            min_bump_ppm: 1_000_000,
+            ..policy(None)
+        };
+        let mut tx_context = stuck_attempt(TxType::BLOB, Some(3_000))
+            .into_cancellation_context()
+            .unwrap();
+
+        tx_context
+            .apply_replacement_fees(&blob_policy, fees(500, 50))
+            .unwrap();
+        tx_context
+            .apply_blob_replacement_fee(&blob_policy, 10)
+            .unwrap();
+
+        assert_eq!(tx_context.fees, Some(fees(2_000, 200)));
+        assert_eq!(tx_context.max_fee_per_blob_gas, Some(6_000));
+    }
+
+    #[test]
+    fn standard_cancellation_ignores_blob_fee() {
+        let mut tx_context = stuck_attempt(TxType::STANDARD, None)
+            .into_cancellation_context()
+            .unwrap();
+
+        tx_context
+            .apply_blob_replacement_fee(&policy(None), 10)
+            .unwrap();
+
+        assert_eq!(tx_context.max_fee_per_blob_gas, None);
+    }
+
+    fn tx_request(tx_id: &str) -> TxRequestWithInput {
+        TxRequestWithInput {
+            tx_id: tx_id.to_string(),
+            requester_id: "requester".to_string(),
+            tx_type: TxType::STANDARD,
+            tx_status: TxStatus::RETRIED,
+            tx_input: TxInput::Standard(StandardTxInput {
+                tx_id: tx_id.to_string(),
+                signature: vec![0; 65],
+                calldata: vec![],
+                to_address: "0x2222222222222222222222222222222222222222".to_string(),
+                value_wei: Wei::ZERO,
+                deadline_timestamp: 0,
+                pass_value_from_operator_wallet: false,
+                created_at: OffsetDateTime::UNIX_EPOCH,
+            }),
+            attempts: 1,
+            metadata: None,
+            use_operator_wallet_id: None,
+        }
+    }
+
+    #[test]
+    fn reverted_batch_is_halved_when_every_item_passes_alone() {
+        let isolated_items = isolate_failing_items(
+            &[tx_request("tx-1"), tx_request("tx-2")],
+            vec![
+                ItemSimulation::Passed {
+                    estimated_gas: 50_000,
+                },
+                ItemSimulation::Passed {
+                    estimated_gas: 50_000,
+                },
+            ],
+        )
+        .unwrap();
+
+        assert!(matches!(
+            plan_reverted_batch(&isolated_items),
+            RevertedBatchPlan::Halve
+        ));
+    }
+
+    #[test]
+    fn already_used_items_of_reverted_batch_are_failed() {
+        let isolated_items = isolate_failing_items(
+            &[tx_request("tx-1"), tx_request("tx-2"), tx_request("tx-3")],
+            vec![
+                ItemSimulation::Passed {
+                    estimated_gas: 50_000,
+                },
+                ItemSimulation::AlreadyUsed,
+                ItemSimulation::AlreadyUsed,
+            ],
+        )
+        .unwrap();
+
+        let RevertedBatchPlan::Resend {
+            unsendable,
+            healthy,
+        } = plan_reverted_batch(&isolated_items)
+        else {
+            panic!("expected the healthy item to be re-sent");
+        };
+
+        let unsendable: Vec<_> = unsendable
+            .iter()
+            .map(|(tx_request, error_object)| {
+                (tx_request.tx_id.as_str(), error_object.error_type.as_str())
+            })
+            .collect();
+        assert_eq!(
+            unsendable,
+            vec![("tx-2", "AlreadyUsed"), ("tx-3", "AlreadyUsed")]
+        );
+        assert_eq!(healthy.len(), 1);
+        assert_eq!(healthy[0].tx_id, "tx-1");
+    }
+}
@@ -1,6 +1,12 @@
-use crate::transaction::ExecuteBatchTxContext;
+use crate::{
+    revert_reason::decode_revert_reason,
+    transaction::{BlobBatchTxContext, CancellationTxContext, ExecuteBatchTxContext},
+};
 use alloy::{
-    primitives::{Address, Uint},
+    consensus::BlobTransactionSidecarEip7594,
+    eips::{BlockNumberOrTag, eip1559::Eip1559Estimation},
+    json_abi::JsonAbi,
+    primitives::{Address, FixedBytes, U256},
     providers::{
         Provider, ProviderBuilder,
         fillers::{BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller},
@@ -8,6 +14,7 @@ use alloy::{
     sol,
 };
 use anyhow::bail;
+use db_types::ExecutionErrorObject;
 use network_db::networks::Network;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, str::FromStr};
@@ -29,6 +36,20 @@ type HardlyTypedProvider = FillProvider<
     alloy::providers::RootProvider,
 >;
 
+pub const CANCELLATION_GAS_LIMIT: u64 = 21_000;
+
+/// Result of simulating a single batch item with `execute`.
+#[derive(Debug, Clone)]
+pub enum ItemSimulation {
+    /// Gas the item needs when sent on its own, intrinsic gas included.
+    Passed {
+        estimated_gas: u64,
+    },
+    /// The item's salt is already used, so it ran in an earlier transaction.
+    AlreadyUsed,
+    Reverted(ExecutionErrorObject),
+}
+
 pub struct ContractManager {
     pub networks_by_chain_id: HashMap<i64, Network>,
     pub providers_by_chain_id: HashMap<i64, HardlyTypedProvider>,
@@ -50,6 +71,66 @@ impl ContractManager {
         })
     }
 
+    pub async fn estimate_eip1559_fees(&self, chain_id: i64) -> anyhow::Result<Eip1559Estimation> {
+        let Some(root_provider) = self.providers_by_chain_id.get(&chain_id) else {
+            bail!("Provider not found for chain id: {}", chain_id);
+        };
+
+        Ok(root_provider.estimate_eip1559_fees().await?)
+    }
+
+    pub async fn estimate_max_fee_per_blob_gas(&self, chain_id: i64) -> anyhow::Result<u128> {
+        let Some(network) = self.networks_by_chain_id.get(&chain_id) else {
+            bail!("Network not found for chain id: {}", chain_id);
+        };
+        let Some(root_provider) = self.providers_by_chain_id.get(&chain_id) else {
+            bail!("Provider not found for chain id: {}", chain_id);
+        };
+
+        let blob_base_fee = root_provider.get_blob_base_fee().await?;
+
+        Ok(blob_base_fee
+            + blob_base_fee * u128::try_from(network.blob_gas_estimation_buffer_ppm)? / 1_000_000)
+    }
+
+    pub async fn get_block_gas_limit(&self, chain_id: i64) -> anyhow::Result<u64> {
+        let Some(root_provider) = self.providers_by_chain_id.get(&chain_id) else {
+            bail!("Provider not found for chain id: {}", chain_id);
+        };
+        let Some(block) = root_provider
+            .get_block_by_number(BlockNumberOrTag::Latest)
+            .await?
+        else {
+            bail!("Latest block not found for chain id: {}", chain_id);
+        };
+
+        Ok(block.header.gas_limit)
+    }
+
+    /// Whether each salt is already used on the chain's sEOA contract, in the same order.
+    pub async fn used_salts(
+        &self,
+        chain_id: i64,
+        salts: &[FixedBytes<32>],
+    ) -> anyhow::Result<Vec<bool>> {
+        let Some(network) = self.networks_by_chain_id.get(&chain_id) else {
+            bail!("Contract address not found for chain id: {}", chain_id);
+        };
+        let Some(root_provider) = self.providers_by_chain_id.get(&chain_id) else {
+            bail!("Provider not found for chain id: {}", chain_id);
+        };
+        let contract = SEOA::new(
+            Address::from_str(network.contract_address.as_str())?,
+            root_provider,
+        );
+
+        let mut used = Vec::with_capacity(salts.len());
+        for salt in salts {
+            used.push(contract.usedSalts(*salt).call().await?);
+        }
+        Ok(used)
+    }
+
     pub async fn simulate_send_batch_tx(
         &self,
         tx_context: &mut ExecuteBatchTxContext,
@@ -73,7 +154,7 @@ impl ContractManager {
             &provider,
         );
 
-        let tx_value = Uint::<256, 4>::from(tx_context.batch_tx_value);
+        let tx_value: U256 = tx_context.batch_tx_value.into();
 
         let fees = provider.estimate_eip1559_fees().await?;
         let call = contract
@@ -98,6 +179,56 @@ impl ContractManager {
         Ok(())
     }
 
+    /// Simulates one item on its own with `eth_estimateGas`, sent from the wallet that will carry
+    /// the batch. Items are simulated against the current state, independently of each other.
+    pub async fn simulate_execute(
+        &self,
+        chain_id: i64,
+        execute_input: &sEOA::ExecuteInput,
+        wallet: &Wallet,
+        target_abis: &[JsonAbi],
+    ) -> anyhow::Result<ItemSimulation> {
+        let Some(network) = self.networks_by_chain_id.get(&chain_id) else {
+            bail!("Contract address not found for chain id: {}", chain_id);
+        };
+        let Some(root_provider) = self.providers_by_chain_id.get(&chain_id) else {
+            bail!("Provider not found for chain id: {}", chain_id);
+        };
+        let contract = SEOA::new(
+            Address::from_str(network.contract_address.as_str())?,
+            root_provider,
+        );
+
+        let err = match contract
+            .execute(execute_input.clone())
+            .from(wallet.ow_wallet.get_address()?)
+            .value(execute_input.value)
+            .estimate_gas()
+            .await
+        {
+            Ok(estimated_gas) => return Ok(ItemSimulation::Passed { estimated_gas }),
+            Err(err) => err,
+        };
+
+        let (error_type, revert_reason) =
+            match err.try_decode_into_interface_error::<SEOA::SEOAErrors>()? {
+                SEOA::SEOAErrors::AlreadyUsed(_) => return Ok(ItemSimulation::AlreadyUsed),
+                SEOA::SEOAErrors::ExecutionFailed(execution_failed) => (
+                    "ExecutionFailed",
+                    Some(decode_revert_reason(&execution_failed.reason, target_abis)),
+                ),
+                SEOA::SEOAErrors::Expired(_) => ("Expired", None),
+                SEOA::SEOAErrors::InvalidSignature(_) => ("InvalidSignature", None),
+                _ => ("Unknown", None),
+            };
+
+        Ok(ItemSimulation::Reverted(ExecutionErrorObject {
+            error_type: error_type.to_string(),
+            error_body: None,
+            revert_reason,
+        }))
+    }
+
     pub async fn send_batch(
         &self,
         tx_context: &mut ExecuteBatchTxContext,
@@ -135,10 +266,136 @@ impl ContractManager {
 
         let pending_tx = contract
             .executeBatch(tx_context.execute_batch_input.clone())
-            .value(Uint::<256, 4>::from(tx_context.batch_tx_value))
+            .value(tx_context.batch_tx_value.into())
+            .nonce(nonce)
+            .max_fee_per_gas(fees.max_fee_per_gas)
+            .max_priority_fee_per_gas(fees.max_priority_fee_per_gas)
+            .gas(gas_limit)
+            .send()
+            .await?;
+
+        tx_context.tx_hash = Some(pending_tx.tx_hash().to_string());
+
+        Ok(())
+    }
+
+    pub async fn simulate_send_blob_batch_tx(
+        &self,
+        tx_context: &mut BlobBatchTxContext,
+        wallet: &mut Wallet,
+    ) -> anyhow::Result<()> {
+        let Some(network) = self.networks_by_chain_id.get(&tx_context.chain_id) else {
+            bail!(
+                "Contract address not found for chain id: {}",
+                tx_context.chain_id
+            );
+        };
+        let Some(root_provider) = self.providers_by_chain_id.get(&tx_context.chain_id) else {
+            bail!("Provider not found for chain id: {}", tx_context.chain_id);
+        };
+        let nonce = wallet.use_nonce()?;
+        let provider = ProviderBuilder::new()
+            .wallet(&wallet.ow_wallet.wallet)
+            .connect_provider(root_provider);
+        let contract = SEOA::new(
+            Address::from_str(network.contract_address.as_str())?,
+            &provider,
+        );
+
+        let tx_input = tx_context
+            .blob_batch_with_sidecar_vec
+            .iter()
+            .map(|entry| entry.blob_batch_input.clone())
+            .collect();
+
+        let tx_sidecar = Self::flat_sidecars(tx_context)?;
+
+        let fees = provider.estimate_eip1559_fees().await?;
+        let blob_base_fee = provider.get_blob_base_fee().await?;
+        let max_fee_per_blob_gas = blob_base_fee
+            + blob_base_fee * u128::try_from(network.blob_gas_estimation_buffer_ppm)? / 1_000_000;
+        let call = contract
+            .sendBlobBatch(tx_input)
+            .sidecar_7594(tx_sidecar)
             .nonce(nonce)
             .max_fee_per_gas(fees.max_fee_per_gas)
             .max_priority_fee_per_gas(fees.max_priority_fee_per_gas)
+            .max_fee_per_blob_gas(max_fee_per_blob_gas);
+
+        let estimated_gas = call.estimate_gas().await?;
+
+        let gas_limit = estimated_gas
+            + estimated_gas * u64::try_from(network.gas_estimation_buffer_ppm)? / 1_000_000;
+
+        call.gas(gas_limit).call().await?;
+
+        tx_context.assigned_nonce = Some(nonce);
+        tx_context.fees = Some(fees);
+        tx_context.max_fee_per_blob_gas = Some(max_fee_per_blob_gas);
+        tx_context.gas_limit = Some(gas_limit);
+        tx_context.successfully_simulated = true;
+
+        Ok(())
+    }
+
+    pub async fn send_blob_batch(
+        &self,
+        tx_context: &mut BlobBatchTxContext,
+        wallet: &Wallet,
+    ) -> anyhow::Result<()> {
+        let Some(network) = self.networks_by_chain_id.get(&tx_context.chain_id) else {
+            bail!(
+                "Contract address not found for chain id: {}",
+                tx_context.chain_id
+            );
+        };
+        let Some(root_provider) = self.providers_by_chain_id.get(&tx_context.chain_id) else {
+            bail!("Provider not found for chain id: {}", tx_context.chain_id);
+        };
+
+        let Some(nonce) = tx_context.assigned_nonce else {
+            bail!("Nonce should be assinged at this point. Use simulate_send_blob_batch_tx first");
+        };
+
+        let Some(fees) = tx_context.fees else {
+            bail!("Fees should be calculated at this point. Use simulate_send_blob_batch_tx first");
+        };
+
+        let Some(max_fee_per_blob_gas) = tx_context.max_fee_per_blob_gas else {
+            bail!(
+                "Max fee per blob gas should be calculated at this point. Use simulate_send_blob_batch_tx first"
+            );
+        };
+
+        let Some(gas_limit) = tx_context.gas_limit else {
+            bail!(
+                "Gas limit should be calculated at this point. Use simulate_send_blob_batch_tx first"
+            );
+        };
+
+        let provider = ProviderBuilder::new()
+            .wallet(wallet.ow_wallet.wallet.clone())
+            .connect_provider(root_provider);
+        let contract = SEOA::new(
+            Address::from_str(network.contract_address.as_str())?,
+            &provider,
+        );
+
+        let tx_input = tx_context
+            .blob_batch_with_sidecar_vec
+            .iter()
+            .map(|entry| entry.blob_batch_input.clone())
+            .collect();
+
+        let tx_sidecar = Self::flat_sidecars(tx_context)?;
+
+        let pending_tx = contract
+            .sendBlobBatch(tx_input)
+            .sidecar_7594(tx_sidecar)
+            .nonce(nonce)
+            .max_fee_per_gas(fees.max_fee_per_gas)
+            .max_priority_fee_per_gas(fees.max_priority_fee_per_gas)
+            .max_fee_per_blob_gas(max_fee_per_blob_gas)
             .gas(gas_limit)
             .send()
             .await?;
@@ -147,4 +404,49 @@ impl ContractManager {
 
         Ok(())
     }
+
+    pub async fn send_cancellation(
+        &self,
+        tx_context: &mut CancellationTxContext,
+        wallet: &Wallet,
+    ) -> anyhow::Result<()> {
+        let Some(root_provider) = self.providers_by_chain_id.get(&tx_context.chain_id) else {
+            bail!("Provider not found for chain id: {}", tx_context.chain_id);
+        };
+
+        let provider = ProviderBuilder::new()
+            .wallet(wallet.ow_wallet.wallet.clone())
+            .connect_provider(root_provider);
+        let tx_request = tx_context.tx_request(wallet.ow_wallet.get_address()?)?;
+
+        let pending_tx = provider.send_transaction(tx_request).await?;
+
+        tx_context.tx_hash = Some(pending_tx.tx_hash().to_string());
+
+        Ok(())
+    }
+
+    fn flat_sidecars(
+        tx_context: &BlobBatchTxContext,
+    ) -> anyhow::Result<BlobTransactionSidecarEip7594> {
+        let mut flat_sidecar = BlobTransactionSidecarEip7594::default();
+        for blob_input in &tx_context.blob_batch_with_sidecar_vec {
+            let (Some(blob), Some(commitment), 1) = (
+                blob_input.sidecar.blobs.first(),
+                blob_input.sidecar.commitments.first(),
+                blob_input.sidecar.blobs.len(),
+            ) else {
+                bail!(
+                    "Expecting one BLOB per tx request, got: {}",
+                    blob_input.sidecar.blobs.len()
+                );
+            };
+            flat_sidecar.blobs.push(*blob);
+            flat_sidecar.commitments.push(*commitment);
+            flat_sidecar
+                .cell_proofs
+                .extend_from_slice(&blob_input.sidecar.cell_proofs);
+        }
+        Ok(flat_sidecar)
+    }
 }
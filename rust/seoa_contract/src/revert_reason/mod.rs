@@ -0,0 +1,87 @@
+use alloy::{
+    dyn_abi::{DynSolValue, JsonAbiExt},
+    hex,
+    json_abi::JsonAbi,
+    sol_types::{Panic, PanicKind, Revert, SolError},
+};
+use db_types::{RevertArgument, RevertReason};
+
+#[cfg(test)]
+mod tests;
+
+/// Decodes the revert data of a target call, e.g. the `reason` of `ExecutionFailed`. Custom
+/// errors are looked up in `target_abis`; anything unrecognized is kept as raw hex.
+pub fn decode_revert_reason(data: &[u8], target_abis: &[JsonAbi]) -> RevertReason {
+    if data.is_empty() {
+        return RevertReason::Empty;
+    }
+    if let Ok(revert) = Revert::abi_decode(data) {
+        return RevertReason::Error {
+            message: revert.reason,
+        };
+    }
+    if let Ok(panic) = Panic::abi_decode(data)
+        && let Ok(code) = u64::try_from(panic.code)
+    {
+        let description = u32::try_from(code)
+            .ok()
+            .and_then(PanicKind::from_number)
+            .map(|panic_kind| panic_kind.to_string())
+            .unwrap_or("unknown panic code".to_string());
+        return RevertReason::Panic { code, description };
+    }
+
+    if let Some((selector, encoded_args)) = data.split_first_chunk::<4>() {
+        for error in target_abis
+            .iter()
+            .flat_map(|target_abi| target_abi.errors())
+        {
+            if error.selector() != selector {
+                continue;
+            }
+            let Ok(values) = error.abi_decode_input(encoded_args) else {
+                continue;
+            };
+            return RevertReason::Custom {
+                name: error.name.clone(),
+                signature: error.signature(),
+                args: error
+                    .inputs
+                    .iter()
+                    .zip(values)
+                    .map(|(input, value)| RevertArgument {
+                        name: input.name.clone(),
+                        value: format_value(&value),
+                    })
+                    .collect(),
+            };
+        }
+    }
+
+    RevertReason::Unknown {
+        data: hex::encode_prefixed(data),
+    }
+}
+
+fn format_value(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Bool(value) => value.to_string(),
+        DynSolValue::Int(value, _) => value.to_string(),
+        DynSolValue::Uint(value, _) => value.to_string(),
+        DynSolValue::Address(address) => address.to_checksum(None),
+        DynSolValue::FixedBytes(word, size) => hex::encode_prefixed(&word[..*size]),
+        DynSolValue::Bytes(bytes) => hex::encode_prefixed(bytes),
+        DynSolValue::String(value) => value.clone(),
+        DynSolValue::Array(values)
+        | DynSolValue::FixedArray(values)
+        | DynSolValue::Tuple(values) => format!(
+            "[{}]",
+            values
+                .iter()
+                .map(format_value)
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        other => format!("{other:?}"),
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use crate::revert_reason::decode_revert_reason;
+    use alloy::{
+        json_abi::JsonAbi,
+        primitives::{Address, U256},
+        sol,
+        sol_types::{Panic, Revert, SolError},
+    };
+    use db_types::{RevertArgument, RevertReason};
+
+    sol! {
+        error InsufficientBalance(address account, uint256 needed);
+    }
+
+    fn target_abi() -> JsonAbi {
+        JsonAbi::parse(["error InsufficientBalance(address account, uint256 needed)"]).unwrap()
+    }
+
+    #[test]
+    fn decodes_error_string_and_panic() {
+        let data = Revert::from("not enough allowance").abi_encode();
+        assert_eq!(
+            decode_revert_reason(&data, &[]),
+            RevertReason::Error {
+                message: "not enough allowance".to_string()
+            }
+        );
+
+        let data = Panic::from(0x11).abi_encode();
+        assert_eq!(
+            decode_revert_reason(&data, &[]),
+            RevertReason::Panic {
+                code: 0x11,
+                description: "arithmetic underflow or overflow".to_string()
+            }
+        );
+
+        assert_eq!(decode_revert_reason(&[], &[]), RevertReason::Empty);
+    }
+
+    #[test]
+    fn decodes_custom_error_from_target_abi() {
+        let data = InsufficientBalance {
+            account: Address::repeat_byte(0xab),
+            needed: U256::from(42),
+        }
+        .abi_encode();
+
+        assert_eq!(
+            decode_revert_reason(&data, &[target_abi()]),
+            RevertReason::Custom {
+                name: "InsufficientBalance".to_string(),
+                signature: "InsufficientBalance(address,uint256)".to_string(),
+                args: vec![
+                    RevertArgument {
+                        name: "account".to_string(),
+                        value: Address::repeat_byte(0xab).to_checksum(None),
+                    },
+                    RevertArgument {
+                        name: "needed".to_string(),
+                        value: "42".to_string(),
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_custom_error_as_hex() {
+        let data = InsufficientBalance {
+            account: Address::ZERO,
+            needed: U256::ZERO,
+        }
+        .abi_encode();
+
+        let RevertReason::Unknown { data: hex_data } = decode_revert_reason(&data, &[]) else {
+            panic!("custom error without an ABI should stay unknown");
+        };
+        assert!(hex_data.starts_with("0x"));
+        assert_eq!(hex_data.len(), 2 + data.len() * 2);
+    }
+}
@@ -0,0 +1,215 @@
+use crate::contract::{
+    CANCELLATION_GAS_LIMIT,
+    sEOA::{BlobBatchInput, ExecuteInput},
+};
+use alloy::{
+    consensus::BlobTransactionSidecarEip7594,
+    eips::{eip1559::Eip1559Estimation, eip4844::Blob},
+    network::{TransactionBuilder, TransactionBuilder7594},
+    primitives::{Address, FixedBytes, U256, Uint, keccak256},
+    rpc::types::TransactionRequest,
+};
+use anyhow::{anyhow, bail};
+use db_types::Wei;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use tx_input_types::TxInput;
+use tx_request_db::types::{StandardTxRequestRaw, TxRequestWithInput};
+use uuid::Uuid;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExecuteBatchTxContext {
+    pub chain_id: i64,
+    pub execute_batch_input: Vec<ExecuteInput>,
+    pub use_operator_wallet_id: Option<Uuid>,
+    pub batch_tx_value: Wei,
+    pub tx_requests: Vec<TxRequestWithInput>,
+    pub successfully_simulated: bool,
+    pub assigned_nonce: Option<u64>,
+    pub fees: Option<Eip1559Estimation>,
+    pub gas_limit: Option<u64>,
+    pub tx_hash: Option<String>,
+}
+
+impl ExecuteBatchTxContext {
+    pub fn get_tx_ids(&self) -> Vec<String> {
+        self.tx_requests
+            .iter()
+            .map(|val| val.tx_id.clone())
+            .collect()
+    }
+
+    pub fn get_requester_id(&self) -> Option<&str> {
+        self.tx_requests
+            .first()
+            .map(|tx_request| tx_request.requester_id.as_str())
+    }
+
+    /// Distinct lowercase addresses of the contracts the batch calls.
+    pub fn get_target_addresses(&self) -> Vec<String> {
+        let mut target_addresses: Vec<String> = self
+            .execute_batch_input
+            .iter()
+            .map(|execute_input| format!("{:#x}", execute_input.target))
+            .collect();
+        target_addresses.sort();
+        target_addresses.dedup();
+        target_addresses
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BlobBatchInputWithSidecar {
+    pub blob_batch_input: BlobBatchInput,
+    pub sidecar: BlobTransactionSidecarEip7594,
+}
+
+#[derive(Debug, Clone)]
+pub struct BlobBatchTxContext {
+    pub chain_id: i64,
+    pub blob_batch_with_sidecar_vec: Vec<BlobBatchInputWithSidecar>,
+    pub use_operator_wallet_id: Option<Uuid>,
+    pub tx_requests: Vec<TxRequestWithInput>,
+    pub successfully_simulated: bool,
+    pub assigned_nonce: Option<u64>,
+    pub fees: Option<Eip1559Estimation>,
+    pub max_fee_per_blob_gas: Option<u128>,
+    pub gas_limit: Option<u64>,
+    pub tx_hash: Option<String>,
+}
+
+impl BlobBatchTxContext {
+    pub fn get_tx_ids(&self) -> Vec<String> {
+        self.tx_requests
+            .iter()
+            .map(|val| val.tx_id.clone())
+            .collect()
+    }
+
+    pub fn get_requester_id(&self) -> Option<&str> {
+        self.tx_requests
+            .first()
+            .map(|tx_request| tx_request.requester_id.as_str())
+    }
+
+    pub fn get_salts(&self) -> Vec<FixedBytes<32>> {
+        self.blob_batch_with_sidecar_vec
+            .iter()
+            .map(|blob_batch_with_sidecar| blob_batch_with_sidecar.blob_batch_input.salt)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CancellationTxContext {
+    pub chain_id: i64,
+    pub nonce: u64,
+    pub fees: Option<Eip1559Estimation>,
+    /// Set when the stuck transaction carries blobs. The txpool only replaces a blob
+    /// transaction with another blob transaction, so the cancellation carries one too.
+    pub max_fee_per_blob_gas: Option<u128>,
+    pub tx_hash: Option<String>,
+}
+
+impl CancellationTxContext {
+    /// Zero-value self-transfer at the stuck nonce, with a single empty blob when the stuck
+    /// transaction carries blobs.
+    pub fn tx_request(&self, wallet_address: Address) -> anyhow::Result<TransactionRequest> {
+        let Some(fees) = self.fees else {
+            bail!("Fees should be calculated before sending a cancellation");
+        };
+
+        let tx_request = TransactionRequest::default()
+            .with_from(wallet_address)
+            .with_to(wallet_address)
+            .with_value(U256::ZERO)
+            .with_nonce(self.nonce)
+            .with_gas_limit(CANCELLATION_GAS_LIMIT)
+            .with_max_fee_per_gas(fees.max_fee_per_gas)
+            .with_max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+
+        let Some(max_fee_per_blob_gas) = self.max_fee_per_blob_gas else {
+            return Ok(tx_request);
+        };
+        let mut tx_request = tx_request.with_blob_sidecar(cancellation_sidecar()?);
+        tx_request.set_max_fee_per_blob_gas(max_fee_per_blob_gas);
+
+        Ok(tx_request)
+    }
+}
+
+fn cancellation_sidecar() -> anyhow::Result<BlobTransactionSidecarEip7594> {
+    BlobTransactionSidecarEip7594::try_from_blobs(vec![Blob::ZERO])
+        .map_err(|err| anyhow!("Failed to build cancellation blob sidecar: {err}"))
+}
+
+pub trait IntoExecuteInput {
+    fn into_execute_input(&self) -> anyhow::Result<ExecuteInput>;
+}
+
+impl IntoExecuteInput for StandardTxRequestRaw {
+    fn into_execute_input(&self) -> anyhow::Result<ExecuteInput> {
+        Ok(ExecuteInput {
+            target: Address::from_str(self.to_address.as_str())?,
+            payload: self.calldata.clone().into(),
+            value: self.value_wei.into(),
+            salt: keccak256(self.tx_id.clone().into_bytes()),
+            deadline: Uint::<256, 4>::from(self.deadline_timestamp as u64),
+            signature: self.signature.clone().into(),
+        })
+    }
+}
+
+impl IntoExecuteInput for TxRequestWithInput {
+    fn into_execute_input(&self) -> anyhow::Result<ExecuteInput> {
+        match self.tx_input.clone() {
+            TxInput::Blob(_) => {
+                bail!("Can't parse blob tx input into execute input");
+            }
+            TxInput::Standard(standard_tx_input) => Ok(ExecuteInput {
+                target: Address::from_str(standard_tx_input.to_address.as_str())?,
+                payload: standard_tx_input.calldata.clone().into(),
+                value: standard_tx_input.value_wei.into(),
+                salt: keccak256(standard_tx_input.tx_id.clone().into_bytes()),
+                deadline: Uint::<256, 4>::from(standard_tx_input.deadline_timestamp as u64),
+                signature: standard_tx_input.signature.clone().into(),
+            }),
+        }
+    }
+}
+
+pub trait IntoBlobBatchInput {
+    fn into_blob_batch_input(&self) -> anyhow::Result<BlobBatchInput>;
+}
+
+impl IntoBlobBatchInput for TxRequestWithInput {
+    fn into_blob_batch_input(&self) -> anyhow::Result<BlobBatchInput> {
+        match self.tx_input.clone() {
+            TxInput::Standard(_) => {
+                bail!("Can't parse standard tx input into blob batch input");
+            }
+            TxInput::Blob(blob_tx_input) => {
+                let image_id_array: [u8; 32] =
+                    blob_tx_input.image_id.try_into().map_err(|v: Vec<u8>| {
+                        anyhow::anyhow!("image_id must be 32 bytes, got {}", v.len())
+                    })?;
+                let blob_sha2_array: [u8; 32] =
+                    blob_tx_input.blob_sha2.try_into().map_err(|v: Vec<u8>| {
+                        anyhow::anyhow!("blob_sha2 must be 32 bytes, got {}", v.len())
+                    })?;
+
+                Ok(BlobBatchInput {
+                    imageId: FixedBytes::<32>::from(image_id_array),
+                    commitment: blob_tx_input.commitment.into(),
+                    blobSha2: FixedBytes::<32>::from(blob_sha2_array),
+                    salt: keccak256(blob_tx_input.tx_id.into_bytes()),
+                    deadline: Uint::<256, 4>::from(blob_tx_input.deadline_timestamp as u64),
+                    signature: blob_tx_input.signature.into(),
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use crate::{contract::CANCELLATION_GAS_LIMIT, transaction::CancellationTxContext};
+    use alloy::{
+        eips::{eip1559::Eip1559Estimation, eip4844::Blob},
+        network::TransactionBuilder7594,
+        primitives::{Address, U256},
+    };
+
+    const WALLET_ADDRESS: Address = Address::repeat_byte(0x11);
+
+    fn cancellation(max_fee_per_blob_gas: Option<u128>) -> CancellationTxContext {
+        CancellationTxContext {
+            chain_id: 1,
+            nonce: 7,
+            fees: Some(Eip1559Estimation {
+                max_fee_per_gas: 2_000,
+                max_priority_fee_per_gas: 200,
+            }),
+            max_fee_per_blob_gas,
+            tx_hash: None,
+        }
+    }
+
+    #[test]
+    fn standard_cancellation_is_a_plain_self_transfer() {
+        let tx_request = cancellation(None).tx_request(WALLET_ADDRESS).unwrap();
+
+        assert_eq!(tx_request.from, Some(WALLET_ADDRESS));
+        assert_eq!(tx_request.to, Some(WALLET_ADDRESS.into()));
+        assert_eq!(tx_request.value, Some(U256::ZERO));
+        assert_eq!(tx_request.nonce, Some(7));
+        assert_eq!(tx_request.gas, Some(CANCELLATION_GAS_LIMIT));
+        assert_eq!(tx_request.max_fee_per_gas, Some(2_000));
+        assert_eq!(tx_request.max_priority_fee_per_gas, Some(200));
+        assert_eq!(tx_request.max_fee_per_blob_gas, None);
+        assert!(tx_request.sidecar.is_none());
+    }
+
+    #[test]
+    fn blob_cancellation_carries_a_single_empty_blob() {
+        let tx_request = cancellation(Some(5_000))
+            .tx_request(WALLET_ADDRESS)
+            .unwrap();
+
+        assert_eq!(tx_request.nonce, Some(7));
+        assert_eq!(tx_request.max_fee_per_blob_gas, Some(5_000));
+        let sidecar = tx_request.blob_sidecar_7594().unwrap();
+        assert_eq!(sidecar.blobs, vec![Blob::ZERO]);
+        assert_eq!(sidecar.commitments.len(), 1);
+        assert_eq!(
+            tx_request.blob_versioned_hashes.map(|hashes| hashes.len()),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn cancellation_needs_fees() {
+        let tx_context = CancellationTxContext {
+            fees: None,
+            ..cancellation(None)
+        };
+
+        assert!(tx_context.tx_request(WALLET_ADDRESS).is_err());
+    }
+}
@@ -0,0 +1,20 @@
+#![cfg(feature = "aws")]
+use lambda_runtime::{run, service_fn, tracing};
+use sqlx::PgPool;
+use status_query::{Config, orchestrator::aws::AwsLambdaOrchestrator};
+
+#[tokio::main]
+async fn main() -> Result<(), lambda_runtime::Error> {
+    tracing::init_default_subscriber();
+    tracing::info!("Cold start");
+
+    let config = Config::build()?;
+    let pool = PgPool::connect(&config.database_url).await?;
+
+    let aws_lambda_orchestrator = AwsLambdaOrchestrator::build(&pool);
+
+    run(service_fn(|event| {
+        aws_lambda_orchestrator.function_handler(event)
+    }))
+    .await
+}
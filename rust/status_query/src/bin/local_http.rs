@@ -0,0 +1,18 @@
+#![cfg(feature = "local")]
+use sqlx::PgPool;
+use status_query::{Config, orchestrator::local::build_router, query::StatusQuery};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let config = Config::build()?;
+    let bind_address = Config::get_env_var("STATUS_QUERY_BIND_ADDRESS");
+    let pool = PgPool::connect(&config.database_url).await?;
+
+    let listener = tokio::net::TcpListener::bind(&bind_address).await?;
+    tracing::info!("Status query API listening on {bind_address}");
+    axum::serve(listener, build_router(StatusQuery::build(&pool))).await?;
+
+    Ok(())
+}
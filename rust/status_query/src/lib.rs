@@ -0,0 +1,20 @@
+pub mod orchestrator;
+pub mod query;
+pub mod request;
+
+use std::env;
+
+pub struct Config {
+    pub database_url: String,
+}
+
+impl Config {
+    pub fn build() -> anyhow::Result<Self> {
+        let database_url = Self::get_env_var("DATABASE_URL");
+        Ok(Self { database_url })
+    }
+
+    pub fn get_env_var(key: &str) -> String {
+        env::var(key).expect(format!("Missing env variable: {key}").as_str())
+    }
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "aws")]
+
+use crate::{
+    query::StatusQuery,
+    request::{StatusRequest, StatusResponse, handle_status_request},
+};
+use aws_lambda_events::{
+    apigw::{ApiGatewayV2httpRequest, ApiGatewayV2httpResponse},
+    encodings::Body,
+    http::{HeaderMap, HeaderValue, header::CONTENT_TYPE},
+};
+use lambda_runtime::{LambdaEvent, tracing};
+
+pub struct AwsLambdaOrchestrator {
+    status_query: StatusQuery,
+}
+
+impl AwsLambdaOrchestrator {
+    pub fn build(pool: &sqlx::Pool<sqlx::Postgres>) -> Self {
+        Self {
+            status_query: StatusQuery::build(pool),
+        }
+    }
+
+    pub async fn function_handler(
+        &self,
+        event: LambdaEvent<ApiGatewayV2httpRequest>,
+    ) -> anyhow::Result<ApiGatewayV2httpResponse, lambda_runtime::Error> {
+        let request = StatusRequest::build(
+            event
+                .payload
+                .path_parameters
+                .get("tx_id")
+                .map(String::as_str),
+            event.payload.query_string_parameters.first("requester_id"),
+            event.payload.query_string_parameters.first("cursor"),
+            event.payload.query_string_parameters.first("limit"),
+        );
+        tracing::info!("{request:?}");
+
+        let status_response = match handle_status_request(&self.status_query, request).await {
+            Ok(status_response) => status_response,
+            Err(err) => {
+                tracing::error!("Status query failed: {err:?}");
+                StatusResponse::internal_error()?
+            }
+        };
+
+        Ok(into_api_gateway_response(status_response))
+    }
+}
+
+fn into_api_gateway_response(status_response: StatusResponse) -> ApiGatewayV2httpResponse {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    let mut response = ApiGatewayV2httpResponse::default();
+    response.status_code = i64::from(status_response.status_code);
+    response.headers = headers;
+    response.body = Some(Body::Text(status_response.body));
+    response
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "local")]
+
+use crate::{
+    query::StatusQuery,
+    request::{StatusRequest, StatusResponse, handle_status_request},
+};
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    http::{StatusCode, header::CONTENT_TYPE},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+struct RequesterIdQuery {
+    requester_id: Option<String>,
+    cursor: Option<String>,
+    limit: Option<String>,
+}
+
+/// Serves the same routes as the API Gateway in front of the status Lambda, for docker-compose.
+pub fn build_router(status_query: StatusQuery) -> Router {
+    Router::new()
+        .route("/tx-requests", get(by_requester_id))
+        .route("/tx-requests/{tx_id}", get(by_tx_id))
+        .with_state(Arc::new(status_query))
+}
+
+async fn by_tx_id(
+    State(status_query): State<Arc<StatusQuery>>,
+    Path(tx_id): Path<String>,
+) -> Response {
+    respond(
+        &status_query,
+        StatusRequest::build(Some(&tx_id), None, None, None),
+    )
+    .await
+}
+
+async fn by_requester_id(
+    State(status_query): State<Arc<StatusQuery>>,
+    Query(query): Query<RequesterIdQuery>,
+) -> Response {
+    respond(
+        &status_query,
+        StatusRequest::build(
+            None,
+            query.requester_id.as_deref(),
+            query.cursor.as_deref(),
+            query.limit.as_deref(),
+        ),
+    )
+    .await
+}
+
+async fn respond(status_query: &StatusQuery, request: Option<StatusRequest>) -> Response {
+    let status_response = match handle_status_request(status_query, request).await {
+        Ok(status_response) => status_response,
+        Err(err) => {
+            tracing::error!("Status query failed: {err:?}");
+            match StatusResponse::internal_error() {
+                Ok(status_response) => status_response,
+                Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            }
+        }
+    };
+
+    let StatusResponse { status_code, body } = status_response;
+    (
+        StatusCode::from_u16(status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        [(CONTENT_TYPE, "application/json")],
+        body,
+    )
+        .into_response()
+}
@@ -0,0 +1,231 @@
+use db_types::{TxExecutionOutcome, TxStatus, TxType};
+use execution_attempt_db::{
+    execution_attempts::ExecutionAttemptRepo, types::ExecutionAttemptWithLineage,
+};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use tx_request_db::{repo::TxRequestRepo, types::TxRequest};
+use uuid::Uuid;
+use wallet_assignment_db::wallet_assignments::WalletAssignmentRepo;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Serialize)]
+pub struct ExecutionAttemptStatus {
+    pub execution_attempt_id: Uuid,
+    /// Attempt this one retried, replaced or cancelled.
+    pub source_execution_attempt_id: Option<Uuid>,
+    pub tx_type: TxType,
+    pub operator_wallet_id: Uuid,
+    pub tx_hash: Option<String>,
+    pub nonce: Option<i64>,
+    pub gas_limit: Option<i64>,
+    pub used_gas: Option<i64>,
+    pub max_fee_per_gas: Option<i64>,
+    pub max_priority_fee: Option<i64>,
+    pub max_fee_per_blob_gas: Option<i64>,
+    pub outcome: Option<TxExecutionOutcome>,
+    pub error_object: Option<String>,
+    pub retryable: Option<bool>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TxRequestStatus {
+    pub tx_id: String,
+    pub requester_id: String,
+    pub chain_id: i64,
+    pub tx_type: TxType,
+    pub tx_status: TxStatus,
+    pub attempts: i32,
+    pub metadata: Option<String>,
+    /// Wallet of the latest assignment.
+    pub operator_wallet_id: Option<String>,
+    pub execution_attempts: Vec<ExecutionAttemptStatus>,
+    /// Attempt ids from the first attempt to the latest one carrying the request.
+    pub retry_lineage: Vec<Uuid>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TxRequestStatusPage {
+    pub tx_requests: Vec<TxRequestStatus>,
+    /// Cursor of the next page, None on the last one.
+    pub next_cursor: Option<i64>,
+}
+
+pub struct StatusQuery {
+    tx_request_repo: TxRequestRepo,
+    execution_attempt_repo: ExecutionAttemptRepo,
+    wallet_assignment_repo: WalletAssignmentRepo,
+}
+
+impl StatusQuery {
+    pub fn build(pool: &PgPool) -> Self {
+        Self {
+            tx_request_repo: TxRequestRepo::new(pool.clone()),
+            execution_attempt_repo: ExecutionAttemptRepo::new(pool.clone()),
+            wallet_assignment_repo: WalletAssignmentRepo::new(pool.clone()),
+        }
+    }
+
+    pub async fn by_tx_id(&self, tx_id: &str) -> anyhow::Result<Option<TxRequestStatus>> {
+        let tx_request = match self.tx_request_repo.find_by_tx_id(&tx_id.to_string()).await {
+            Ok(tx_request) => tx_request,
+            Err(err) => match err.downcast_ref::<sqlx::Error>() {
+                Some(sqlx::Error::RowNotFound) => return Ok(None),
+                _ => return Err(err),
+            },
+        };
+
+        Ok(self.build_statuses(vec![tx_request]).await?.pop())
+    }
+
+    /// Requests of a requester in submission order, `limit` at a time starting after `cursor`.
+    pub async fn by_requester_id(
+        &self,
+        requester_id: &str,
+        cursor: Option<i64>,
+        limit: i64,
+    ) -> anyhow::Result<TxRequestStatusPage> {
+        let mut tx_requests = self
+            .tx_request_repo
+            .select_by_requester_id(requester_id, cursor, limit + 1)
+            .await?;
+
+        let has_next_page = tx_requests.len() > usize::try_from(limit)?;
+        tx_requests.truncate(usize::try_from(limit)?);
+        let next_cursor = has_next_page
+            .then(|| tx_requests.last().map(|tx_request| tx_request.sequence_id))
+            .flatten();
+
+        Ok(TxRequestStatusPage {
+            tx_requests: self.build_statuses(tx_requests).await?,
+            next_cursor,
+        })
+    }
+
+    async fn build_statuses(
+        &self,
+        tx_requests: Vec<TxRequest>,
+    ) -> anyhow::Result<Vec<TxRequestStatus>> {
+        let tx_ids: Vec<String> = tx_requests
+            .iter()
+            .map(|tx_request| tx_request.tx_id.clone())
+            .collect();
+        let mut execution_attempts_by_tx_id = self
+            .execution_attempt_repo
+            .select_by_tx_ids(&tx_ids)
+            .await?;
+        let mut operator_wallet_id_by_tx_id: HashMap<String, String> = self
+            .wallet_assignment_repo
+            .select_by_tx_ids(&tx_ids)
+            .await?
+            .into_iter()
+            .map(|wallet_assignment| {
+                (
+                    wallet_assignment.tx_id,
+                    wallet_assignment.operator_wallet_id,
+                )
+            })
+            .collect();
+
+        tx_requests
+            .into_iter()
+            .map(|tx_request| {
+                let execution_attempts = execution_attempts_by_tx_id
+                    .remove(&tx_request.tx_id)
+                    .unwrap_or_default();
+                let operator_wallet_id = operator_wallet_id_by_tx_id.remove(&tx_request.tx_id);
+                build_status(tx_request, execution_attempts, operator_wallet_id)
+            })
+            .collect()
+    }
+}
+
+fn build_status(
+    tx_request: TxRequest,
+    execution_attempts: Vec<ExecutionAttemptWithLineage>,
+    operator_wallet_id: Option<String>,
+) -> anyhow::Result<TxRequestStatus> {
+    Ok(TxRequestStatus {
+        retry_lineage: retry_lineage(&execution_attempts),
+        execution_attempts: execution_attempts
+            .into_iter()
+            .map(ExecutionAttemptStatus::try_from)
+            .collect::<anyhow::Result<Vec<_>>>()?,
+        operator_wallet_id,
+        tx_id: tx_request.tx_id,
+        requester_id: tx_request.requester_id,
+        chain_id: tx_request.chain_id,
+        tx_type: tx_request.tx_type,
+        tx_status: tx_request.tx_status,
+        attempts: tx_request.attempts,
+        metadata: tx_request.metadata,
+        created_at: format_timestamp(tx_request.created_at)?,
+        updated_at: format_timestamp(tx_request.updated_at)?,
+    })
+}
+
+impl TryFrom<ExecutionAttemptWithLineage> for ExecutionAttemptStatus {
+    type Error = anyhow::Error;
+
+    fn try_from(attempt: ExecutionAttemptWithLineage) -> anyhow::Result<Self> {
+        let execution_attempt = attempt.execution_attempt;
+        Ok(Self {
+            execution_attempt_id: execution_attempt.id,
+            source_execution_attempt_id: attempt.source_execution_attempt_id,
+            tx_type: execution_attempt.tx_type,
+            operator_wallet_id: execution_attempt.operator_wallet_id,
+            tx_hash: execution_attempt.tx_hash,
+            nonce: execution_attempt.nonce_used,
+            gas_limit: execution_attempt.gas_limit,
+            used_gas: execution_attempt.used_gas,
+            max_fee_per_gas: execution_attempt.max_fee_per_gas,
+            max_priority_fee: execution_attempt.max_priority_fee,
+            max_fee_per_blob_gas: execution_attempt.max_fee_per_blob_gas,
+            outcome: execution_attempt.outcome,
+            error_object: execution_attempt.error_object,
+            retryable: attempt.retryable,
+            created_at: format_timestamp(execution_attempt.created_at)?,
+        })
+    }
+}
+
+/// Walks `source_execution_attempt_id` back from the latest attempt carrying the request.
+/// Cancellations don't carry requests, so they are left out of the lineage.
+pub fn retry_lineage(execution_attempts: &[ExecutionAttemptWithLineage]) -> Vec<Uuid> {
+    let Some(mut current) = execution_attempts
+        .iter()
+        .filter(|attempt| attempt.execution_attempt.tx_type != TxType::CANCELLATION)
+        .max_by_key(|attempt| attempt.execution_attempt.created_at)
+    else {
+        return Vec::new();
+    };
+
+    let mut lineage = vec![current.execution_attempt.id];
+    while let Some(source_id) = current.source_execution_attempt_id {
+        if lineage.contains(&source_id) {
+            break;
+        }
+        let Some(source) = execution_attempts
+            .iter()
+            .find(|attempt| attempt.execution_attempt.id == source_id)
+        else {
+            break;
+        };
+        lineage.push(source_id);
+        current = source;
+    }
+
+    lineage.reverse();
+    lineage
+}
+
+fn format_timestamp(timestamp: OffsetDateTime) -> anyhow::Result<String> {
+    Ok(timestamp.format(&Rfc3339)?)
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use crate::query::retry_lineage;
+    use db_types::{TxType, Wei};
+    use execution_attempt_db::{
+        execution_attempts::ExecutionAttempt, types::ExecutionAttemptWithLineage,
+    };
+    use time::{Duration, OffsetDateTime};
+    use uuid::Uuid;
+
+    fn attempt(
+        tx_type: TxType,
+        age_sec: i64,
+        source_execution_attempt_id: Option<Uuid>,
+    ) -> ExecutionAttemptWithLineage {
+        let created_at = OffsetDateTime::UNIX_EPOCH + Duration::seconds(1_000 - age_sec);
+        ExecutionAttemptWithLineage {
+            execution_attempt: ExecutionAttempt {
+                id: Uuid::new_v4(),
+                chain_id: 1,
+                operator_wallet_id: Uuid::new_v4(),
+                nonce_used: Some(0),
+                tx_value: Wei::ZERO,
+                tx_type,
+                tx_hash: None,
+                gas_limit: None,
+                used_gas: None,
+                max_fee_per_gas: None,
+                max_priority_fee: None,
+                max_fee_per_blob_gas: None,
+                outcome: None,
+                error_object: None,
+                created_at,
+                updated_at: created_at,
+            },
+            retryable: None,
+            source_execution_attempt_id,
+        }
+    }
+
+    #[test]
+    fn empty_without_attempts() {
+        assert!(retry_lineage(&[]).is_empty());
+    }
+
+    #[test]
+    fn follows_sources_from_first_to_latest_attempt() {
+        let first = attempt(TxType::STANDARD, 30, None);
+        let second = attempt(TxType::STANDARD, 20, Some(first.execution_attempt.id));
+        let third = attempt(TxType::STANDARD, 10, Some(second.execution_attempt.id));
+        let expected = vec![
+            first.execution_attempt.id,
+            second.execution_attempt.id,
+            third.execution_attempt.id,
+        ];
+
+        let lineage = retry_lineage(&[third, first, second]);
+
+        assert_eq!(lineage, expected);
+    }
+
+    #[test]
+    fn skips_cancellations() {
+        let first = attempt(TxType::BLOB, 30, None);
+        let cancellation = attempt(TxType::CANCELLATION, 10, Some(first.execution_attempt.id));
+        let expected = vec![first.execution_attempt.id];
+
+        let lineage = retry_lineage(&[first, cancellation]);
+
+        assert_eq!(lineage, expected);
+    }
+
+    #[test]
+    fn stops_at_source_outside_of_the_request() {
+        let retried = attempt(TxType::STANDARD, 10, Some(Uuid::new_v4()));
+        let expected = vec![retried.execution_attempt.id];
+
+        let lineage = retry_lineage(&[retried]);
+
+        assert_eq!(lineage, expected);
+    }
+}
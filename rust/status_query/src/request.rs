@@ -0,0 +1,104 @@
+use crate::query::{StatusQuery, TxRequestStatusPage};
+
+/// Page size of requester lookups when no `limit` is given.
+pub const DEFAULT_PAGE_LIMIT: i64 = 50;
+pub const MAX_PAGE_LIMIT: i64 = 200;
+
+/// Status lookups served over HTTP:
+/// `GET /tx-requests/{tx_id}` and
+/// `GET /tx-requests?requester_id={requester_id}&cursor={cursor}&limit={limit}`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatusRequest {
+    ByTxId(String),
+    ByRequesterId {
+        requester_id: String,
+        cursor: Option<i64>,
+        limit: i64,
+    },
+}
+
+impl StatusRequest {
+    /// None when neither id is given or the page parameters are malformed.
+    pub fn build(
+        tx_id: Option<&str>,
+        requester_id: Option<&str>,
+        cursor: Option<&str>,
+        limit: Option<&str>,
+    ) -> Option<Self> {
+        match (tx_id, requester_id) {
+            (Some(tx_id), _) if !tx_id.is_empty() => Some(Self::ByTxId(tx_id.to_string())),
+            (_, Some(requester_id)) if !requester_id.is_empty() => {
+                let cursor = cursor.map(str::parse).transpose().ok()?;
+                let limit = match limit {
+                    Some(limit) => limit.parse().ok()?,
+                    None => DEFAULT_PAGE_LIMIT,
+                };
+                if !(1..=MAX_PAGE_LIMIT).contains(&limit) {
+                    return None;
+                }
+                Some(Self::ByRequesterId {
+                    requester_id: requester_id.to_string(),
+                    cursor,
+                    limit,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+pub struct StatusResponse {
+    pub status_code: u16,
+    pub body: String,
+}
+
+impl StatusResponse {
+    fn json<T: serde::Serialize>(status_code: u16, body: &T) -> anyhow::Result<Self> {
+        Ok(Self {
+            status_code,
+            body: serde_json::to_string(body)?,
+        })
+    }
+
+    fn error(status_code: u16, message: &str) -> anyhow::Result<Self> {
+        Self::json(status_code, &serde_json::json!({ "error": message }))
+    }
+
+    pub fn bad_request() -> anyhow::Result<Self> {
+        Self::error(
+            400,
+            &format!(
+                "Expected a tx_id path parameter or a requester_id query parameter, \
+                 with an optional numeric cursor and a limit up to {MAX_PAGE_LIMIT}"
+            ),
+        )
+    }
+
+    pub fn internal_error() -> anyhow::Result<Self> {
+        Self::error(500, "Internal server error")
+    }
+}
+
+/// Runs the lookup and renders it the same way for every transport.
+pub async fn handle_status_request(
+    status_query: &StatusQuery,
+    request: Option<StatusRequest>,
+) -> anyhow::Result<StatusResponse> {
+    match request {
+        Some(StatusRequest::ByTxId(tx_id)) => match status_query.by_tx_id(&tx_id).await? {
+            Some(tx_request_status) => StatusResponse::json(200, &tx_request_status),
+            None => StatusResponse::error(404, &format!("Tx request not found: {tx_id}")),
+        },
+        Some(StatusRequest::ByRequesterId {
+            requester_id,
+            cursor,
+            limit,
+        }) => {
+            let tx_request_status_page: TxRequestStatusPage = status_query
+                .by_requester_id(&requester_id, cursor, limit)
+                .await?;
+            StatusResponse::json(200, &tx_request_status_page)
+        }
+        None => StatusResponse::bad_request(),
+    }
+}
@@ -2,7 +2,10 @@ use alloy::{consensus::BlobTransactionSidecarEip7594, primitives::FixedBytes};
 use blob_tx_input_db::blob_tx_inputs::NewBlobTxInput;
 use db_types::{BlobStorageType, TxStatus, TxType};
 use serde::{Deserialize, Serialize};
-use tx_request_db::types::{NewTxInput, NewTxRequest, NewTxRequestWithTxInput};
+use tx_request_db::{
+    payload_hash::BlobPayload,
+    types::{NewTxInput, NewTxRequest, NewTxRequestWithTxInput},
+};
 use uuid::Uuid;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -39,6 +42,7 @@ impl BlobTxRequestBody {
                 chain_id: self.chain_id,
                 use_operator_wallet_id: self.use_operator_wallet_id.clone(),
                 metadata: None,
+                payload_hash: self.payload_hash()?,
             },
             tx_input: NewTxInput::Blob(NewBlobTxInput {
                 tx_id: self.tx_id.clone(),
@@ -52,4 +56,17 @@ impl BlobTxRequestBody {
             }),
         })
     }
+
+    /// Hash of everything the requester controls apart from the tx_id.
+    pub fn payload_hash(&self) -> anyhow::Result<Vec<u8>> {
+        BlobPayload {
+            requester_id: &self.requester_id,
+            chain_id: self.chain_id,
+            deadline_timestamp: self.deadline_timestamp,
+            storage_type: &self.storage_type,
+            source_file_path: &self.source_file_path,
+            use_operator_wallet_id: self.use_operator_wallet_id,
+        }
+        .hash()
+    }
 }
@@ -1,7 +1,10 @@
-use db_types::{TxStatus, TxType};
+use db_types::{TxStatus, TxType, Wei};
 use serde::{Deserialize, Serialize};
 use standard_tx_input_db::standard_tx_inputs::NewStandardTxInput;
-use tx_request_db::types::{NewTxInput, NewTxRequest, NewTxRequestWithTxInput};
+use tx_request_db::{
+    payload_hash::StandardPayload,
+    types::{NewTxInput, NewTxRequest, NewTxRequestWithTxInput},
+};
 use uuid::Uuid;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -11,7 +14,7 @@ pub struct StandardTxRequestBody {
     pub chain_id: i64,
     pub calldata: String,
     pub to_address: String,
-    pub value_wei: i64,
+    pub value_wei: Wei,
     pub deadline_timestamp: i64,
     pub pass_value_from_operator_wallet: bool,
     pub use_operator_wallet_id: Option<Uuid>,
@@ -29,6 +32,7 @@ impl StandardTxRequestBody {
                 chain_id: self.chain_id,
                 use_operator_wallet_id: self.use_operator_wallet_id.clone(),
                 metadata: self.metadata.clone(),
+                payload_hash: self.payload_hash()?,
             },
             tx_input: NewTxInput::Standard(NewStandardTxInput {
                 tx_id: self.tx_id.clone(),
@@ -42,6 +46,45 @@ impl StandardTxRequestBody {
         })
     }
 
+    /// Request row for a request refused before signing. It has no input row.
+    pub fn invalid_tx_request(
+        &self,
+        use_operator_wallet_id: Option<Uuid>,
+    ) -> anyhow::Result<NewTxRequest> {
+        Ok(NewTxRequest {
+            tx_id: self.tx_id.clone(),
+            requester_id: self.requester_id.clone(),
+            tx_status: TxStatus::INVALID,
+            tx_type: TxType::STANDARD,
+            chain_id: self.chain_id,
+            use_operator_wallet_id,
+            metadata: self.metadata.clone(),
+            payload_hash: self.payload_hash()?,
+        })
+    }
+
+    /// Hash of everything the requester controls apart from the tx_id. Calldata and
+    /// address spelling are normalized so that a replay hashes the same; calldata that
+    /// doesn't decode is hashed as given.
+    pub fn payload_hash(&self) -> anyhow::Result<Vec<u8>> {
+        let calldata = self
+            .calldata_vec()
+            .map(hex::encode)
+            .unwrap_or_else(|_| self.calldata.clone());
+        StandardPayload {
+            requester_id: &self.requester_id,
+            chain_id: self.chain_id,
+            calldata: &calldata,
+            to_address: &self.to_address,
+            value_wei: self.value_wei,
+            deadline_timestamp: self.deadline_timestamp,
+            pass_value_from_operator_wallet: self.pass_value_from_operator_wallet,
+            use_operator_wallet_id: self.use_operator_wallet_id,
+            metadata: self.metadata.as_deref(),
+        }
+        .hash()
+    }
+
     fn calldata_vec(&self) -> anyhow::Result<Vec<u8>> {
         let hex = self
             .calldata
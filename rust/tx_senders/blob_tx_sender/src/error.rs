@@ -0,0 +1,293 @@
+#![cfg(feature = "aws")]
+use crate::{
+    execution_attempt::ExecutionAttemptFromBlobFailed, orchestrator::aws::AwsLambdaOrchestrator,
+    transaction::split_already_used,
+};
+use db_types::{ExecutionErrorObject, TxStatus};
+use execution_attempt_db::execution_attempts::{
+    ExecutionAttempt, ExecutionAttemptRepo, NewExecutionAttempt,
+};
+use execution_attempt_item_db::execution_attempt_items::ExecutionAttemptItemRepo;
+use lambda_runtime::tracing;
+use outcome_emitter::{
+    emitter::OutcomeEmitter,
+    lifecycle::LifecycleEvent,
+    outbox::{OutboxRelay, to_outbox_events},
+    outcome::OutcomeEvent,
+};
+use retry_queue::RetryQueueMessageBody;
+use seoa_contract::{
+    contract::{ContractManager, SEOA},
+    transaction::BlobBatchTxContext,
+};
+use sqs_queue::{message_body::ToJsonString, queue::SqsQueue};
+use tx_request_db::repo::TxRequestRepo;
+use wallet_pool::wallet::Wallet;
+
+/// A batch that failed to send, with the items that can still be sent in a new batch.
+pub struct FailedBlobBatch {
+    pub execution_attempt: ExecutionAttempt,
+    pub rest: Option<BlobBatchTxContext>,
+}
+
+#[allow(async_fn_in_trait)]
+pub trait BlobExecutionErrorHandler {
+    fn execution_attempt_repo(&self) -> &ExecutionAttemptRepo;
+    fn execution_attempt_item_repo(&self) -> &ExecutionAttemptItemRepo;
+    fn tx_request_repo(&self) -> &TxRequestRepo;
+    fn retry_queue(&self) -> &SqsQueue;
+    fn outcome_emitter(&self) -> &impl OutcomeEmitter;
+    fn outbox_relay(&self) -> &OutboxRelay;
+    fn contract_manager(&self) -> &ContractManager;
+
+    async fn handle_blob_error(
+        &self,
+        blob_batch_context: &BlobBatchTxContext,
+        wallet: &Wallet,
+        error: anyhow::Error,
+    ) -> anyhow::Result<Option<ExecutionAttempt>> {
+        let Some(failed_new_execution) =
+            build_failed_new_blob_execution(blob_batch_context, wallet, error)?
+        else {
+            return Ok(None);
+        };
+
+        self.record_failed_blob_batch(blob_batch_context, wallet, &failed_new_execution)
+            .await
+            .map(Some)
+    }
+
+    /// Handles the error of a new batch. When the batch reverted with `AlreadyUsed`, only the
+    /// items whose salt is used on chain are failed, and the rest is returned to be sent again.
+    /// If no salt reads as used, the whole batch is failed so that it is not sent again.
+    async fn handle_new_blob_batch_error(
+        &self,
+        blob_batch_context: &BlobBatchTxContext,
+        wallet: &Wallet,
+        error: anyhow::Error,
+    ) -> anyhow::Result<FailedBlobBatch> {
+        if let Some(execution_attempt) = self
+            .handle_blob_error(blob_batch_context, wallet, error)
+            .await?
+        {
+            return Ok(FailedBlobBatch {
+                execution_attempt,
+                rest: None,
+            });
+        }
+
+        let used = self
+            .contract_manager()
+            .used_salts(blob_batch_context.chain_id, &blob_batch_context.get_salts())
+            .await?;
+        let (already_used, rest) = match split_already_used(blob_batch_context, &used) {
+            (Some(already_used), rest) => (already_used, rest),
+            (None, _) => {
+                tracing::warn!(
+                    "No salt of the blob batch reads as used: {:?}",
+                    blob_batch_context.get_tx_ids()
+                );
+                (blob_batch_context.clone(), None)
+            }
+        };
+
+        let failed_new_execution = NewExecutionAttempt::blob_failed(
+            &already_used,
+            wallet.db_record.id,
+            ExecutionErrorObject {
+                error_type: "AlreadyUsed".to_string(),
+                error_body: Some(format!("{:?}", already_used.get_tx_ids())),
+                revert_reason: None,
+            },
+            false,
+        )?;
+        let execution_attempt = self
+            .record_failed_blob_batch(&already_used, wallet, &failed_new_execution)
+            .await?;
+
+        Ok(FailedBlobBatch {
+            execution_attempt,
+            rest,
+        })
+    }
+
+    async fn record_failed_blob_batch(
+        &self,
+        blob_batch_context: &BlobBatchTxContext,
+        wallet: &Wallet,
+        failed_new_execution: &NewExecutionAttempt,
+    ) -> anyhow::Result<ExecutionAttempt> {
+        let execution_attempt = self
+            .execution_attempt_repo()
+            .insert(failed_new_execution)
+            .await?;
+
+        self.execution_attempt_item_repo()
+            .insert_many(execution_attempt.id, &blob_batch_context.get_tx_ids())
+            .await?;
+
+        if failed_new_execution.retryable == Some(true) {
+            let outbox_events = to_outbox_events(&LifecycleEvent::for_tx_requests(
+                &blob_batch_context.tx_requests,
+                TxStatus::RETRIED,
+                failed_new_execution.tx_hash.clone(),
+                Some(wallet.db_record.wallet_address.clone()),
+            ))?;
+            self.tx_request_repo()
+                .set_status_for_many(
+                    &blob_batch_context.get_tx_ids(),
+                    TxStatus::RETRIED,
+                    &outbox_events,
+                )
+                .await?;
+
+            self.outbox_relay()
+                .relay(self.outcome_emitter(), &outbox_events)
+                .await?;
+
+            let message_body = &RetryQueueMessageBody {
+                execution_attempt_id: execution_attempt.id.to_string(),
+                cancel_nonce: false,
+                gap_nonce: None,
+            };
+            self.retry_queue()
+                .send_new(&message_body.to_json_string()?)
+                .await?;
+        } else {
+            let outcome_events: Vec<OutcomeEvent> = blob_batch_context
+                .tx_requests
+                .iter()
+                .map(|tx_request| {
+                    OutcomeEvent::failed(
+                        tx_request.tx_id.clone(),
+                        tx_request.requester_id.clone(),
+                        tx_request.metadata.clone(),
+                        failed_new_execution.error_object.clone(),
+                    )
+                    .with_transaction(
+                        failed_new_execution.used_gas,
+                        failed_new_execution.tx_hash.clone(),
+                    )
+                })
+                .collect();
+            let mut outbox_events = to_outbox_events(&LifecycleEvent::for_tx_requests(
+                &blob_batch_context.tx_requests,
+                TxStatus::FAILED,
+                failed_new_execution.tx_hash.clone(),
+                Some(wallet.db_record.wallet_address.clone()),
+            ))?;
+            outbox_events.extend(to_outbox_events(&outcome_events)?);
+            self.tx_request_repo()
+                .set_status_for_many(
+                    &blob_batch_context.get_tx_ids(),
+                    TxStatus::FAILED,
+                    &outbox_events,
+                )
+                .await?;
+
+            self.outbox_relay()
+                .relay(self.outcome_emitter(), &outbox_events)
+                .await?;
+        }
+
+        Ok(execution_attempt)
+    }
+}
+
+/// None when the batch reverted with `AlreadyUsed`. A replacement leaves its requests to the
+/// attempt that executed them, a new batch fails the used items through
+/// `handle_new_blob_batch_error`.
+pub fn build_failed_new_blob_execution(
+    blob_batch_context: &BlobBatchTxContext,
+    wallet: &Wallet,
+    error: anyhow::Error,
+) -> anyhow::Result<Option<NewExecutionAttempt>> {
+    let batch_size = blob_batch_context.tx_requests.len();
+    let splittable = batch_size > 1 && blob_batch_context.use_operator_wallet_id.is_none();
+
+    let (error_object, retryable) = match error.downcast::<alloy::contract::Error>() {
+        Ok(alloy_error) => {
+            match alloy_error.try_decode_into_interface_error::<SEOA::SEOAErrors>() {
+                Ok(decoded) => {
+                    let error_type = match decoded {
+                        SEOA::SEOAErrors::Expired(_) => "Expired",
+                        SEOA::SEOAErrors::AlreadyUsed(_) => {
+                            tracing::warn!(
+                                "Tried to send blob batch that was already used: {:?}",
+                                blob_batch_context.get_tx_ids()
+                            );
+                            return Ok(None);
+                        }
+                        SEOA::SEOAErrors::InvalidSignature(_) => "InvalidSignature",
+                        SEOA::SEOAErrors::NotAuthorized(_) => "NotAuthorized",
+                        _ => "Unknown",
+                    };
+                    (
+                        ExecutionErrorObject {
+                            error_type: error_type.to_string(),
+                            error_body: Some(format!("{:?}", blob_batch_context.get_tx_ids())),
+                            revert_reason: None,
+                        },
+                        false,
+                    )
+                }
+                // Reverts that are not sEOA errors come from the DDEX sequencer call. A single
+                // item fails the whole batch, so the batch is retried in smaller parts.
+                Err(encoded_error) => (
+                    ExecutionErrorObject {
+                        error_type: "Generic alloy error".to_string(),
+                        error_body: Some(encoded_error.to_string()),
+                        revert_reason: None,
+                    },
+                    splittable,
+                ),
+            }
+        }
+        Err(generic_error) => (
+            ExecutionErrorObject {
+                error_type: "Generic error".to_string(),
+                error_body: Some(generic_error.to_string()),
+                revert_reason: None,
+            },
+            false,
+        ),
+    };
+
+    NewExecutionAttempt::blob_failed(
+        blob_batch_context,
+        wallet.db_record.id,
+        error_object,
+        retryable,
+    )
+    .map(Some)
+}
+
+impl BlobExecutionErrorHandler for AwsLambdaOrchestrator {
+    fn execution_attempt_repo(&self) -> &ExecutionAttemptRepo {
+        &self.execution_attempt_repo
+    }
+
+    fn execution_attempt_item_repo(&self) -> &ExecutionAttemptItemRepo {
+        &self.execution_attempt_item_repo
+    }
+
+    fn tx_request_repo(&self) -> &TxRequestRepo {
+        &self.tx_request_repo
+    }
+
+    fn retry_queue(&self) -> &SqsQueue {
+        &self.retry_queue
+    }
+
+    fn outcome_emitter(&self) -> &impl OutcomeEmitter {
+        &self.outcome_emitter
+    }
+
+    fn outbox_relay(&self) -> &OutboxRelay {
+        &self.outbox_relay
+    }
+
+    fn contract_manager(&self) -> &ContractManager {
+        &self.contract_manager
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::bail;
+use db_types::{ExecutionErrorObject, TxExecutionOutcome, TxType, Wei};
+use execution_attempt_db::execution_attempts::NewExecutionAttempt;
+use seoa_contract::transaction::BlobBatchTxContext;
+use uuid::Uuid;
+
+pub trait ExecutionAttemptFromBlobSuccessful {
+    fn blob_successful(
+        tx_context: &BlobBatchTxContext,
+        operator_wallet_id: Uuid,
+        lease_owner: Option<String>,
+        source_execution_attempt_id: Option<Uuid>,
+    ) -> anyhow::Result<NewExecutionAttempt>;
+}
+
+impl ExecutionAttemptFromBlobSuccessful for NewExecutionAttempt {
+    fn blob_successful(
+        tx_context: &BlobBatchTxContext,
+        operator_wallet_id: Uuid,
+        lease_owner: Option<String>,
+        source_execution_attempt_id: Option<Uuid>,
+    ) -> anyhow::Result<Self> {
+        let Some(fees) = tx_context.fees else {
+            bail!("Can't build successful tx without fees");
+        };
+        let Some(max_fee_per_blob_gas) = tx_context.max_fee_per_blob_gas else {
+            bail!("Can't build successful tx without max_fee_per_blob_gas");
+        };
+        if tx_context.tx_hash.is_none() {
+            bail!("Can't build successful tx without tx_hash");
+        };
+        let nonce_used = try_option_u64_to_option_i64(tx_context.assigned_nonce)?;
+        let gas_limit = try_option_u64_to_option_i64(tx_context.gas_limit)?;
+
+        Ok(NewExecutionAttempt {
+            chain_id: tx_context.chain_id,
+            operator_wallet_id,
+            nonce_used,
+            tx_type: TxType::BLOB,
+            tx_hash: tx_context.tx_hash.clone(),
+            used_gas: None,
+            gas_limit,
+            max_fee_per_gas: Some(i64::try_from(fees.max_fee_per_gas)?),
+            max_priority_fee: Some(i64::try_from(fees.max_priority_fee_per_gas)?),
+            max_fee_per_blob_gas: Some(i64::try_from(max_fee_per_blob_gas)?),
+            tx_value: Wei::ZERO,
+            outcome: None,
+            error_object: None,
+            retryable: None,
+            source_execution_attempt_id,
+            lease_owner,
+        })
+    }
+}
+
+pub trait ExecutionAttemptFromBlobFailed {
+    fn blob_failed(
+        tx_context: &BlobBatchTxContext,
+        operator_wallet_id: Uuid,
+        error_object: ExecutionErrorObject,
+        retryable: bool,
+    ) -> anyhow::Result<NewExecutionAttempt>;
+}
+
+impl ExecutionAttemptFromBlobFailed for NewExecutionAttempt {
+    fn blob_failed(
+        tx_context: &BlobBatchTxContext,
+        operator_wallet_id: Uuid,
+        error_object: ExecutionErrorObject,
+        retryable: bool,
+    ) -> anyhow::Result<Self> {
+        let error_object = Some(serde_json::to_string(&error_object)?);
+
+        if tx_context.successfully_simulated {
+            let (Some(fees), Some(max_fee_per_blob_gas)) =
+                (tx_context.fees, tx_context.max_fee_per_blob_gas)
+            else {
+                bail!("Tx fees should be known after simulation");
+            };
+            let nonce_used = try_option_u64_to_option_i64(tx_context.assigned_nonce)?;
+            let gas_limit = try_option_u64_to_option_i64(tx_context.gas_limit)?;
+
+            Ok(NewExecutionAttempt {
+                chain_id: tx_context.chain_id,
+                operator_wallet_id,
+                nonce_used,
+                tx_value: Wei::ZERO,
+                tx_type: TxType::BLOB,
+                tx_hash: tx_context.tx_hash.clone(),
+                gas_limit,
+                used_gas: None,
+                max_fee_per_gas: Some(i64::try_from(fees.max_fee_per_gas)?),
+                max_priority_fee: Some(i64::try_from(fees.max_priority_fee_per_gas)?),
+                max_fee_per_blob_gas: Some(i64::try_from(max_fee_per_blob_gas)?),
+                outcome: Some(TxExecutionOutcome::REVERTED),
+                error_object,
+                retryable: Some(retryable),
+                source_execution_attempt_id: None,
+                lease_owner: None,
+            })
+        } else {
+            Ok(NewExecutionAttempt {
+                chain_id: tx_context.chain_id,
+                operator_wallet_id,
+                nonce_used: None,
+                tx_value: Wei::ZERO,
+                tx_type: TxType::BLOB,
+                tx_hash: None,
+                gas_limit: None,
+                used_gas: None,
+                max_fee_per_gas: None,
+                max_priority_fee: None,
+                max_fee_per_blob_gas: None,
+                outcome: Some(TxExecutionOutcome::REVERTED),
+                error_object,
+                retryable: Some(retryable),
+                source_execution_attempt_id: None,
+                lease_owner: None,
+            })
+        }
+    }
+}
+
+fn try_option_u64_to_option_i64(input: Option<u64>) -> anyhow::Result<Option<i64>> {
+    let Some(output_u64) = input else {
+        bail!("Can't parse None value");
+    };
+
+    let output = Some(i64::try_from(output_u64)?);
+
+    Ok(output)
+}
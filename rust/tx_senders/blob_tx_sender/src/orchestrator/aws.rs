@@ -0,0 +1,277 @@
+#![cfg(feature = "aws")]
+
+use crate::{
+    Config, error::BlobExecutionErrorHandler,
+    execution_attempt::ExecutionAttemptFromBlobSuccessful, transaction::BlobTxContextBuilder,
+};
+use aws_lambda_events::sqs::{SqsBatchResponse, SqsEvent};
+use blob_sender_queue::BlobSenderQueueEvent;
+use blob_storage::storage::s3::S3BlobStorageManager;
+use db_types::TxStatus;
+use execution_attempt_db::execution_attempts::{
+    ExecutionAttempt, ExecutionAttemptRepo, NewExecutionAttempt,
+};
+use execution_attempt_item_db::execution_attempt_items::ExecutionAttemptItemRepo;
+use lambda_runtime::{LambdaEvent, tracing};
+use network_db::networks::NetworkRepo;
+use operator_wallet_db::operator_wallets::OperatorWalletRepo;
+use outbox_db::outbox_events::OutboxEventRepo;
+use outcome_emitter::{
+    emitter::{
+        event_bridge::AwsEventBridgeOutcomeEmitter, routing::RoutingOutcomeEmitter,
+        webhook::WebhookOutcomeEmitter,
+    },
+    lifecycle::LifecycleEvent,
+    outbox::{OutboxRelay, to_outbox_events},
+};
+use receipt_poller_queue::ReceiptPollerQueueMessageBody;
+use seoa_contract::{contract::ContractManager, transaction::BlobBatchTxContext};
+use sqs_queue::{message_body::ToJsonString, queue::SqsQueue};
+use std::collections::VecDeque;
+use tx_request_db::repo::TxRequestRepo;
+use wallet_assignment_db::wallet_assignments::WalletAssignmentRepo;
+use wallet_pool::{manager::WalletPoolManager, wallet::Wallet};
+use webhook_db::{webhook_deliveries::WebhookDeliveryRepo, webhook_endpoints::WebhookEndpointRepo};
+
+pub struct AwsLambdaOrchestrator {
+    pub wallet_assignment_repo: WalletAssignmentRepo,
+    pub tx_request_repo: TxRequestRepo,
+    pub execution_attempt_repo: ExecutionAttemptRepo,
+    pub execution_attempt_item_repo: ExecutionAttemptItemRepo,
+    pub wallet_pool_manager: WalletPoolManager,
+    pub tx_context_builder: BlobTxContextBuilder,
+    pub contract_manager: ContractManager,
+    pub receipt_poller_queue: SqsQueue,
+    pub retry_queue: SqsQueue,
+    pub outcome_emitter: RoutingOutcomeEmitter,
+    pub outbox_relay: OutboxRelay,
+}
+
+impl AwsLambdaOrchestrator {
+    pub async fn build(
+        pool: &sqlx::Pool<sqlx::Postgres>,
+        aws_config: &aws_config::SdkConfig,
+    ) -> anyhow::Result<Self> {
+        tracing::info!("Building blob_tx_sender...");
+
+        let config = Config::build()?;
+
+        let wallet_assignment_repo = WalletAssignmentRepo::new(pool.clone());
+        let operator_wallet_repo = OperatorWalletRepo::new(pool.clone());
+        let network_repo = NetworkRepo::new(pool.clone());
+        let tx_request_repo = TxRequestRepo::new(pool.clone());
+        let execution_attempt_repo = ExecutionAttemptRepo::new(pool.clone());
+        let execution_attempt_item_repo = ExecutionAttemptItemRepo::new(pool.clone());
+        let networks = network_repo.select_all().await?;
+        let blob_storage_manager =
+            S3BlobStorageManager::build(aws_config, &config.blob_storage_bucket_name);
+
+        let wallet_pool_manager = WalletPoolManager::build(operator_wallet_repo, &networks);
+        let tx_context_builder =
+            BlobTxContextBuilder::build(&tx_request_repo, blob_storage_manager, &networks)?;
+        let contract_manager = ContractManager::build(&networks).await?;
+        let sqs_client = aws_sdk_sqs::Client::new(aws_config);
+        let receipt_poller_queue = SqsQueue::build(
+            &sqs_client,
+            &config.receipt_poller_queue_url,
+            &config.receipt_poller_queue_message_group_id,
+        )?;
+        let event_bridge_client = aws_sdk_eventbridge::Client::new(aws_config);
+        let outcome_emitter = RoutingOutcomeEmitter::build(
+            AwsEventBridgeOutcomeEmitter::build(
+                &event_bridge_client,
+                config.outcome_event_bus_name,
+                config.outcome_schema_version,
+            ),
+            WebhookOutcomeEmitter::build(
+                WebhookEndpointRepo::new(pool.clone()),
+                WebhookDeliveryRepo::new(pool.clone()),
+                config.outcome_schema_version,
+            )?,
+        );
+        let outbox_relay = OutboxRelay::new(OutboxEventRepo::new(pool.clone()));
+
+        let retry_queue = SqsQueue::build(
+            &sqs_client,
+            &config.retry_queue_url,
+            &config.retry_queue_message_group_id,
+        )?;
+        Ok(Self {
+            wallet_assignment_repo,
+            tx_request_repo,
+            execution_attempt_repo,
+            execution_attempt_item_repo,
+            wallet_pool_manager,
+            tx_context_builder,
+            contract_manager,
+            receipt_poller_queue,
+            retry_queue,
+            outcome_emitter,
+            outbox_relay,
+        })
+    }
+
+    pub async fn function_handler(
+        &self,
+        event: LambdaEvent<SqsEvent>,
+    ) -> anyhow::Result<SqsBatchResponse, lambda_runtime::Error> {
+        let mut sqs_batch_response = SqsBatchResponse::default();
+        tracing::info!("Reading...");
+        let tx_sender_queue_event = BlobSenderQueueEvent::from_sqs_lambda_event(event)?;
+
+        let tx_ids = tx_sender_queue_event
+            .messages
+            .iter()
+            .map(|message| message.body.tx_id.clone())
+            .collect::<Vec<String>>();
+
+        let mut blob_batch_contexts: VecDeque<BlobBatchTxContext> = self
+            .tx_context_builder
+            .fetch_and_sort_into_batches(&tx_ids)
+            .await?
+            .into();
+        // Requests the builder turned away as INVALID left their events pending
+        self.outbox_relay
+            .relay_for_tx_ids(&self.outcome_emitter, &tx_ids)
+            .await?;
+
+        tracing::info!("Executing...");
+        // The items of a batch that are not already used go back in as a new batch
+        while let Some(mut blob_batch_context) = blob_batch_contexts.pop_front() {
+            let Some(mut wallet) = self
+                .wallet_pool_manager
+                .acquire(
+                    blob_batch_context.chain_id,
+                    blob_batch_context.use_operator_wallet_id,
+                    blob_batch_context.get_requester_id(),
+                )
+                .await?
+            else {
+                self.tx_request_repo
+                    .release_many(&blob_batch_context.get_tx_ids())
+                    .await?;
+                blob_batch_context.get_tx_ids().iter().for_each(|tx_id| {
+                    if let Some(message_id) = tx_sender_queue_event.tx_id_to_message_id.get(tx_id) {
+                        sqs_batch_response.add_failure(message_id);
+                    };
+                });
+                continue;
+            };
+
+            self.wallet_assignment_repo
+                .new_assignments(&blob_batch_context.get_tx_ids(), wallet.db_record.id)
+                .await?;
+
+            match self
+                .contract_manager
+                .simulate_send_blob_batch_tx(&mut blob_batch_context, &mut wallet)
+                .await
+            {
+                Ok(_) => {}
+                Err(err) => {
+                    tracing::error!("{err:?}");
+                    self.wallet_pool_manager
+                        .release_unused(wallet.db_record.id)
+                        .await?;
+                    let failed_blob_batch = self
+                        .handle_new_blob_batch_error(&blob_batch_context, &wallet, err)
+                        .await?;
+                    blob_batch_contexts.extend(failed_blob_batch.rest);
+                    continue;
+                }
+            };
+
+            match self
+                .contract_manager
+                .send_blob_batch(&mut blob_batch_context, &wallet)
+                .await
+            {
+                Ok(_) => {
+                    let execution_attempt = self
+                        .save_successful_execution(&blob_batch_context, &wallet)
+                        .await?;
+                    self.wallet_pool_manager
+                        .release_sent(&wallet, blob_batch_context.assigned_nonce)
+                        .await?;
+
+                    self.send_receipt_poller_queue_message(
+                        &blob_batch_context,
+                        &execution_attempt.id.to_string(),
+                    )
+                    .await?;
+                }
+
+                Err(err) => {
+                    tracing::error!("{err:?}");
+                    self.wallet_pool_manager
+                        .release_unused(wallet.db_record.id)
+                        .await?;
+                    let failed_blob_batch = self
+                        .handle_new_blob_batch_error(&blob_batch_context, &wallet, err)
+                        .await?;
+                    blob_batch_contexts.extend(failed_blob_batch.rest);
+                }
+            };
+        }
+
+        Ok(sqs_batch_response)
+    }
+
+    pub async fn save_successful_execution(
+        &self,
+        blob_batch_context: &BlobBatchTxContext,
+        wallet: &Wallet,
+    ) -> anyhow::Result<ExecutionAttempt> {
+        let execution_attempt_input = NewExecutionAttempt::blob_successful(
+            blob_batch_context,
+            wallet.db_record.id,
+            wallet.db_record.lease_owner.clone(),
+            None,
+        )?;
+        let execution_attempt = self
+            .execution_attempt_repo
+            .insert(&execution_attempt_input)
+            .await?;
+
+        self.execution_attempt_item_repo
+            .insert_many(execution_attempt.id, &blob_batch_context.get_tx_ids())
+            .await?;
+
+        let outbox_events = to_outbox_events(&LifecycleEvent::for_tx_requests(
+            &blob_batch_context.tx_requests,
+            TxStatus::BROADCASTED,
+            execution_attempt.tx_hash.clone(),
+            Some(wallet.db_record.wallet_address.clone()),
+        ))?;
+        self.tx_request_repo
+            .set_status_for_many(
+                &blob_batch_context.get_tx_ids(),
+                TxStatus::BROADCASTED,
+                &outbox_events,
+            )
+            .await?;
+
+        self.outbox_relay
+            .relay(&self.outcome_emitter, &outbox_events)
+            .await?;
+
+        Ok(execution_attempt)
+    }
+
+    pub async fn send_receipt_poller_queue_message(
+        &self,
+        blob_batch_context: &BlobBatchTxContext,
+        execution_attempt_id: &str,
+    ) -> anyhow::Result<()> {
+        let receipt_poller_queue_message_body = ReceiptPollerQueueMessageBody {
+            execution_attempt_id: execution_attempt_id.to_string(),
+            batch_size: u8::try_from(blob_batch_context.tx_requests.len())?,
+        };
+
+        self.receipt_poller_queue
+            .send_new(&receipt_poller_queue_message_body.to_json_string()?)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+#[cfg(feature = "aws")]
+pub mod aws;
@@ -0,0 +1,321 @@
+use alloy::{
+    eips::eip7594::MAX_BLOBS_PER_TX_FUSAKA,
+    primitives::{FixedBytes, Uint, keccak256},
+};
+use blob_storage::storage::s3::S3BlobStorageManager;
+use db_types::{ExecutionErrorObject, TxStatus};
+use network_db::networks::Network;
+use outcome_emitter::{lifecycle::LifecycleEvent, outbox::to_outbox_events, outcome::OutcomeEvent};
+use seoa_contract::{
+    contract::sEOA::BlobBatchInput,
+    transaction::{BlobBatchInputWithSidecar, BlobBatchTxContext},
+};
+use std::collections::HashMap;
+use tx_request_db::{
+    repo::TxRequestRepo,
+    types::{BlobTxRequestRaw, IntoTxRequestWithInput, TxRequestWithInput},
+};
+use uuid::Uuid;
+
+#[cfg(test)]
+mod tests;
+
+/// Blobs a single transaction may carry on the network: its `max_blobs_per_tx`, capped by the
+/// protocol limit.
+pub fn max_blobs_per_tx(network: &Network) -> anyhow::Result<usize> {
+    let protocol_max_blobs = usize::try_from(MAX_BLOBS_PER_TX_FUSAKA)?;
+
+    Ok(match network.max_blobs_per_tx {
+        Some(max_blobs_per_tx) => usize::try_from(max_blobs_per_tx)?.min(protocol_max_blobs),
+        None => protocol_max_blobs,
+    })
+}
+
+type BlobItem = (TxRequestWithInput, BlobBatchInputWithSidecar);
+
+pub struct BlobTxContextBuilder {
+    transaction_repo: TxRequestRepo,
+    blob_storage_manager: S3BlobStorageManager,
+    max_blobs_per_tx_by_chain_id: HashMap<i64, usize>,
+}
+
+impl BlobTxContextBuilder {
+    pub fn build(
+        transaction_repo: &TxRequestRepo,
+        blob_storage_manager: S3BlobStorageManager,
+        networks: &[Network],
+    ) -> anyhow::Result<Self> {
+        let mut max_blobs_per_tx_by_chain_id = HashMap::new();
+        for network in networks {
+            max_blobs_per_tx_by_chain_id.insert(network.chain_id, max_blobs_per_tx(network)?);
+        }
+
+        Ok(Self {
+            transaction_repo: transaction_repo.clone(),
+            blob_storage_manager,
+            max_blobs_per_tx_by_chain_id,
+        })
+    }
+
+    pub async fn fetch_and_sort_into_batches(
+        &self,
+        tx_ids: &Vec<String>,
+    ) -> anyhow::Result<Vec<BlobBatchTxContext>> {
+        let fetched_txs = self
+            .transaction_repo
+            .select_and_lock_many_blob(tx_ids)
+            .await?;
+
+        let sorted = Self::group_by_chain_and_wallet(fetched_txs);
+
+        let mut batch_contexts = Vec::new();
+        for (chain_id, wallet_map) in sorted {
+            for (use_operator_wallet_id, transactions) in wallet_map {
+                batch_contexts.extend(
+                    self.build_batch_contexts(chain_id, use_operator_wallet_id, transactions)
+                        .await,
+                );
+            }
+        }
+
+        Ok(batch_contexts)
+    }
+
+    /// Packs the requests into batches that stay within the network's blobs per transaction.
+    async fn build_batch_contexts(
+        &self,
+        chain_id: i64,
+        use_operator_wallet_id: Option<Uuid>,
+        transactions: Vec<BlobTxRequestRaw>,
+    ) -> Vec<BlobBatchTxContext> {
+        let Some(items) = self.load_items(transactions).await else {
+            return Vec::new();
+        };
+        let max_blobs_per_tx = self
+            .max_blobs_per_tx_by_chain_id
+            .get(&chain_id)
+            .copied()
+            .unwrap_or(MAX_BLOBS_PER_TX_FUSAKA as usize);
+
+        let (batches, over_blob_limit) = pack_by_blobs(items, max_blobs_per_tx);
+        for (tx_request, blob_batch_with_sidecar) in over_blob_limit {
+            self.mark_as_invalid(
+                &tx_request.tx_id,
+                &tx_request.requester_id,
+                tx_request.attempts,
+                &tx_request.metadata,
+                ExecutionErrorObject {
+                    error_type: "ExceedsBlobLimit".to_string(),
+                    error_body: Some(format!(
+                        "{} blobs are above the network's {max_blobs_per_tx} blobs per transaction",
+                        blob_batch_with_sidecar.sidecar.blobs.len()
+                    )),
+                    revert_reason: None,
+                },
+            )
+            .await
+            .ok();
+        }
+
+        batches
+            .into_iter()
+            .map(|batch| new_batch_context(chain_id, use_operator_wallet_id, batch))
+            .collect()
+    }
+
+    async fn load_items(&self, transactions: Vec<BlobTxRequestRaw>) -> Option<Vec<BlobItem>> {
+        let mut items = Vec::new();
+
+        for transaction in transactions {
+            match transaction.clone().into_blob_batch_input() {
+                Ok(blob_batch_input) => {
+                    let tx_request = transaction.into_tx_request_with_input().ok()?;
+                    let blob_input_json_file = self
+                        .blob_storage_manager
+                        .read_json_file(transaction.source_file_path)
+                        .await
+                        .ok()?;
+                    items.push((
+                        tx_request,
+                        BlobBatchInputWithSidecar {
+                            blob_batch_input: blob_batch_input.clone(),
+                            sidecar: blob_input_json_file.blob_sidecar,
+                        },
+                    ));
+                }
+                Err(_) => {
+                    self.mark_as_invalid(
+                        &transaction.tx_id,
+                        &transaction.requester_id,
+                        transaction.attempts,
+                        &transaction.metadata,
+                        ExecutionErrorObject {
+                            error_type: "InvalidInput".to_string(),
+                            error_body: None,
+                            revert_reason: None,
+                        },
+                    )
+                    .await
+                    .ok();
+                }
+            }
+        }
+
+        Some(items)
+    }
+
+    /// The INVALID lifecycle and outcome events are published when the sender relays the
+    /// events of its requests.
+    async fn mark_as_invalid(
+        &self,
+        tx_id: &String,
+        requester_id: &str,
+        attempts: i32,
+        metadata: &Option<String>,
+        error_object: ExecutionErrorObject,
+    ) -> anyhow::Result<()> {
+        let mut outbox_events = to_outbox_events(&[LifecycleEvent::new(
+            TxStatus::INVALID,
+            tx_id.clone(),
+            requester_id.to_string(),
+            attempts,
+            metadata.clone(),
+        )])?;
+        outbox_events.extend(to_outbox_events(&[OutcomeEvent::invalid(
+            tx_id.clone(),
+            requester_id.to_string(),
+            metadata.clone(),
+            serde_json::to_string(&error_object)?,
+        )])?);
+        self.transaction_repo
+            .mark_as_invalid(tx_id, &outbox_events)
+            .await
+    }
+
+    fn group_by_chain_and_wallet(
+        transactions: Vec<BlobTxRequestRaw>,
+    ) -> HashMap<i64, HashMap<Option<Uuid>, Vec<BlobTxRequestRaw>>> {
+        let mut grouped: HashMap<i64, HashMap<Option<Uuid>, Vec<BlobTxRequestRaw>>> =
+            HashMap::new();
+
+        for tx in transactions {
+            grouped
+                .entry(tx.chain_id)
+                .or_default()
+                .entry(tx.use_operator_wallet_id)
+                .or_default()
+                .push(tx);
+        }
+
+        grouped
+    }
+}
+
+/// Splits the items, in order, into batches of at most `max_blobs_per_tx` blobs. Items with more
+/// blobs than that can't be sent on this network and are returned separately.
+fn pack_by_blobs(
+    items: Vec<BlobItem>,
+    max_blobs_per_tx: usize,
+) -> (Vec<Vec<BlobItem>>, Vec<BlobItem>) {
+    let mut batches = Vec::new();
+    let mut over_blob_limit = Vec::new();
+    let mut batch = Vec::new();
+    let mut batch_blobs = 0;
+
+    for item in items {
+        let item_blobs = item.1.sidecar.blobs.len();
+        if item_blobs > max_blobs_per_tx {
+            over_blob_limit.push(item);
+            continue;
+        }
+        if !batch.is_empty() && batch_blobs + item_blobs > max_blobs_per_tx {
+            batches.push(std::mem::take(&mut batch));
+            batch_blobs = 0;
+        }
+        batch_blobs += item_blobs;
+        batch.push(item);
+    }
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+
+    (batches, over_blob_limit)
+}
+
+fn new_batch_context(
+    chain_id: i64,
+    use_operator_wallet_id: Option<Uuid>,
+    items: Vec<BlobItem>,
+) -> BlobBatchTxContext {
+    let (tx_requests, blob_batch_with_sidecar_vec) = items.into_iter().unzip();
+    BlobBatchTxContext {
+        chain_id,
+        use_operator_wallet_id,
+        blob_batch_with_sidecar_vec,
+        tx_requests,
+        successfully_simulated: false,
+        assigned_nonce: None,
+        fees: None,
+        max_fee_per_blob_gas: None,
+        gas_limit: None,
+        tx_hash: None,
+    }
+}
+
+/// Splits a batch by whether the salt of each item is `used`, into the already-used items and
+/// the rest. Both come back as fresh batches, and a side without items as None.
+pub fn split_already_used(
+    blob_batch_context: &BlobBatchTxContext,
+    used: &[bool],
+) -> (Option<BlobBatchTxContext>, Option<BlobBatchTxContext>) {
+    let (already_used, rest): (Vec<_>, Vec<_>) = blob_batch_context
+        .tx_requests
+        .iter()
+        .cloned()
+        .zip(
+            blob_batch_context
+                .blob_batch_with_sidecar_vec
+                .iter()
+                .cloned(),
+        )
+        .zip(used)
+        .partition(|(_, used)| **used);
+
+    let into_context = |items: Vec<(BlobItem, &bool)>| {
+        (!items.is_empty()).then(|| {
+            new_batch_context(
+                blob_batch_context.chain_id,
+                blob_batch_context.use_operator_wallet_id,
+                items.into_iter().map(|(item, _)| item).collect(),
+            )
+        })
+    };
+
+    (into_context(already_used), into_context(rest))
+}
+
+trait IntoBlobBatchInput {
+    fn into_blob_batch_input(&self) -> anyhow::Result<BlobBatchInput>;
+}
+
+impl IntoBlobBatchInput for BlobTxRequestRaw {
+    fn into_blob_batch_input(&self) -> anyhow::Result<BlobBatchInput> {
+        let image_id_array: [u8; 32] =
+            self.image_id.clone().try_into().map_err(|v: Vec<u8>| {
+                anyhow::anyhow!("image_id must be 32 bytes, got {}", v.len())
+            })?;
+        let blob_sha2_array: [u8; 32] =
+            self.blob_sha2.clone().try_into().map_err(|v: Vec<u8>| {
+                anyhow::anyhow!("blob_sha2 must be 32 bytes, got {}", v.len())
+            })?;
+
+        Ok(BlobBatchInput {
+            imageId: FixedBytes::<32>::try_from(image_id_array)?,
+            commitment: self.commitment.clone().try_into()?,
+            blobSha2: FixedBytes::<32>::try_from(blob_sha2_array)?,
+            salt: keccak256(self.tx_id.clone().into_bytes()),
+            deadline: Uint::<256, 4>::from(self.deadline_timestamp as u64),
+            signature: self.signature.clone().into(),
+        })
+    }
+}
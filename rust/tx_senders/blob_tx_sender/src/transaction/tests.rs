@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod tests {
+    use crate::transaction::{pack_by_blobs, split_already_used};
+    use alloy::{
+        eips::{eip4844::Blob, eip7594::BlobTransactionSidecarEip7594},
+        primitives::{B256, Bytes, U256},
+    };
+    use blob_tx_input_db::blob_tx_inputs::BlobTxInput;
+    use db_types::{BlobStorageType, TxStatus, TxType};
+    use seoa_contract::{
+        contract::sEOA::BlobBatchInput,
+        transaction::{BlobBatchInputWithSidecar, BlobBatchTxContext},
+    };
+    use sqlx::types::time::OffsetDateTime;
+    use tx_input_types::TxInput;
+    use tx_request_db::types::TxRequestWithInput;
+
+    fn item(tx_id: &str, blobs: usize) -> (TxRequestWithInput, BlobBatchInputWithSidecar) {
+        let tx_request = TxRequestWithInput {
+            tx_id: tx_id.to_string(),
+            requester_id: "requester".to_string(),
+            tx_type: TxType::BLOB,
+            tx_status: TxStatus::LOCKED,
+            tx_input: TxInput::Blob(BlobTxInput {
+                tx_id: tx_id.to_string(),
+                signature: vec![0; 65],
+                image_id: vec![0; 32],
+                commitment: vec![0; 48],
+                blob_sha2: vec![0; 32],
+                deadline_timestamp: 0,
+                source_file_path: format!("{tx_id}.json"),
+                storage_type: BlobStorageType::S3,
+                created_at: OffsetDateTime::UNIX_EPOCH,
+            }),
+            attempts: 1,
+            metadata: None,
+            use_operator_wallet_id: None,
+        };
+        let blob_batch_input_with_sidecar = BlobBatchInputWithSidecar {
+            blob_batch_input: BlobBatchInput {
+                imageId: B256::ZERO,
+                commitment: Bytes::new(),
+                blobSha2: B256::ZERO,
+                salt: B256::ZERO,
+                deadline: U256::ZERO,
+                signature: Bytes::new(),
+            },
+            sidecar: BlobTransactionSidecarEip7594 {
+                blobs: vec![Blob::ZERO; blobs],
+                ..Default::default()
+            },
+        };
+
+        (tx_request, blob_batch_input_with_sidecar)
+    }
+
+    fn tx_ids(
+        batches: &[Vec<(TxRequestWithInput, BlobBatchInputWithSidecar)>],
+    ) -> Vec<Vec<String>> {
+        batches
+            .iter()
+            .map(|batch| {
+                batch
+                    .iter()
+                    .map(|(tx_request, _)| tx_request.tx_id.clone())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn packs_batches_within_blob_limit() {
+        let items = vec![
+            item("tx-1", 2),
+            item("tx-2", 3),
+            item("tx-3", 2),
+            item("tx-4", 1),
+            item("tx-5", 6),
+        ];
+
+        let (batches, over_blob_limit) = pack_by_blobs(items, 6);
+
+        assert!(over_blob_limit.is_empty());
+        assert_eq!(
+            tx_ids(&batches),
+            vec![vec!["tx-1", "tx-2"], vec!["tx-3", "tx-4"], vec!["tx-5"]]
+        );
+    }
+
+    #[test]
+    fn item_over_blob_limit_is_set_aside() {
+        let items = vec![item("tx-1", 1), item("tx-2", 4), item("tx-3", 1)];
+
+        let (batches, over_blob_limit) = pack_by_blobs(items, 3);
+
+        assert_eq!(tx_ids(&batches), vec![vec!["tx-1", "tx-3"]]);
+        assert_eq!(tx_ids(&[over_blob_limit]), vec![vec!["tx-2"]]);
+    }
+
+    #[test]
+    fn keeps_order_and_returns_no_empty_batches() {
+        assert!(pack_by_blobs(Vec::new(), 6).0.is_empty());
+
+        let (batches, _) = pack_by_blobs(vec![item("tx-1", 1), item("tx-2", 1)], 6);
+
+        assert_eq!(tx_ids(&batches), vec![vec!["tx-1", "tx-2"]]);
+    }
+
+    #[test]
+    fn splits_already_used_items_from_the_rest() {
+        let (tx_requests, blob_batch_with_sidecar_vec) =
+            vec![item("tx-1", 1), item("tx-2", 2), item("tx-3", 3)]
+                .into_iter()
+                .unzip();
+        let blob_batch_context = BlobBatchTxContext {
+            chain_id: 1,
+            use_operator_wallet_id: None,
+            blob_batch_with_sidecar_vec,
+            tx_requests,
+            successfully_simulated: true,
+            assigned_nonce: Some(7),
+            fees: None,
+            max_fee_per_blob_gas: None,
+            gas_limit: Some(100_000),
+            tx_hash: Some("0xabc".to_string()),
+        };
+
+        let (already_used, rest) = split_already_used(&blob_batch_context, &[false, true, false]);
+
+        let already_used = already_used.unwrap();
+        assert_eq!(already_used.get_tx_ids(), vec!["tx-2"]);
+        assert_eq!(
+            already_used.blob_batch_with_sidecar_vec[0]
+                .sidecar
+                .blobs
+                .len(),
+            2
+        );
+
+        let rest = rest.unwrap();
+        assert_eq!(rest.get_tx_ids(), vec!["tx-1", "tx-3"]);
+        let rest_blobs: Vec<usize> = rest
+            .blob_batch_with_sidecar_vec
+            .iter()
+            .map(|blob_batch_with_sidecar| blob_batch_with_sidecar.sidecar.blobs.len())
+            .collect();
+        assert_eq!(rest_blobs, vec![1, 3]);
+        assert!(!rest.successfully_simulated);
+        assert_eq!(rest.assigned_nonce, None);
+        assert_eq!(rest.tx_hash, None);
+
+        let (already_used, rest) = split_already_used(&blob_batch_context, &[true, true, true]);
+        assert_eq!(already_used.unwrap().get_tx_ids().len(), 3);
+        assert!(rest.is_none());
+    }
+}
@@ -1,6 +1,8 @@
 use crate::{
-    execution_attempt::ExecutionAttemptFromStandardFailed, orchestrator::aws::AwsLambdaOrchestrator,
+    execution_attempt::ExecutionAttemptFromStandardFailed,
+    orchestrator::aws::AwsLambdaOrchestrator, transaction::build_execute_batch_context,
 };
+use alloy::json_abi::JsonAbi;
 use anyhow::bail;
 use db_types::{ExecutionErrorObject, TxExecutionOutcome, TxStatus};
 use execution_attempt_db::execution_attempts::{
@@ -8,11 +10,19 @@ use execution_attempt_db::execution_attempts::{
 };
 use execution_attempt_item_db::execution_attempt_items::ExecutionAttemptItemRepo;
 use lambda_runtime::tracing;
-use outcome_emitter::{emitter::event_bridge::AwsEventBridgeOutcomeEmitter, outcome::OutcomeEvent};
+use outcome_emitter::{
+    emitter::OutcomeEmitter,
+    lifecycle::LifecycleEvent,
+    outbox::{OutboxRelay, to_outbox_events},
+    outcome::OutcomeEvent,
+};
 use retry_queue::RetryQueueMessageBody;
-use seoa_contract::{contract::SEOA, transaction::ExecuteBatchTxContext};
+use seoa_contract::{
+    contract::SEOA, revert_reason::decode_revert_reason, transaction::ExecuteBatchTxContext,
+};
 use sqs_queue::{message_body::ToJsonString, queue::SqsQueue};
-use tx_request_db::repo::TxRequestRepo;
+use target_abi_db::target_abis::TargetAbiRepo;
+use tx_request_db::{repo::TxRequestRepo, types::TxRequestWithInput};
 use wallet_pool::wallet::Wallet;
 
 #[allow(async_fn_in_trait)]
@@ -20,8 +30,96 @@ pub trait ExecutionErrorHandler {
     fn execution_attempt_repo(&self) -> &ExecutionAttemptRepo;
     fn execution_attempt_item_repo(&self) -> &ExecutionAttemptItemRepo;
     fn tx_request_repo(&self) -> &TxRequestRepo;
+    fn target_abi_repo(&self) -> &TargetAbiRepo;
     fn retry_queue(&self) -> &SqsQueue;
-    fn outcome_emitter(&self) -> &AwsEventBridgeOutcomeEmitter;
+    fn outcome_emitter(&self) -> &impl OutcomeEmitter;
+    fn outbox_relay(&self) -> &OutboxRelay;
+
+    /// ABIs registered for the batch targets. Unparseable ABIs are skipped.
+    async fn load_target_abis(
+        &self,
+        execute_batch_context: &ExecuteBatchTxContext,
+    ) -> anyhow::Result<Vec<JsonAbi>> {
+        let target_abis = self
+            .target_abi_repo()
+            .select_by_addresses(
+                execute_batch_context.chain_id,
+                &execute_batch_context.get_target_addresses(),
+            )
+            .await?;
+
+        Ok(target_abis
+            .into_iter()
+            .filter_map(
+                |target_abi| match serde_json::from_str::<JsonAbi>(&target_abi.abi) {
+                    Ok(abi) => Some(abi),
+                    Err(err) => {
+                        tracing::warn!(
+                            "Invalid ABI of target {} on chain {}: {err}",
+                            target_abi.to_address,
+                            target_abi.chain_id
+                        );
+                        None
+                    }
+                },
+            )
+            .collect())
+    }
+
+    /// Records a single item that failed its own simulation as a non-retryable attempt and
+    /// reports it as FAILED.
+    async fn fail_isolated_item(
+        &self,
+        tx_request: &TxRequestWithInput,
+        wallet: &Wallet,
+        error_object: ExecutionErrorObject,
+    ) -> anyhow::Result<ExecutionAttempt> {
+        let execute_batch_context = build_execute_batch_context(
+            wallet.chain_id,
+            tx_request.use_operator_wallet_id,
+            std::slice::from_ref(tx_request),
+        )?;
+        let failed_new_execution = NewExecutionAttempt::standard_failed(
+            &execute_batch_context,
+            wallet.db_record.id,
+            error_object,
+            false,
+        )?;
+
+        let execution_attempt = self
+            .execution_attempt_repo()
+            .insert(&failed_new_execution)
+            .await?;
+        self.execution_attempt_item_repo()
+            .insert_many(execution_attempt.id, &execute_batch_context.get_tx_ids())
+            .await?;
+        let mut outbox_events = to_outbox_events(&LifecycleEvent::for_tx_requests(
+            std::slice::from_ref(tx_request),
+            TxStatus::FAILED,
+            None,
+            Some(wallet.db_record.wallet_address.clone()),
+        ))?;
+        outbox_events.extend(to_outbox_events(&[OutcomeEvent::failed(
+            tx_request.tx_id.clone(),
+            tx_request.requester_id.clone(),
+            tx_request.metadata.clone(),
+            failed_new_execution.error_object.clone(),
+        )
+        .with_value_wei(tx_request.value_wei())])?);
+        self.tx_request_repo()
+            .set_status_for_many(
+                &execute_batch_context.get_tx_ids(),
+                TxStatus::FAILED,
+                &outbox_events,
+            )
+            .await?;
+
+        self.outbox_relay()
+            .relay(self.outcome_emitter(), &outbox_events)
+            .await?;
+
+        Ok(execution_attempt)
+    }
 
     async fn handle_error(
         &self,
@@ -30,8 +128,9 @@ pub trait ExecutionErrorHandler {
         error: anyhow::Error,
     ) -> anyhow::Result<ExecutionAttempt> {
         let error_string = error.to_string();
+        let target_abis = self.load_target_abis(execute_batch_context).await?;
         if let Some(failed_new_execution) =
-            build_failed_new_execution(execute_batch_context, wallet, error)?
+            build_failed_new_execution(execute_batch_context, wallet, error, &target_abis)?
         {
             let execution_attempt = self
                 .execution_attempt_repo()
@@ -44,36 +143,72 @@ pub trait ExecutionErrorHandler {
 
             if let Some(retryable) = failed_new_execution.retryable.clone() {
                 if retryable == true {
+                    let outbox_events = to_outbox_events(&LifecycleEvent::for_tx_requests(
+                        &execute_batch_context.tx_requests,
+                        TxStatus::RETRIED,
+                        failed_new_execution.tx_hash.clone(),
+                        Some(wallet.db_record.wallet_address.clone()),
+                    ))?;
                     self.tx_request_repo()
-                        .set_status_for_many(&execute_batch_context.get_tx_ids(), TxStatus::RETRIED)
+                        .set_status_for_many(
+                            &execute_batch_context.get_tx_ids(),
+                            TxStatus::RETRIED,
+                            &outbox_events,
+                        )
+                        .await?;
+
+                    self.outbox_relay()
+                        .relay(self.outcome_emitter(), &outbox_events)
                         .await?;
+
                     let message_body = &RetryQueueMessageBody {
                         execution_attempt_id: execution_attempt.id.to_string(),
+                        cancel_nonce: false,
+                        gap_nonce: None,
                     };
                     let message_body_string = message_body.to_json_string()?;
                     self.retry_queue().send_new(&message_body_string).await?;
                 } else {
+                    let outcome = failed_new_execution
+                        .outcome
+                        .clone()
+                        .unwrap_or(TxExecutionOutcome::FAILED);
+                    let outcome_events: Vec<OutcomeEvent> = execute_batch_context
+                        .tx_requests
+                        .iter()
+                        .map(|tx_request| {
+                            OutcomeEvent::new(
+                                outcome.clone(),
+                                tx_request.tx_id.clone(),
+                                tx_request.requester_id.clone(),
+                                tx_request.metadata.clone(),
+                                failed_new_execution.error_object.clone(),
+                            )
+                            .with_value_wei(tx_request.value_wei())
+                            .with_transaction(
+                                failed_new_execution.used_gas,
+                                failed_new_execution.tx_hash.clone(),
+                            )
+                        })
+                        .collect();
+                    let mut outbox_events = to_outbox_events(&LifecycleEvent::for_tx_requests(
+                        &execute_batch_context.tx_requests,
+                        TxStatus::FAILED,
+                        failed_new_execution.tx_hash.clone(),
+                        Some(wallet.db_record.wallet_address.clone()),
+                    ))?;
+                    outbox_events.extend(to_outbox_events(&outcome_events)?);
                     self.tx_request_repo()
-                        .set_status_for_many(&execute_batch_context.get_tx_ids(), TxStatus::FAILED)
+                        .set_status_for_many(
+                            &execute_batch_context.get_tx_ids(),
+                            TxStatus::FAILED,
+                            &outbox_events,
+                        )
                         .await?;
 
-                    for tx_request in execute_batch_context.tx_requests.clone() {
-                        let outcome = failed_new_execution
-                            .outcome
-                            .clone()
-                            .unwrap_or(TxExecutionOutcome::FAILED);
-
-                        self.outcome_emitter()
-                            .emit_outcome(&OutcomeEvent {
-                                outcome,
-                                tx_request_id: tx_request.tx_id,
-                                gas_fee: failed_new_execution.used_gas,
-                                transaction_hash: failed_new_execution.tx_hash.clone(),
-                                error: failed_new_execution.error_object.clone(),
-                                metadata: tx_request.metadata,
-                            })
-                            .await?;
-                    }
+                    self.outbox_relay()
+                        .relay(self.outcome_emitter(), &outbox_events)
+                        .await?;
                 }
             }
             return Ok(execution_attempt);
@@ -88,10 +223,12 @@ pub trait ExecutionErrorHandler {
     }
 }
 
+/// `target_abis` are used to decode custom errors in the revert reason of `ExecutionFailed`.
 pub fn build_failed_new_execution(
     execute_batch_context: &ExecuteBatchTxContext,
     wallet: &Wallet,
     error: anyhow::Error,
+    target_abis: &[JsonAbi],
 ) -> anyhow::Result<Option<NewExecutionAttempt>> {
     let mut failed_new_execution = NewExecutionAttempt::default_standard(
         execute_batch_context.chain_id,
@@ -110,6 +247,7 @@ pub fn build_failed_new_execution(
                             ExecutionErrorObject {
                                 error_type: "Expired".to_string(),
                                 error_body: None,
+                                revert_reason: None,
                             },
                             false,
                         )
@@ -122,12 +260,13 @@ pub fn build_failed_new_execution(
                             ExecutionErrorObject {
                                 error_type: "InvalidSignature".to_string(),
                                 error_body: Some(execute_batch_context.to_json_string()?),
+                                revert_reason: None,
                             },
                             false,
                         )
                         .expect("error parsing failed");
                     }
-                    SEOA::SEOAErrors::ExecutionFailed(_) => {
+                    SEOA::SEOAErrors::ExecutionFailed(execution_failed) => {
                         let batch_size = execute_batch_context.tx_requests.len();
                         let retryable = if batch_size > 1
                             && execute_batch_context.use_operator_wallet_id.is_none()
@@ -142,6 +281,10 @@ pub fn build_failed_new_execution(
                             ExecutionErrorObject {
                                 error_type: "ExecutionFailed".to_string(),
                                 error_body: Some(execute_batch_context.to_json_string()?),
+                                revert_reason: Some(decode_revert_reason(
+                                    &execution_failed.reason,
+                                    target_abis,
+                                )),
                             },
                             retryable,
                         )
@@ -160,6 +303,7 @@ pub fn build_failed_new_execution(
                             ExecutionErrorObject {
                                 error_type: "Unknown".to_string(),
                                 error_body: Some(execute_batch_context.to_json_string()?),
+                                revert_reason: None,
                             },
                             false,
                         )
@@ -173,6 +317,7 @@ pub fn build_failed_new_execution(
                         ExecutionErrorObject {
                             error_type: "Generic alloy error".to_string(),
                             error_body: Some(encoded_error.to_string()),
+                            revert_reason: None,
                         },
                         false,
                     )
@@ -187,6 +332,7 @@ pub fn build_failed_new_execution(
                 ExecutionErrorObject {
                     error_type: "Generic error".to_string(),
                     error_body: Some(generic_error.to_string()),
+                    revert_reason: None,
                 },
                 false,
             )
@@ -209,11 +355,19 @@ impl ExecutionErrorHandler for AwsLambdaOrchestrator {
         &self.tx_request_repo
     }
 
+    fn target_abi_repo(&self) -> &TargetAbiRepo {
+        &self.target_abi_repo
+    }
+
     fn retry_queue(&self) -> &SqsQueue {
         &self.retry_queue
     }
 
-    fn outcome_emitter(&self) -> &AwsEventBridgeOutcomeEmitter {
+    fn outcome_emitter(&self) -> &impl OutcomeEmitter {
         &self.outcome_emitter
     }
+
+    fn outbox_relay(&self) -> &OutboxRelay {
+        &self.outbox_relay
+    }
 }
@@ -9,6 +9,7 @@ pub trait ExecutionAttemptFromStandardSuccessful {
     fn standard_successful(
         tx_context: &ExecuteBatchTxContext,
         operator_wallet_id: Uuid,
+        lease_owner: Option<String>,
         source_execution_attempt_id: Option<Uuid>,
     ) -> anyhow::Result<NewExecutionAttempt>;
 }
@@ -17,6 +18,7 @@ impl ExecutionAttemptFromStandardSuccessful for NewExecutionAttempt {
     fn standard_successful(
         tx_context: &ExecuteBatchTxContext,
         operator_wallet_id: Uuid,
+        lease_owner: Option<String>,
         source_execution_attempt_id: Option<Uuid>,
     ) -> anyhow::Result<Self> {
         let Some(fees) = tx_context.fees else {
@@ -44,6 +46,7 @@ impl ExecutionAttemptFromStandardSuccessful for NewExecutionAttempt {
             error_object: None,
             retryable: None,
             source_execution_attempt_id,
+            lease_owner,
         })
     }
 }
@@ -87,6 +90,7 @@ impl ExecutionAttemptFromStandardFailed for NewExecutionAttempt {
                 error_object: Some(error_object.to_json_string()?),
                 retryable: Some(retryable),
                 source_execution_attempt_id: None,
+                lease_owner: None,
             });
         } else {
             return Ok(NewExecutionAttempt {
@@ -105,6 +109,7 @@ impl ExecutionAttemptFromStandardFailed for NewExecutionAttempt {
                 error_object: Some(error_object.to_json_string()?),
                 retryable: Some(retryable),
                 source_execution_attempt_id: None,
+                lease_owner: None,
             });
         }
     }
@@ -5,6 +5,7 @@ pub mod execution_attempt;
 pub mod orchestrator;
 pub mod transaction;
 
+use outcome_emitter::schema::OutcomeSchemaVersion;
 use std::env;
 
 pub struct Config {
@@ -14,6 +15,7 @@ pub struct Config {
     pub retry_queue_message_group_id: String,
     pub retry_queue_url: String,
     pub outcome_event_bus_name: String,
+    pub outcome_schema_version: OutcomeSchemaVersion,
 }
 
 impl Config {
@@ -25,6 +27,7 @@ impl Config {
         let retry_queue_message_group_id = Self::get_env_var("RETRY_QUEUE_MESSAGE_GROUP_ID");
         let retry_queue_url = Self::get_env_var("RETRY_QUEUE_URL");
         let outcome_event_bus_name = Self::get_env_var("OUTCOME_EVENT_BUS_NAME");
+        let outcome_schema_version = OutcomeSchemaVersion::from_env()?;
 
         Ok(Self {
             database_url,
@@ -33,6 +36,7 @@ impl Config {
             retry_queue_message_group_id,
             retry_queue_url,
             outcome_event_bus_name,
+            outcome_schema_version,
         })
     }
 
@@ -1,10 +1,16 @@
 #![cfg(feature = "aws")]
 
 use crate::{
-    Config, error::ExecutionErrorHandler,
-    execution_attempt::ExecutionAttemptFromStandardSuccessful, transaction::TxContextBuilder,
+    Config,
+    error::ExecutionErrorHandler,
+    execution_attempt::ExecutionAttemptFromStandardSuccessful,
+    transaction::{
+        TxContextBuilder, build_execute_batch_context, isolate_failing_items, pack_by_gas,
+        simulate_items,
+    },
 };
 use aws_lambda_events::sqs::{SqsBatchResponse, SqsEvent};
+use db_types::TxStatus;
 use execution_attempt_db::execution_attempts::{
     ExecutionAttempt, ExecutionAttemptRepo, NewExecutionAttempt,
 };
@@ -12,18 +18,29 @@ use execution_attempt_item_db::execution_attempt_items::ExecutionAttemptItemRepo
 use lambda_runtime::{LambdaEvent, tracing};
 use network_db::networks::NetworkRepo;
 use operator_wallet_db::operator_wallets::OperatorWalletRepo;
-use outcome_emitter::emitter::event_bridge::AwsEventBridgeOutcomeEmitter;
+use outbox_db::outbox_events::OutboxEventRepo;
+use outcome_emitter::{
+    emitter::{
+        event_bridge::AwsEventBridgeOutcomeEmitter, routing::RoutingOutcomeEmitter,
+        webhook::WebhookOutcomeEmitter,
+    },
+    lifecycle::LifecycleEvent,
+    outbox::{OutboxRelay, to_outbox_events},
+};
 use receipt_poller_queue::ReceiptPollerQueueMessageBody;
 use seoa_contract::{contract::ContractManager, transaction::ExecuteBatchTxContext};
 use sqs_queue::{message_body::ToJsonString, queue::SqsQueue};
 use standard_sender_queue::StandardSenderQueueEvent;
+use target_abi_db::target_abis::TargetAbiRepo;
 use tx_request_db::repo::TxRequestRepo;
 use wallet_assignment_db::wallet_assignments::WalletAssignmentRepo;
 use wallet_pool::{manager::WalletPoolManager, wallet::Wallet};
+use webhook_db::{webhook_deliveries::WebhookDeliveryRepo, webhook_endpoints::WebhookEndpointRepo};
 
 pub struct AwsLambdaOrchestrator {
     pub wallet_assignment_repo: WalletAssignmentRepo,
     pub tx_request_repo: TxRequestRepo,
+    pub target_abi_repo: TargetAbiRepo,
     pub execution_attempt_repo: ExecutionAttemptRepo,
     pub execution_attempt_item_repo: ExecutionAttemptItemRepo,
     pub wallet_pool_manager: WalletPoolManager,
@@ -31,7 +48,8 @@ pub struct AwsLambdaOrchestrator {
     pub contract_manager: ContractManager,
     pub receipt_poller_queue: SqsQueue,
     pub retry_queue: SqsQueue,
-    pub outcome_emitter: AwsEventBridgeOutcomeEmitter,
+    pub outcome_emitter: RoutingOutcomeEmitter,
+    pub outbox_relay: OutboxRelay,
 }
 
 impl AwsLambdaOrchestrator {
@@ -47,12 +65,13 @@ impl AwsLambdaOrchestrator {
         let operator_wallet_repo = OperatorWalletRepo::new(pool.clone());
         let network_repo = NetworkRepo::new(pool.clone());
         let tx_request_repo = TxRequestRepo::new(pool.clone());
+        let target_abi_repo = TargetAbiRepo::new(pool.clone());
         let execution_attempt_repo = ExecutionAttemptRepo::new(pool.clone());
         let execution_attempt_item_repo = ExecutionAttemptItemRepo::new(pool.clone());
         let networks = network_repo.select_all().await?;
 
         let wallet_pool_manager = WalletPoolManager::build(operator_wallet_repo.clone(), &networks);
-        let tx_context_builder = TxContextBuilder::build(&tx_request_repo);
+        let tx_context_builder = TxContextBuilder::build(&tx_request_repo, &networks)?;
         let contract_manager = ContractManager::build(&networks).await?;
         let sqs_client = aws_sdk_sqs::Client::new(&aws_config);
         let receipt_poller_queue = SqsQueue::build(
@@ -61,10 +80,19 @@ impl AwsLambdaOrchestrator {
             &config.receipt_poller_queue_message_group_id,
         )?;
         let event_bridge_client = aws_sdk_eventbridge::Client::new(&aws_config);
-        let outcome_emitter = AwsEventBridgeOutcomeEmitter::build(
-            &event_bridge_client,
-            config.outcome_event_bus_name,
+        let outcome_emitter = RoutingOutcomeEmitter::build(
+            AwsEventBridgeOutcomeEmitter::build(
+                &event_bridge_client,
+                config.outcome_event_bus_name,
+                config.outcome_schema_version,
+            ),
+            WebhookOutcomeEmitter::build(
+                WebhookEndpointRepo::new(pool.clone()),
+                WebhookDeliveryRepo::new(pool.clone()),
+                config.outcome_schema_version,
+            )?,
         );
+        let outbox_relay = OutboxRelay::new(OutboxEventRepo::new(pool.clone()));
 
         let retry_queue = SqsQueue::build(
             &sqs_client,
@@ -74,6 +102,7 @@ impl AwsLambdaOrchestrator {
         Ok(Self {
             wallet_assignment_repo,
             tx_request_repo,
+            target_abi_repo,
             execution_attempt_repo,
             execution_attempt_item_repo,
             wallet_pool_manager,
@@ -82,6 +111,7 @@ impl AwsLambdaOrchestrator {
             receipt_poller_queue,
             retry_queue,
             outcome_emitter,
+            outbox_relay,
         })
     }
 
@@ -105,76 +135,233 @@ impl AwsLambdaOrchestrator {
             .tx_context_builder
             .fetch_and_sort_into_batches(&tx_ids)
             .await?;
+        // Requests the builder turned away as INVALID left their events pending
+        self.outbox_relay
+            .relay_for_tx_ids(&self.outcome_emitter, &tx_ids)
+            .await?;
 
         tracing::info!("Executing...");
-        for mut execute_batch_context in execute_batch_context_vec {
-            let Some(mut wallet) = self
-                .wallet_pool_manager
-                .acquire(
-                    execute_batch_context.chain_id,
-                    execute_batch_context.use_operator_wallet_id,
+        for execute_batch_context in execute_batch_context_vec {
+            let Some(wallet) = self.acquire_wallet(&execute_batch_context).await? else {
+                self.release_unsent(
+                    &execute_batch_context,
+                    &tx_sender_queue_event,
+                    &mut sqs_batch_response,
                 )
+                .await?;
+                continue;
+            };
+
+            let mut packed_batch_contexts = self
+                .preflight_items(execute_batch_context, &wallet)
                 .await?
-            else {
-                self.tx_request_repo
-                    .release_many(&execute_batch_context.get_tx_ids())
+                .into_iter();
+
+            let Some(execute_batch_context) = packed_batch_contexts.next() else {
+                self.wallet_pool_manager
+                    .release_unused(wallet.db_record.id)
                     .await?;
-                execute_batch_context.get_tx_ids().iter().for_each(|tx_id| {
-                    if let Some(message_id) = tx_sender_queue_event.tx_id_to_message_id.get(tx_id) {
-                        sqs_batch_response.add_failure(message_id);
-                    };
-                });
                 continue;
             };
-
-            let wallet_assignment_ids = self
-                .wallet_assignment_repo
-                .new_assignments(&execute_batch_context.get_tx_ids(), wallet.db_record.id)
+            self.send_execute_batch(execute_batch_context, wallet)
                 .await?;
 
-            match self
-                .contract_manager
-                .simulate_send_batch_tx(&mut execute_batch_context, &mut wallet)
-                .await
-            {
-                Ok(_) => {}
-                Err(err) => {
-                    tracing::error!("{err:?}");
-                    self.wallet_pool_manager
-                        .release_unused(wallet.db_record.id)
-                        .await?;
-                    self.handle_error(&execute_batch_context, &wallet, err)
-                        .await?;
+            // Batches split off by the gas limit need wallets of their own
+            for execute_batch_context in packed_batch_contexts {
+                let Some(wallet) = self.acquire_wallet(&execute_batch_context).await? else {
+                    self.release_unsent(
+                        &execute_batch_context,
+                        &tx_sender_queue_event,
+                        &mut sqs_batch_response,
+                    )
+                    .await?;
                     continue;
-                }
+                };
+                self.send_execute_batch(execute_batch_context, wallet)
+                    .await?;
+            }
+        }
+
+        Ok(sqs_batch_response)
+    }
+
+    async fn acquire_wallet(
+        &self,
+        execute_batch_context: &ExecuteBatchTxContext,
+    ) -> anyhow::Result<Option<Wallet>> {
+        self.wallet_pool_manager
+            .acquire(
+                execute_batch_context.chain_id,
+                execute_batch_context.use_operator_wallet_id,
+                execute_batch_context.get_requester_id(),
+            )
+            .await
+    }
+
+    /// Unlocks the requests and reports their messages as failed, so SQS redelivers them.
+    async fn release_unsent(
+        &self,
+        execute_batch_context: &ExecuteBatchTxContext,
+        tx_sender_queue_event: &StandardSenderQueueEvent,
+        sqs_batch_response: &mut SqsBatchResponse,
+    ) -> anyhow::Result<()> {
+        self.tx_request_repo
+            .release_many(&execute_batch_context.get_tx_ids())
+            .await?;
+        execute_batch_context.get_tx_ids().iter().for_each(|tx_id| {
+            if let Some(message_id) = tx_sender_queue_event.tx_id_to_message_id.get(tx_id) {
+                sqs_batch_response.add_failure(message_id);
             };
+        });
 
-            match self
-                .contract_manager
-                .send_batch(&mut execute_batch_context, &wallet)
-                .await
-            {
-                Ok(_) => {
-                    let execution_attempt = self
-                        .save_successful_execution(&execute_batch_context, &wallet)
-                        .await?;
-
-                    self.send_receipt_poller_queue_message(
-                        &execute_batch_context,
-                        &execution_attempt.id.to_string(),
-                    )
+        Ok(())
+    }
+
+    async fn send_execute_batch(
+        &self,
+        mut execute_batch_context: ExecuteBatchTxContext,
+        mut wallet: Wallet,
+    ) -> anyhow::Result<()> {
+        self.wallet_assignment_repo
+            .new_assignments(&execute_batch_context.get_tx_ids(), wallet.db_record.id)
+            .await?;
+
+        match self
+            .contract_manager
+            .simulate_send_batch_tx(&mut execute_batch_context, &mut wallet)
+            .await
+        {
+            Ok(_) => {}
+            Err(err) => {
+                tracing::error!("{err:?}");
+                self.wallet_pool_manager
+                    .release_unused(wallet.db_record.id)
                     .await?;
-                }
+                self.handle_error(&execute_batch_context, &wallet, err)
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        match self
+            .contract_manager
+            .send_batch(&mut execute_batch_context, &wallet)
+            .await
+        {
+            Ok(_) => {
+                let execution_attempt = self
+                    .save_successful_execution(&execute_batch_context, &wallet)
+                    .await?;
+                self.wallet_pool_manager
+                    .release_sent(&wallet, execute_batch_context.assigned_nonce)
+                    .await?;
+
+                self.send_receipt_poller_queue_message(
+                    &execute_batch_context,
+                    &execution_attempt.id.to_string(),
+                )
+                .await?;
+            }
 
-                Err(err) => {
-                    tracing::error!("{err:?}");
-                    self.handle_error(&execute_batch_context, &wallet, err)
-                        .await?;
+            Err(err) => {
+                tracing::error!("{err:?}");
+                self.wallet_pool_manager
+                    .release_unused(wallet.db_record.id)
+                    .await?;
+                self.handle_error(&execute_batch_context, &wallet, err)
+                    .await?;
+            }
+        };
+
+        Ok(())
+    }
+
+    /// Simulates each item on its own and fails the ones that revert or whose salt is already
+    /// used, so one broken request does not fail the simulation of the whole batch. The items
+    /// that passed are packed into batches within the network's gas limit. When the items could
+    /// not be simulated, their gas is unknown, so a network with a gas limit gets one item per
+    /// batch and any other network gets the batch unchanged.
+    async fn preflight_items(
+        &self,
+        execute_batch_context: ExecuteBatchTxContext,
+        wallet: &Wallet,
+    ) -> anyhow::Result<Vec<ExecuteBatchTxContext>> {
+        let target_abis = self.load_target_abis(&execute_batch_context).await?;
+        let item_simulations = match simulate_items(
+            &self.contract_manager,
+            &execute_batch_context,
+            wallet,
+            &target_abis,
+        )
+        .await
+        {
+            Ok(item_simulations) => item_simulations,
+            Err(err) => {
+                tracing::warn!("Skipping per-item simulation: {err:?}");
+                if !self.has_batch_gas_limit(execute_batch_context.chain_id) {
+                    return Ok(vec![execute_batch_context]);
                 }
-            };
+                return execute_batch_context
+                    .tx_requests
+                    .iter()
+                    .map(|tx_request| {
+                        build_execute_batch_context(
+                            execute_batch_context.chain_id,
+                            execute_batch_context.use_operator_wallet_id,
+                            std::slice::from_ref(tx_request),
+                        )
+                    })
+                    .collect();
+            }
+        };
+
+        let isolated_items =
+            isolate_failing_items(&execute_batch_context.tx_requests, item_simulations)?;
+        let max_batch_gas = self.max_batch_gas(execute_batch_context.chain_id).await?;
+        if isolated_items.all_healthy() && max_batch_gas.is_none() {
+            return Ok(vec![execute_batch_context]);
         }
 
-        Ok(sqs_batch_response)
+        for (tx_request, error_object) in isolated_items.unsendable() {
+            self.fail_isolated_item(&tx_request, wallet, error_object)
+                .await?;
+        }
+
+        pack_by_gas(isolated_items.healthy, max_batch_gas.unwrap_or(u64::MAX))
+            .iter()
+            .map(|batch| {
+                build_execute_batch_context(
+                    execute_batch_context.chain_id,
+                    execute_batch_context.use_operator_wallet_id,
+                    batch,
+                )
+            })
+            .collect()
+    }
+
+    fn has_batch_gas_limit(&self, chain_id: i64) -> bool {
+        self.contract_manager
+            .networks_by_chain_id
+            .get(&chain_id)
+            .is_some_and(|network| network.max_batch_gas_ppm.is_some())
+    }
+
+    /// Gas a single batch may use on the chain, from `max_batch_gas_ppm` of the latest block's gas
+    /// limit.
+    async fn max_batch_gas(&self, chain_id: i64) -> anyhow::Result<Option<u64>> {
+        let Some(max_batch_gas_ppm) = self
+            .contract_manager
+            .networks_by_chain_id
+            .get(&chain_id)
+            .and_then(|network| network.max_batch_gas_ppm)
+        else {
+            return Ok(None);
+        };
+        let block_gas_limit = self.contract_manager.get_block_gas_limit(chain_id).await?;
+
+        Ok(Some(u64::try_from(
+            u128::from(block_gas_limit) * u128::try_from(max_batch_gas_ppm)? / 1_000_000,
+        )?))
     }
 
     pub async fn save_successful_execution(
@@ -185,6 +372,7 @@ impl AwsLambdaOrchestrator {
         let execution_attempt_input = NewExecutionAttempt::standard_successful(
             execute_batch_context,
             wallet.db_record.id,
+            wallet.db_record.lease_owner.clone(),
             None,
         )?;
         let execution_attempt = self
@@ -196,13 +384,24 @@ impl AwsLambdaOrchestrator {
             .insert_many(execution_attempt.id, &execute_batch_context.get_tx_ids())
             .await?;
 
+        let outbox_events = to_outbox_events(&LifecycleEvent::for_tx_requests(
+            &execute_batch_context.tx_requests,
+            TxStatus::BROADCASTED,
+            execution_attempt.tx_hash.clone(),
+            Some(wallet.db_record.wallet_address.clone()),
+        ))?;
         self.tx_request_repo
             .set_status_for_many(
                 &execute_batch_context.get_tx_ids(),
-                db_types::TxStatus::BROADCASTED,
+                TxStatus::BROADCASTED,
+                &outbox_events,
             )
             .await?;
 
+        self.outbox_relay
+            .relay(&self.outcome_emitter, &outbox_events)
+            .await?;
+
         Ok(execution_attempt)
     }
 
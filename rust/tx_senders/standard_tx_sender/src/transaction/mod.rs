@@ -0,0 +1,420 @@
+use alloy::json_abi::JsonAbi;
+use anyhow::{anyhow, bail};
+use db_types::{ExecutionErrorObject, TxStatus, Wei};
+use network_db::networks::Network;
+use outcome_emitter::{lifecycle::LifecycleEvent, outbox::to_outbox_events, outcome::OutcomeEvent};
+use seoa_contract::{
+    contract::{ContractManager, ItemSimulation, sEOA::ExecuteInput},
+    transaction::{ExecuteBatchTxContext, IntoExecuteInput},
+};
+use std::collections::HashMap;
+use tx_input_types::TxInput;
+use tx_request_db::{
+    repo::TxRequestRepo,
+    types::{IntoTxRequestWithInput, StandardTxRequestRaw, TxRequestWithInput},
+};
+use uuid::Uuid;
+use wallet_pool::wallet::Wallet;
+
+#[cfg(test)]
+mod tests;
+
+/// Packing limits of a network. `None` means no limit.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BatchLimits {
+    pub max_items: Option<usize>,
+    pub max_value: Option<Wei>,
+}
+
+impl BatchLimits {
+    pub fn from_network(network: &Network) -> anyhow::Result<Self> {
+        Ok(Self {
+            max_items: network.max_batch_items.map(usize::try_from).transpose()?,
+            max_value: network.max_batch_value_wei,
+        })
+    }
+}
+
+pub struct TxContextBuilder {
+    transaction_repo: TxRequestRepo,
+    batch_limits_by_chain_id: HashMap<i64, BatchLimits>,
+}
+
+impl TxContextBuilder {
+    pub fn build(transaction_repo: &TxRequestRepo, networks: &[Network]) -> anyhow::Result<Self> {
+        let mut batch_limits_by_chain_id = HashMap::new();
+        for network in networks {
+            batch_limits_by_chain_id.insert(network.chain_id, BatchLimits::from_network(network)?);
+        }
+
+        Ok(Self {
+            transaction_repo: transaction_repo.clone(),
+            batch_limits_by_chain_id,
+        })
+    }
+
+    pub async fn fetch_and_sort_into_batches(
+        &self,
+        tx_ids: &Vec<String>,
+    ) -> anyhow::Result<Vec<ExecuteBatchTxContext>> {
+        let fetched_txs = self
+            .transaction_repo
+            .select_and_lock_many_standard(tx_ids)
+            .await?;
+
+        let sorted = Self::group_by_chain_and_wallet(fetched_txs);
+
+        let mut batch_contexts = Vec::new();
+        for (chain_id, wallet_map) in sorted {
+            for (use_operator_wallet_id, transactions) in wallet_map {
+                batch_contexts.extend(
+                    self.build_batch_contexts(chain_id, use_operator_wallet_id, transactions)
+                        .await?,
+                );
+            }
+        }
+
+        Ok(batch_contexts)
+    }
+
+    /// Drops the requests that can't be sent and packs the rest into batches within the
+    /// network's limits.
+    async fn build_batch_contexts(
+        &self,
+        chain_id: i64,
+        use_operator_wallet_id: Option<Uuid>,
+        transactions: Vec<StandardTxRequestRaw>,
+    ) -> anyhow::Result<Vec<ExecuteBatchTxContext>> {
+        let batch_limits = self
+            .batch_limits_by_chain_id
+            .get(&chain_id)
+            .copied()
+            .unwrap_or_default();
+        let mut batch_tx_value = Wei::ZERO;
+        let mut tx_requests = Vec::new();
+
+        for transaction in transactions {
+            let next_batch_tx_value = if transaction.pass_value_from_operator_wallet {
+                batch_tx_value.checked_add(transaction.value_wei)
+            } else {
+                Some(batch_tx_value)
+            };
+
+            match (transaction.into_execute_input(), next_batch_tx_value) {
+                (Ok(_), Some(next_batch_tx_value)) => {
+                    batch_tx_value = next_batch_tx_value;
+                    tx_requests.push(transaction.into_tx_request_with_input()?);
+                }
+                // A value that would overflow the batch value can't be sent either
+                _ => {
+                    self.mark_as_invalid(
+                        &transaction.tx_id,
+                        &transaction.requester_id,
+                        transaction.attempts,
+                        &transaction.metadata,
+                        Some(transaction.value_wei),
+                        ExecutionErrorObject {
+                            error_type: "InvalidInput".to_string(),
+                            error_body: None,
+                            revert_reason: None,
+                        },
+                    )
+                    .await
+                    .ok();
+                }
+            }
+        }
+
+        let (batches, over_value_limit) = pack_into_batches(tx_requests, &batch_limits);
+        for tx_request in over_value_limit {
+            self.mark_as_invalid(
+                &tx_request.tx_id,
+                &tx_request.requester_id,
+                tx_request.attempts,
+                &tx_request.metadata,
+                tx_request.value_wei(),
+                ExecutionErrorObject {
+                    error_type: "ExceedsBatchValueLimit".to_string(),
+                    error_body: Some(format!(
+                        "value_wei is above the network's max_batch_value_wei of {:?}",
+                        batch_limits.max_value
+                    )),
+                    revert_reason: None,
+                },
+            )
+            .await
+            .ok();
+        }
+
+        batches
+            .iter()
+            .map(|batch| build_execute_batch_context(chain_id, use_operator_wallet_id, batch))
+            .collect()
+    }
+
+    /// The INVALID lifecycle and outcome events are published when the sender relays the
+    /// events of its requests.
+    async fn mark_as_invalid(
+        &self,
+        tx_id: &String,
+        requester_id: &str,
+        attempts: i32,
+        metadata: &Option<String>,
+        value_wei: Option<Wei>,
+        error_object: ExecutionErrorObject,
+    ) -> anyhow::Result<()> {
+        let mut outbox_events = to_outbox_events(&[LifecycleEvent::new(
+            TxStatus::INVALID,
+            tx_id.clone(),
+            requester_id.to_string(),
+            attempts,
+            metadata.clone(),
+        )])?;
+        outbox_events.extend(to_outbox_events(&[OutcomeEvent::invalid(
+            tx_id.clone(),
+            requester_id.to_string(),
+            metadata.clone(),
+            serde_json::to_string(&error_object)?,
+        )
+        .with_value_wei(value_wei)])?);
+        self.transaction_repo
+            .mark_as_invalid(tx_id, &outbox_events)
+            .await
+    }
+
+    fn group_by_chain_and_wallet(
+        transactions: Vec<StandardTxRequestRaw>,
+    ) -> HashMap<i64, HashMap<Option<Uuid>, Vec<StandardTxRequestRaw>>> {
+        let mut grouped: HashMap<i64, HashMap<Option<Uuid>, Vec<StandardTxRequestRaw>>> =
+            HashMap::new();
+
+        for tx in transactions {
+            grouped
+                .entry(tx.chain_id)
+                .or_default()
+                .entry(tx.use_operator_wallet_id)
+                .or_default()
+                .push(tx);
+        }
+
+        grouped
+    }
+}
+
+/// Value the operator wallet sends along for the request.
+fn passed_value(tx_request: &TxRequestWithInput) -> Wei {
+    match &tx_request.tx_input {
+        TxInput::Standard(standard_tx_input)
+            if standard_tx_input.pass_value_from_operator_wallet =>
+        {
+            standard_tx_input.value_wei
+        }
+        _ => Wei::ZERO,
+    }
+}
+
+/// Splits requests, in order, into batches within the item and value limits. Requests over the
+/// value limit on their own can't be sent on this network and are returned separately.
+pub fn pack_into_batches(
+    tx_requests: Vec<TxRequestWithInput>,
+    batch_limits: &BatchLimits,
+) -> (Vec<Vec<TxRequestWithInput>>, Vec<TxRequestWithInput>) {
+    let mut batches = Vec::new();
+    let mut over_value_limit = Vec::new();
+    let mut batch: Vec<TxRequestWithInput> = Vec::new();
+    let mut batch_value = Wei::ZERO;
+
+    for tx_request in tx_requests {
+        let value = passed_value(&tx_request);
+        if batch_limits
+            .max_value
+            .is_some_and(|max_value| value > max_value)
+        {
+            over_value_limit.push(tx_request);
+            continue;
+        }
+        let over_items = batch_limits
+            .max_items
+            .is_some_and(|max_items| batch.len() >= max_items);
+        let over_value = batch_limits
+            .max_value
+            .is_some_and(|max_value| batch_value.saturating_add(value) > max_value);
+
+        if !batch.is_empty() && (over_items || over_value) {
+            batches.push(std::mem::take(&mut batch));
+            batch_value = Wei::ZERO;
+        }
+        batch_value = batch_value.saturating_add(value);
+        batch.push(tx_request);
+    }
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+
+    (batches, over_value_limit)
+}
+
+/// Gas every transaction pays before execution. Per-item estimates include it once each, a
+/// batch pays it once.
+const INTRINSIC_GAS: u64 = 21_000;
+
+/// Splits simulated requests, in order, into batches whose estimated gas stays within `max_gas`.
+/// A request that needs more on its own goes out as a batch of one.
+pub fn pack_by_gas(
+    healthy_items: Vec<(TxRequestWithInput, u64)>,
+    max_gas: u64,
+) -> Vec<Vec<TxRequestWithInput>> {
+    let mut batches = Vec::new();
+    let mut batch = Vec::new();
+    let mut batch_gas = INTRINSIC_GAS;
+
+    for (tx_request, estimated_gas) in healthy_items {
+        let item_gas = estimated_gas.saturating_sub(INTRINSIC_GAS);
+        if !batch.is_empty() && batch_gas.saturating_add(item_gas) > max_gas {
+            batches.push(std::mem::take(&mut batch));
+            batch_gas = INTRINSIC_GAS;
+        }
+        batch_gas = batch_gas.saturating_add(item_gas);
+        batch.push(tx_request);
+    }
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+
+    batches
+}
+
+/// Fresh context for sending `tx_requests` as a new batch. Fees, nonce and gas are set by the
+/// simulation.
+pub fn build_execute_batch_context(
+    chain_id: i64,
+    use_operator_wallet_id: Option<Uuid>,
+    tx_requests: &[TxRequestWithInput],
+) -> anyhow::Result<ExecuteBatchTxContext> {
+    let mut batch_tx_value = Wei::ZERO;
+    for tx_request in tx_requests {
+        let TxInput::Standard(standard_tx_input) = &tx_request.tx_input else {
+            bail!("Can't build execute batch context for BLOB input");
+        };
+        if standard_tx_input.pass_value_from_operator_wallet {
+            batch_tx_value = batch_tx_value
+                .checked_add(standard_tx_input.value_wei)
+                .ok_or(anyhow!("Batch tx value overflows uint256"))?;
+        }
+    }
+
+    Ok(ExecuteBatchTxContext {
+        chain_id,
+        use_operator_wallet_id,
+        execute_batch_input: tx_requests
+            .iter()
+            .map(|tx_request| tx_request.into_execute_input())
+            .collect::<anyhow::Result<Vec<ExecuteInput>>>()?,
+        batch_tx_value,
+        tx_requests: tx_requests.to_vec(),
+        successfully_simulated: false,
+        assigned_nonce: None,
+        fees: None,
+        gas_limit: None,
+        tx_hash: None,
+    })
+}
+
+/// Simulates every item of the batch on its own, in batch order.
+pub async fn simulate_items(
+    contract_manager: &ContractManager,
+    execute_batch_context: &ExecuteBatchTxContext,
+    wallet: &Wallet,
+    target_abis: &[JsonAbi],
+) -> anyhow::Result<Vec<ItemSimulation>> {
+    let mut item_simulations = Vec::new();
+    for execute_input in &execute_batch_context.execute_batch_input {
+        item_simulations.push(
+            contract_manager
+                .simulate_execute(
+                    execute_batch_context.chain_id,
+                    execute_input,
+                    wallet,
+                    target_abis,
+                )
+                .await?,
+        );
+    }
+
+    Ok(item_simulations)
+}
+
+/// Error of an item whose salt is already used on chain. The request went out in an earlier
+/// transaction, so sending it again can only revert.
+pub fn already_used_error_object() -> ExecutionErrorObject {
+    ExecutionErrorObject {
+        error_type: "AlreadyUsed".to_string(),
+        error_body: Some("The salt of the request is already used on chain".to_string()),
+        revert_reason: None,
+    }
+}
+
+/// Items of a batch sorted by how their own simulation went.
+#[derive(Debug, Default)]
+pub struct IsolatedItems {
+    /// Items that passed, with their estimated gas.
+    pub healthy: Vec<(TxRequestWithInput, u64)>,
+    pub failing: Vec<(TxRequestWithInput, ExecutionErrorObject)>,
+    pub already_used: Vec<TxRequestWithInput>,
+}
+
+impl IsolatedItems {
+    pub fn all_healthy(&self) -> bool {
+        self.failing.is_empty() && self.already_used.is_empty()
+    }
+
+    /// Items to fail instead of sending, with the error of each: the ones that reverted and the
+    /// ones whose salt is already used.
+    pub fn unsendable(&self) -> Vec<(TxRequestWithInput, ExecutionErrorObject)> {
+        self.failing
+            .iter()
+            .cloned()
+            .chain(
+                self.already_used
+                    .iter()
+                    .map(|tx_request| (tx_request.clone(), already_used_error_object())),
+            )
+            .collect()
+    }
+
+    pub fn healthy_tx_requests(&self) -> Vec<TxRequestWithInput> {
+        self.healthy
+            .iter()
+            .map(|(tx_request, _)| tx_request.clone())
+            .collect()
+    }
+}
+
+/// Pairs each tx_request with the simulation of its `execute` call, in the same order.
+pub fn isolate_failing_items(
+    tx_requests: &[TxRequestWithInput],
+    item_simulations: Vec<ItemSimulation>,
+) -> anyhow::Result<IsolatedItems> {
+    if tx_requests.len() != item_simulations.len() {
+        bail!(
+            "Got {} simulations for {} tx requests",
+            item_simulations.len(),
+            tx_requests.len()
+        );
+    }
+
+    let mut isolated_items = IsolatedItems::default();
+    for (tx_request, item_simulation) in tx_requests.iter().zip(item_simulations) {
+        match item_simulation {
+            ItemSimulation::Passed { estimated_gas } => isolated_items
+                .healthy
+                .push((tx_request.clone(), estimated_gas)),
+            ItemSimulation::AlreadyUsed => isolated_items.already_used.push(tx_request.clone()),
+            ItemSimulation::Reverted(error_object) => isolated_items
+                .failing
+                .push((tx_request.clone(), error_object)),
+        }
+    }
+
+    Ok(isolated_items)
+}
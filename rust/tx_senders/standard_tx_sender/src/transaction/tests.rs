@@ -0,0 +1,236 @@
+#[cfg(test)]
+mod tests {
+    use crate::transaction::{
+        BatchLimits, already_used_error_object, build_execute_batch_context, isolate_failing_items,
+        pack_by_gas, pack_into_batches,
+    };
+    use alloy::primitives::U256;
+    use db_types::{ExecutionErrorObject, TxStatus, TxType, Wei};
+    use seoa_contract::contract::ItemSimulation;
+    use sqlx::types::time::OffsetDateTime;
+    use standard_tx_input_db::standard_tx_inputs::StandardTxInput;
+    use tx_input_types::TxInput;
+    use tx_request_db::types::TxRequestWithInput;
+
+    fn tx_request(tx_id: &str, value_wei: u64) -> TxRequestWithInput {
+        TxRequestWithInput {
+            tx_id: tx_id.to_string(),
+            requester_id: "requester".to_string(),
+            tx_type: TxType::STANDARD,
+            tx_status: TxStatus::LOCKED,
+            tx_input: TxInput::Standard(StandardTxInput {
+                tx_id: tx_id.to_string(),
+                signature: vec![0; 65],
+                calldata: vec![],
+                to_address: "0x2222222222222222222222222222222222222222".to_string(),
+                value_wei: Wei(U256::from(value_wei)),
+                deadline_timestamp: 0,
+                pass_value_from_operator_wallet: true,
+                created_at: OffsetDateTime::UNIX_EPOCH,
+            }),
+            attempts: 1,
+            metadata: None,
+            use_operator_wallet_id: None,
+        }
+    }
+
+    fn reverted(error_type: &str) -> ItemSimulation {
+        ItemSimulation::Reverted(ExecutionErrorObject {
+            error_type: error_type.to_string(),
+            error_body: None,
+            revert_reason: None,
+        })
+    }
+
+    fn passed(estimated_gas: u64) -> ItemSimulation {
+        ItemSimulation::Passed { estimated_gas }
+    }
+
+    fn tx_ids(tx_requests: &[TxRequestWithInput]) -> Vec<String> {
+        tx_requests
+            .iter()
+            .map(|tx_request| tx_request.tx_id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn isolates_reverted_and_already_used_items() {
+        let tx_requests = vec![
+            tx_request("tx-1", 1),
+            tx_request("tx-2", 2),
+            tx_request("tx-3", 3),
+            tx_request("tx-4", 4),
+        ];
+
+        let isolated_items = isolate_failing_items(
+            &tx_requests,
+            vec![
+                passed(50_000),
+                reverted("ExecutionFailed"),
+                ItemSimulation::AlreadyUsed,
+                passed(60_000),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            tx_ids(&isolated_items.healthy_tx_requests()),
+            vec!["tx-1", "tx-4"]
+        );
+        assert_eq!(isolated_items.healthy[1].1, 60_000);
+        assert_eq!(tx_ids(&isolated_items.already_used), vec!["tx-3"]);
+        assert_eq!(isolated_items.failing.len(), 1);
+        assert_eq!(isolated_items.failing[0].0.tx_id, "tx-2");
+        assert_eq!(isolated_items.failing[0].1.error_type, "ExecutionFailed");
+
+        let tx_context =
+            build_execute_batch_context(1, None, &isolated_items.healthy_tx_requests()).unwrap();
+        assert_eq!(tx_context.execute_batch_input.len(), 2);
+        assert_eq!(tx_context.batch_tx_value, Wei(U256::from(5)));
+    }
+
+    #[test]
+    fn already_used_items_are_failed_with_their_own_error() {
+        let isolated_items = isolate_failing_items(
+            &[
+                tx_request("tx-1", 1),
+                tx_request("tx-2", 2),
+                tx_request("tx-3", 3),
+            ],
+            vec![
+                ItemSimulation::AlreadyUsed,
+                passed(50_000),
+                reverted("ExecutionFailed"),
+            ],
+        )
+        .unwrap();
+
+        let unsendable = isolated_items.unsendable();
+
+        assert!(!isolated_items.all_healthy());
+        assert_eq!(unsendable.len(), 2);
+        assert_eq!(unsendable[0].0.tx_id, "tx-3");
+        assert_eq!(unsendable[0].1.error_type, "ExecutionFailed");
+        assert_eq!(unsendable[1].0.tx_id, "tx-1");
+        assert_eq!(
+            unsendable[1].1.error_type,
+            already_used_error_object().error_type
+        );
+    }
+
+    #[test]
+    fn fails_if_simulations_do_not_match_tx_requests() {
+        let result = isolate_failing_items(
+            &[tx_request("tx-1", 1), tx_request("tx-2", 2)],
+            vec![passed(50_000)],
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn batch_value_only_counts_values_passed_from_operator_wallet() {
+        let mut tx_request_without_value = tx_request("tx-2", 7);
+        if let TxInput::Standard(standard_tx_input) = &mut tx_request_without_value.tx_input {
+            standard_tx_input.pass_value_from_operator_wallet = false;
+        }
+
+        let tx_context = build_execute_batch_context(
+            1,
+            None,
+            &[tx_request("tx-1", 3), tx_request_without_value],
+        )
+        .unwrap();
+
+        assert_eq!(tx_context.batch_tx_value, Wei(U256::from(3)));
+        assert_eq!(tx_context.execute_batch_input[1].value, U256::from(7));
+    }
+
+    #[test]
+    fn packs_batches_within_item_and_value_limits() {
+        let tx_requests = vec![
+            tx_request("tx-1", 4),
+            tx_request("tx-2", 4),
+            tx_request("tx-3", 4),
+            tx_request("tx-4", 10),
+            tx_request("tx-5", 1),
+            tx_request("tx-6", 1),
+            tx_request("tx-7", 1),
+        ];
+        let batch_limits = BatchLimits {
+            max_items: Some(2),
+            max_value: Some(Wei(U256::from(10))),
+        };
+
+        let (batches, over_value_limit) = pack_into_batches(tx_requests, &batch_limits);
+        let batches: Vec<Vec<String>> = batches.iter().map(|batch| tx_ids(batch)).collect();
+
+        assert_eq!(
+            batches,
+            vec![
+                vec!["tx-1", "tx-2"],
+                vec!["tx-3"],
+                vec!["tx-4"],
+                vec!["tx-5", "tx-6"],
+                vec!["tx-7"],
+            ]
+        );
+        assert!(over_value_limit.is_empty());
+    }
+
+    #[test]
+    fn sets_aside_requests_over_the_value_limit_on_their_own() {
+        let mut tx_request_without_value = tx_request("tx-4", 50);
+        if let TxInput::Standard(standard_tx_input) = &mut tx_request_without_value.tx_input {
+            standard_tx_input.pass_value_from_operator_wallet = false;
+        }
+        let tx_requests = vec![
+            tx_request("tx-1", 4),
+            tx_request("tx-2", 20),
+            tx_request("tx-3", 4),
+            tx_request_without_value,
+        ];
+        let batch_limits = BatchLimits {
+            max_items: None,
+            max_value: Some(Wei(U256::from(10))),
+        };
+
+        let (batches, over_value_limit) = pack_into_batches(tx_requests, &batch_limits);
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(tx_ids(&batches[0]), vec!["tx-1", "tx-3", "tx-4"]);
+        assert_eq!(tx_ids(&over_value_limit), vec!["tx-2"]);
+    }
+
+    #[test]
+    fn keeps_one_batch_without_limits() {
+        let tx_requests = vec![tx_request("tx-1", 1), tx_request("tx-2", 2)];
+
+        let (batches, over_value_limit) = pack_into_batches(tx_requests, &BatchLimits::default());
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(tx_ids(&batches[0]), vec!["tx-1", "tx-2"]);
+        assert!(over_value_limit.is_empty());
+    }
+
+    #[test]
+    fn packs_batches_within_gas_limit() {
+        let healthy_items = vec![
+            (tx_request("tx-1", 1), 71_000),
+            (tx_request("tx-2", 1), 71_000),
+            (tx_request("tx-3", 1), 71_000),
+            (tx_request("tx-4", 1), 500_000),
+        ];
+
+        let batches: Vec<Vec<String>> = pack_by_gas(healthy_items, 130_000)
+            .iter()
+            .map(|batch| tx_ids(batch))
+            .collect();
+
+        // 21_000 + 2 * 50_000 fits, a third item does not
+        assert_eq!(
+            batches,
+            vec![vec!["tx-1", "tx-2"], vec!["tx-3"], vec!["tx-4"]]
+        );
+    }
+}
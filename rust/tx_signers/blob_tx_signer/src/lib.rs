@@ -1,5 +1,6 @@
 pub mod signature;
 
+use outcome_emitter::schema::OutcomeSchemaVersion;
 use signer_wallet::IntoSignerWalletConfig;
 use std::env;
 
@@ -20,6 +21,8 @@ pub struct Config {
     pub blob_storage_bucket_name: String,
     pub blob_sender_queue_message_group_id: String,
     pub sender_blob_queue_url: String,
+    pub outcome_event_bus_name: String,
+    pub outcome_schema_version: OutcomeSchemaVersion,
 }
 
 impl Config {
@@ -28,6 +31,8 @@ impl Config {
             Self::get_env_var("BLOB_SENDER_QUEUE_MESSAGE_GROUP_ID");
         let sender_blob_queue_url = Self::get_env_var("BLOB_SENDER_QUEUE_URL");
         let blob_storage_bucket_name = Self::get_env_var("BLOB_STORAGE_BUCKET_NAME");
+        let outcome_event_bus_name = Self::get_env_var("OUTCOME_EVENT_BUS_NAME");
+        let outcome_schema_version = OutcomeSchemaVersion::from_env()?;
         let mut signer_kms_id = None;
         let mut private_key = None;
         let use_kms = matches!(
@@ -50,6 +55,8 @@ impl Config {
             blob_storage_bucket_name,
             blob_sender_queue_message_group_id,
             sender_blob_queue_url,
+            outcome_event_bus_name,
+            outcome_schema_version,
         })
     }
 
@@ -65,13 +72,26 @@ pub mod aws_lambda {
     use aws_lambda_events::sqs::SqsEvent;
     use blob_sender_queue::BlobSenderQueueMessageBody;
     use blob_storage::storage::s3::S3BlobStorageManager;
-    use db_types::BlobStorageType;
-    use lambda_runtime::LambdaEvent;
+    use db_types::{BlobStorageType, TxStatus};
+    use lambda_runtime::{LambdaEvent, tracing};
     use network_db::networks::NetworkRepo;
+    use outbox_db::outbox_events::OutboxEventRepo;
+    use outcome_emitter::{
+        emitter::{
+            OutcomeEmitter, event_bridge::AwsEventBridgeOutcomeEmitter,
+            routing::RoutingOutcomeEmitter, webhook::WebhookOutcomeEmitter,
+        },
+        lifecycle::LifecycleEvent,
+        outbox::{OutboxRelay, to_outbox_events},
+        outcome::OutcomeEvent,
+    };
     use signer_wallet::{IntoSignerWalletConfig, manager::SignerWalletManager};
     use sqs_queue::{message_body::ToJsonString, queue::SqsQueue};
     use tx_request::{blob_tx::BlobTxRequestBody, sqs_parser::tx_requests_from_sqs_event};
-    use tx_request_db::repo::TxRequestRepo;
+    use tx_request_db::{repo::TxRequestRepo, types::TxRequestInsert};
+    use webhook_db::{
+        webhook_deliveries::WebhookDeliveryRepo, webhook_endpoints::WebhookEndpointRepo,
+    };
 
     use crate::{Config, signature::sign_tx_request};
 
@@ -99,6 +119,20 @@ pub mod aws_lambda {
             &config.sender_blob_queue_url,
             &config.blob_sender_queue_message_group_id,
         )?;
+        let event_bridge_client = aws_sdk_eventbridge::Client::new(&aws_config);
+        let outcome_emitter = RoutingOutcomeEmitter::build(
+            AwsEventBridgeOutcomeEmitter::build(
+                &event_bridge_client,
+                config.outcome_event_bus_name.clone(),
+                config.outcome_schema_version,
+            ),
+            WebhookOutcomeEmitter::build(
+                WebhookEndpointRepo::new(pool.clone()),
+                WebhookDeliveryRepo::new(pool.clone()),
+                config.outcome_schema_version,
+            )?,
+        );
+        let outbox_relay = OutboxRelay::new(OutboxEventRepo::new(pool.clone()));
 
         let s3_blob_storage_manager =
             S3BlobStorageManager::build(&aws_config, &config.blob_storage_bucket_name);
@@ -123,10 +157,39 @@ pub mod aws_lambda {
 
             let insert_tx_input = tx_request_body
                 .into_db_input(&blob_input_json_file, signature.as_bytes().to_vec())?;
-
-            transaction_repo
-                .insert_tx_request_with_tx_input(&insert_tx_input)
-                .await?;
+            let outbox_events = to_outbox_events(&[LifecycleEvent::for_new_tx_request(
+                &insert_tx_input.new_tx_request,
+            )])?;
+
+            match transaction_repo
+                .insert_tx_request_with_tx_input(&insert_tx_input, &outbox_events)
+                .await?
+            {
+                TxRequestInsert::Inserted => {
+                    outbox_relay.relay(&outcome_emitter, &outbox_events).await?;
+                }
+                TxRequestInsert::Replayed(existing) => {
+                    if existing.tx_status != TxStatus::SIGNED {
+                        tracing::info!(
+                            "Replay of {} ignored: {:?}",
+                            existing.tx_id,
+                            existing.tx_status
+                        );
+                        continue;
+                    }
+                }
+                TxRequestInsert::Conflict(existing) => {
+                    tracing::warn!("Conflicting resubmission of {}", existing.tx_id);
+                    outcome_emitter
+                        .emit_outcome(&OutcomeEvent::conflict(
+                            tx_request_body.tx_id.clone(),
+                            tx_request_body.requester_id.clone(),
+                            None,
+                        ))
+                        .await?;
+                    continue;
+                }
+            }
 
             let trigger_body = BlobSenderQueueMessageBody {
                 tx_id: insert_tx_input.new_tx_request.tx_id,
@@ -1 +1,16 @@
 pub mod constants;
+
+use alloy::primitives::{Address, Uint};
+use alloy_sol_types::Eip712Domain;
+use constants::{EIP712_DOMAIN_NAME, EIP712_DOMAIN_VERSION};
+
+/// Domain of the sEOA signed calls. The sEOA lives at the signer address.
+pub fn build_domain(chain_id: i64, verifying_contract: Address) -> Eip712Domain {
+    Eip712Domain {
+        name: Some(EIP712_DOMAIN_NAME.into()),
+        version: Some(EIP712_DOMAIN_VERSION.into()),
+        chain_id: Some(Uint::<256, 4>::from(chain_id)),
+        verifying_contract: Some(verifying_contract),
+        salt: None,
+    }
+}
@@ -1,6 +1,9 @@
 pub mod calldata;
+pub mod policy;
 pub mod signature;
+pub mod validation;
 
+use outcome_emitter::schema::OutcomeSchemaVersion;
 use signer_wallet::IntoSignerWalletConfig;
 use std::env;
 
@@ -21,6 +24,8 @@ pub struct Config {
     pub sender_standard_queue_url: String,
     pub standard_sender_queue_message_group_id: String,
     pub database_url: String,
+    pub outcome_event_bus_name: String,
+    pub outcome_schema_version: OutcomeSchemaVersion,
 }
 
 impl Config {
@@ -29,6 +34,8 @@ impl Config {
             Self::get_env_var("STANDARD_SENDER_QUEUE_MESSAGE_GROUP_ID");
         let sender_standard_queue_url = Self::get_env_var("STANDARD_SENDER_QUEUE_URL");
         let database_url = Self::get_env_var("DATABASE_URL");
+        let outcome_event_bus_name = Self::get_env_var("OUTCOME_EVENT_BUS_NAME");
+        let outcome_schema_version = OutcomeSchemaVersion::from_env()?;
         let mut signer_kms_id = None;
         let mut private_key = None;
         let use_kms = matches!(
@@ -51,6 +58,8 @@ impl Config {
             database_url,
             sender_standard_queue_url,
             standard_sender_queue_message_group_id,
+            outcome_event_bus_name,
+            outcome_schema_version,
         })
     }
 
@@ -62,16 +71,38 @@ impl Config {
 #[cfg(feature = "aws")]
 pub mod aws_lambda {
 
-    use crate::{Config, signature::sign_tx_request};
+    use crate::{
+        Config,
+        policy::SigningPolicyEngine,
+        signature::sign_tx_request,
+        validation::{RejectionReason, validate_tx_request},
+    };
     use aws_config::{BehaviorVersion, meta::region::RegionProviderChain};
     use aws_lambda_events::sqs::SqsEvent;
+    use db_types::TxStatus;
     use lambda_runtime::{LambdaEvent, tracing};
     use network_db::networks::NetworkRepo;
+    use operator_wallet_db::operator_wallets::{OperatorWallet, OperatorWalletRepo};
+    use outbox_db::outbox_events::OutboxEventRepo;
+    use outcome_emitter::{
+        emitter::{
+            OutcomeEmitter, event_bridge::AwsEventBridgeOutcomeEmitter,
+            routing::RoutingOutcomeEmitter, webhook::WebhookOutcomeEmitter,
+        },
+        lifecycle::LifecycleEvent,
+        outbox::{OutboxRelay, to_outbox_events},
+        outcome::OutcomeEvent,
+    };
     use signer_wallet::{IntoSignerWalletConfig, manager::SignerWalletManager};
     use sqs_queue::{message_body::ToJsonString, queue::SqsQueue};
     use standard_sender_queue::StandardSenderQueueMessageBody;
+    use std::time::{SystemTime, UNIX_EPOCH};
     use tx_request::{sqs_parser::tx_requests_from_sqs_event, standard::StandardTxRequestBody};
-    use tx_request_db::repo::TxRequestRepo;
+    use tx_request_db::{repo::TxRequestRepo, types::TxRequestInsert};
+    use uuid::Uuid;
+    use webhook_db::{
+        webhook_deliveries::WebhookDeliveryRepo, webhook_endpoints::WebhookEndpointRepo,
+    };
 
     pub async fn function_handler(
         event: LambdaEvent<SqsEvent>,
@@ -84,7 +115,10 @@ pub mod aws_lambda {
 
         let transaction_repo = TxRequestRepo::new(pool.clone());
         let network_repo = NetworkRepo::new(pool.clone());
+        let operator_wallet_repo = OperatorWalletRepo::new(pool.clone());
+        let policy_engine = SigningPolicyEngine::new(pool.clone());
         let networks = network_repo.select_all().await?;
+        let chain_ids: Vec<i64> = networks.iter().map(|network| network.chain_id).collect();
 
         let sqs_client = aws_sdk_sqs::Client::new(&aws_config);
 
@@ -93,12 +127,71 @@ pub mod aws_lambda {
             &config.sender_standard_queue_url,
             &config.standard_sender_queue_message_group_id,
         )?;
+        let event_bridge_client = aws_sdk_eventbridge::Client::new(aws_config);
+        let outcome_emitter = RoutingOutcomeEmitter::build(
+            AwsEventBridgeOutcomeEmitter::build(
+                &event_bridge_client,
+                config.outcome_event_bus_name.clone(),
+                config.outcome_schema_version,
+            ),
+            WebhookOutcomeEmitter::build(
+                WebhookEndpointRepo::new(pool.clone()),
+                WebhookDeliveryRepo::new(pool.clone()),
+                config.outcome_schema_version,
+            )?,
+        );
+        let outbox_relay = OutboxRelay::new(OutboxEventRepo::new(pool.clone()));
 
         let tx_request_body_vec = tx_requests_from_sqs_event::<StandardTxRequestBody>(event)?;
         let mut wallet_manager =
             SignerWalletManager::build(&networks, &config.into_signer_wallet_config())?;
 
         for tx_request_body in tx_request_body_vec {
+            let operator_wallet = match tx_request_body.use_operator_wallet_id {
+                Some(operator_wallet_id) => {
+                    find_operator_wallet(&operator_wallet_repo, operator_wallet_id).await?
+                }
+                None => None,
+            };
+            let rejection_reasons = validate_tx_request(
+                &tx_request_body,
+                &chain_ids,
+                operator_wallet.as_ref().map(|wallet| wallet.chain_id),
+                now(),
+            );
+            if !rejection_reasons.is_empty() {
+                // A request on an unknown chain can't be stored, so it is only reported.
+                let is_unknown_chain = rejection_reasons
+                    .iter()
+                    .any(|reason| matches!(reason, RejectionReason::UnknownChainId { .. }));
+                reject_tx_request(
+                    &tx_request_body,
+                    join_reasons(&rejection_reasons),
+                    !is_unknown_chain,
+                    operator_wallet.map(|wallet| wallet.id),
+                    &transaction_repo,
+                    &outcome_emitter,
+                    &outbox_relay,
+                )
+                .await?;
+                continue;
+            }
+
+            let policy_violations = policy_engine.check(&tx_request_body, now()).await?;
+            if !policy_violations.is_empty() {
+                reject_tx_request(
+                    &tx_request_body,
+                    join_reasons(&policy_violations),
+                    true,
+                    operator_wallet.map(|wallet| wallet.id),
+                    &transaction_repo,
+                    &outcome_emitter,
+                    &outbox_relay,
+                )
+                .await?;
+                continue;
+            }
+
             tracing::info!("Signing: {tx_request_body:?}");
 
             let wallet = wallet_manager.get_wallet(tx_request_body.chain_id).await?;
@@ -106,9 +199,34 @@ pub mod aws_lambda {
 
             tracing::info!("Saving...");
             let insert_tx_input = tx_request_body.into_db_input(signature.as_bytes().to_vec())?;
-            transaction_repo
-                .insert_tx_request_with_tx_input(&insert_tx_input)
-                .await?;
+            let outbox_events = to_outbox_events(&[LifecycleEvent::for_new_tx_request(
+                &insert_tx_input.new_tx_request,
+            )])?;
+            match transaction_repo
+                .insert_tx_request_with_tx_input(&insert_tx_input, &outbox_events)
+                .await?
+            {
+                TxRequestInsert::Inserted => {
+                    outbox_relay.relay(&outcome_emitter, &outbox_events).await?;
+                }
+                // A replay of a request whose sender message may have been lost is queued
+                // again; the sender only picks up SIGNED requests.
+                TxRequestInsert::Replayed(existing) => {
+                    if existing.tx_status != TxStatus::SIGNED {
+                        tracing::info!(
+                            "Replay of {} ignored: {:?}",
+                            existing.tx_id,
+                            existing.tx_status
+                        );
+                        continue;
+                    }
+                }
+                TxRequestInsert::Conflict(existing) => {
+                    tracing::warn!("Conflicting resubmission of {}", existing.tx_id);
+                    emit_conflict(&outcome_emitter, &tx_request_body).await?;
+                    continue;
+                }
+            }
             let trigger_body = StandardSenderQueueMessageBody {
                 tx_id: insert_tx_input.new_tx_request.tx_id,
             };
@@ -120,4 +238,97 @@ pub mod aws_lambda {
 
         Ok(())
     }
+
+    async fn find_operator_wallet(
+        operator_wallet_repo: &OperatorWalletRepo,
+        operator_wallet_id: Uuid,
+    ) -> anyhow::Result<Option<OperatorWallet>> {
+        match operator_wallet_repo.find_by_id(operator_wallet_id).await {
+            Ok(operator_wallet) => Ok(Some(operator_wallet)),
+            Err(err) => match err.downcast_ref::<sqlx::Error>() {
+                Some(sqlx::Error::RowNotFound) => Ok(None),
+                _ => Err(err),
+            },
+        }
+    }
+
+    fn join_reasons<T: ToString>(reasons: &[T]) -> String {
+        reasons
+            .iter()
+            .map(T::to_string)
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    /// Stores the request as INVALID unless `store` is false, and reports the error as an
+    /// outcome event. A stored rejection publishes its outcome event through the outbox.
+    async fn reject_tx_request(
+        tx_request_body: &StandardTxRequestBody,
+        error: String,
+        store: bool,
+        operator_wallet_id: Option<Uuid>,
+        transaction_repo: &TxRequestRepo,
+        outcome_emitter: &impl OutcomeEmitter,
+        outbox_relay: &OutboxRelay,
+    ) -> anyhow::Result<()> {
+        tracing::warn!("Rejecting {}: {error}", tx_request_body.tx_id);
+
+        let outcome_event = OutcomeEvent::invalid(
+            tx_request_body.tx_id.clone(),
+            tx_request_body.requester_id.clone(),
+            tx_request_body.metadata.clone(),
+            error,
+        )
+        .with_value_wei(Some(tx_request_body.value_wei));
+
+        if store {
+            let invalid_tx_request = tx_request_body.invalid_tx_request(operator_wallet_id)?;
+            let mut outbox_events =
+                to_outbox_events(&[LifecycleEvent::for_new_tx_request(&invalid_tx_request)])?;
+            outbox_events.extend(to_outbox_events(std::slice::from_ref(&outcome_event))?);
+            match transaction_repo
+                .insert_invalid(&invalid_tx_request, &outbox_events)
+                .await?
+            {
+                TxRequestInsert::Inserted => {
+                    outbox_relay.relay(outcome_emitter, &outbox_events).await?;
+                    return Ok(());
+                }
+                TxRequestInsert::Replayed(existing) => {
+                    if existing.tx_status != TxStatus::INVALID {
+                        return Ok(());
+                    }
+                }
+                TxRequestInsert::Conflict(_) => {
+                    return emit_conflict(outcome_emitter, tx_request_body).await;
+                }
+            }
+        }
+
+        // Nothing was stored, either for an unknown chain or a replay of an earlier rejection
+        outcome_emitter.emit_outcome(&outcome_event).await
+    }
+
+    async fn emit_conflict(
+        outcome_emitter: &impl OutcomeEmitter,
+        tx_request_body: &StandardTxRequestBody,
+    ) -> anyhow::Result<()> {
+        outcome_emitter
+            .emit_outcome(
+                &OutcomeEvent::conflict(
+                    tx_request_body.tx_id.clone(),
+                    tx_request_body.requester_id.clone(),
+                    tx_request_body.metadata.clone(),
+                )
+                .with_value_wei(Some(tx_request_body.value_wei)),
+            )
+            .await
+    }
+
+    fn now() -> i64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or_default()
+    }
 }
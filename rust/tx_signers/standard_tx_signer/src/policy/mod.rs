@@ -0,0 +1,228 @@
+use db_types::Wei;
+use signing_policy_db::{
+    signing_policies::{AllowedCall, SigningPolicy, SigningPolicyRepo},
+    signing_policy_decisions::{NewSigningPolicyDecision, SigningPolicyDecisionRepo},
+};
+use std::fmt;
+use tx_request::standard::StandardTxRequestBody;
+
+#[cfg(test)]
+mod tests;
+
+/// Why a requester's policy refuses to sign a request.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyViolation {
+    NoPolicy {
+        requester_id: String,
+    },
+    TargetNotAllowed {
+        to_address: String,
+    },
+    SelectorNotAllowed {
+        to_address: String,
+        selector: Option<String>,
+    },
+    ValueAboveRequestCap {
+        value_wei: Wei,
+        max_value_wei: Wei,
+    },
+    ValueAboveWindowCap {
+        window_value_wei: Wei,
+        value_wei: Wei,
+        max_value_wei: Wei,
+        window_sec: i64,
+    },
+    DeadlineBeyondHorizon {
+        deadline_timestamp: i64,
+        max_deadline_horizon_sec: i64,
+    },
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoPolicy { requester_id } => {
+                write!(f, "no signing policy for requester {requester_id}")
+            }
+            Self::TargetNotAllowed { to_address } => {
+                write!(f, "target {to_address} is not allowed")
+            }
+            Self::SelectorNotAllowed {
+                to_address,
+                selector: Some(selector),
+            } => write!(f, "selector {selector} is not allowed on {to_address}"),
+            Self::SelectorNotAllowed {
+                to_address,
+                selector: None,
+            } => write!(
+                f,
+                "calls without a selector are not allowed on {to_address}"
+            ),
+            Self::ValueAboveRequestCap {
+                value_wei,
+                max_value_wei,
+            } => write!(
+                f,
+                "value_wei {value_wei} exceeds the per-request cap of {max_value_wei}"
+            ),
+            Self::ValueAboveWindowCap {
+                window_value_wei,
+                value_wei,
+                max_value_wei,
+                window_sec,
+            } => write!(
+                f,
+                "value_wei {value_wei} on top of {window_value_wei} signed in the last {window_sec}s exceeds the cap of {max_value_wei}"
+            ),
+            Self::DeadlineBeyondHorizon {
+                deadline_timestamp,
+                max_deadline_horizon_sec,
+            } => write!(
+                f,
+                "deadline {deadline_timestamp} is more than {max_deadline_horizon_sec}s ahead"
+            ),
+        }
+    }
+}
+
+/// Lowercase 0x-prefixed 4-byte selector of the calldata, or None for a call without one.
+pub fn selector(calldata: &str) -> Option<String> {
+    let hex = calldata.strip_prefix("0x").unwrap_or(calldata);
+    hex.get(..8)
+        .map(|selector| format!("0x{}", selector.to_lowercase()))
+}
+
+/// Checks a request against its requester's policy. Requesters without a policy are refused.
+/// `window_value_wei` is what the requester already got signed within the policy's window.
+pub fn evaluate_policy(
+    tx_request_body: &StandardTxRequestBody,
+    policy: Option<&SigningPolicy>,
+    allowed_calls: &[AllowedCall],
+    window_value_wei: Wei,
+    now: i64,
+) -> Vec<PolicyViolation> {
+    let Some(policy) = policy else {
+        return vec![PolicyViolation::NoPolicy {
+            requester_id: tx_request_body.requester_id.clone(),
+        }];
+    };
+    let mut violations = Vec::new();
+
+    if !policy.allow_any_target {
+        let to_address = tx_request_body.to_address.to_lowercase();
+        let selector = selector(&tx_request_body.calldata);
+        let target_calls: Vec<&AllowedCall> = allowed_calls
+            .iter()
+            .filter(|call| call.to_address == to_address)
+            .collect();
+
+        if target_calls.is_empty() {
+            violations.push(PolicyViolation::TargetNotAllowed { to_address });
+        } else if !target_calls
+            .iter()
+            .any(|call| call.selector.is_none() || call.selector == selector)
+        {
+            violations.push(PolicyViolation::SelectorNotAllowed {
+                to_address,
+                selector,
+            });
+        }
+    }
+
+    if let Some(max_value_wei) = policy.max_value_wei_per_request
+        && tx_request_body.value_wei > max_value_wei
+    {
+        violations.push(PolicyViolation::ValueAboveRequestCap {
+            value_wei: tx_request_body.value_wei,
+            max_value_wei,
+        });
+    }
+
+    if let (Some(max_value_wei), Some(window_sec)) =
+        (policy.max_value_wei_per_window, policy.value_window_sec)
+        && window_value_wei
+            .checked_add(tx_request_body.value_wei)
+            .is_none_or(|total| total > max_value_wei)
+    {
+        violations.push(PolicyViolation::ValueAboveWindowCap {
+            window_value_wei,
+            value_wei: tx_request_body.value_wei,
+            max_value_wei,
+            window_sec,
+        });
+    }
+
+    if let Some(max_deadline_horizon_sec) = policy.max_deadline_horizon_sec
+        && tx_request_body.deadline_timestamp > now.saturating_add(max_deadline_horizon_sec)
+    {
+        violations.push(PolicyViolation::DeadlineBeyondHorizon {
+            deadline_timestamp: tx_request_body.deadline_timestamp,
+            max_deadline_horizon_sec,
+        });
+    }
+
+    violations
+}
+
+/// Loads the requester's policy from Postgres, evaluates the request and records the decision
+/// in the audit table. The rolling window relies on the signer queue delivering one batch at a
+/// time, so decisions of the same requester are not evaluated concurrently.
+pub struct SigningPolicyEngine {
+    policy_repo: SigningPolicyRepo,
+    decision_repo: SigningPolicyDecisionRepo,
+}
+
+impl SigningPolicyEngine {
+    pub fn new(pool: sqlx::Pool<sqlx::Postgres>) -> Self {
+        Self {
+            policy_repo: SigningPolicyRepo::new(pool.clone()),
+            decision_repo: SigningPolicyDecisionRepo::new(pool),
+        }
+    }
+
+    pub async fn check(
+        &self,
+        tx_request_body: &StandardTxRequestBody,
+        now: i64,
+    ) -> anyhow::Result<Vec<PolicyViolation>> {
+        let requester_id = &tx_request_body.requester_id;
+        let policy = self.policy_repo.find_by_requester_id(requester_id).await?;
+        let allowed_calls = match &policy {
+            Some(policy) if !policy.allow_any_target => {
+                self.policy_repo.select_allowed_calls(requester_id).await?
+            }
+            _ => Vec::new(),
+        };
+        let window_value_wei = match policy.as_ref().and_then(|policy| policy.value_window_sec) {
+            Some(window_sec) => {
+                self.decision_repo
+                    .allowed_value_wei_within(requester_id, window_sec, &tx_request_body.tx_id)
+                    .await?
+            }
+            None => Wei::ZERO,
+        };
+
+        let violations = evaluate_policy(
+            tx_request_body,
+            policy.as_ref(),
+            &allowed_calls,
+            window_value_wei,
+            now,
+        );
+
+        self.decision_repo
+            .insert_decision(&NewSigningPolicyDecision {
+                tx_id: tx_request_body.tx_id.clone(),
+                requester_id: requester_id.clone(),
+                chain_id: tx_request_body.chain_id,
+                to_address: tx_request_body.to_address.to_lowercase(),
+                selector: selector(&tx_request_body.calldata),
+                value_wei: tx_request_body.value_wei,
+                deadline_timestamp: tx_request_body.deadline_timestamp,
+                violations: violations.iter().map(PolicyViolation::to_string).collect(),
+            })
+            .await?;
+
+        Ok(violations)
+    }
+}
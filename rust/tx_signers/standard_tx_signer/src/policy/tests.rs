@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use crate::policy::{PolicyViolation, evaluate_policy, selector};
+    use db_types::Wei;
+    use signing_policy_db::signing_policies::{AllowedCall, SigningPolicy};
+    use sqlx::types::time::OffsetDateTime;
+    use tx_request::standard::StandardTxRequestBody;
+    use uuid::Uuid;
+
+    const NOW: i64 = 1_800_000_000;
+    const TARGET: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+
+    fn tx_request_body() -> StandardTxRequestBody {
+        StandardTxRequestBody {
+            tx_id: "tx-1".to_string(),
+            requester_id: "requester-1".to_string(),
+            chain_id: 31337,
+            calldata: "0xDEADBEEF00".to_string(),
+            to_address: TARGET.to_string(),
+            value_wei: Wei::from(100),
+            deadline_timestamp: NOW + 600,
+            pass_value_from_operator_wallet: false,
+            use_operator_wallet_id: None,
+            metadata: None,
+        }
+    }
+
+    fn policy() -> SigningPolicy {
+        SigningPolicy {
+            requester_id: "requester-1".to_string(),
+            allow_any_target: false,
+            max_value_wei_per_request: Some(Wei::from(1_000)),
+            max_value_wei_per_window: Some(Wei::from(5_000)),
+            value_window_sec: Some(3600),
+            max_deadline_horizon_sec: Some(3600),
+            created_at: OffsetDateTime::UNIX_EPOCH,
+            updated_at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    fn allowed_call(selector: Option<&str>) -> AllowedCall {
+        AllowedCall {
+            id: Uuid::new_v4(),
+            requester_id: "requester-1".to_string(),
+            to_address: TARGET.to_lowercase(),
+            selector: selector.map(str::to_string),
+            created_at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn extracts_lowercase_selector() {
+        assert_eq!(selector("0xDEADBEEF00"), Some("0xdeadbeef".to_string()));
+        assert_eq!(selector("0x"), None);
+    }
+
+    #[test]
+    fn allows_request_within_policy() {
+        let violations = evaluate_policy(
+            &tx_request_body(),
+            Some(&policy()),
+            &[allowed_call(Some("0xdeadbeef"))],
+            Wei::from(4_900),
+            NOW,
+        );
+
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn refuses_requester_without_policy() {
+        let violations = evaluate_policy(&tx_request_body(), None, &[], Wei::ZERO, NOW);
+
+        assert_eq!(
+            violations,
+            vec![PolicyViolation::NoPolicy {
+                requester_id: "requester-1".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn refuses_unlisted_target_and_selector() {
+        let violations = evaluate_policy(&tx_request_body(), Some(&policy()), &[], Wei::ZERO, NOW);
+        assert_eq!(
+            violations,
+            vec![PolicyViolation::TargetNotAllowed {
+                to_address: TARGET.to_lowercase()
+            }]
+        );
+
+        let violations = evaluate_policy(
+            &tx_request_body(),
+            Some(&policy()),
+            &[allowed_call(Some("0xa9059cbb"))],
+            Wei::ZERO,
+            NOW,
+        );
+        assert_eq!(
+            violations,
+            vec![PolicyViolation::SelectorNotAllowed {
+                to_address: TARGET.to_lowercase(),
+                selector: Some("0xdeadbeef".to_string())
+            }]
+        );
+    }
+
+    #[test]
+    fn refuses_value_and_deadline_over_limits() {
+        let mut body = tx_request_body();
+        body.value_wei = Wei::from(1_001);
+        body.deadline_timestamp = NOW + 3601;
+
+        let violations = evaluate_policy(
+            &body,
+            Some(&policy()),
+            &[allowed_call(None)],
+            Wei::from(4_000),
+            NOW,
+        );
+
+        assert_eq!(
+            violations,
+            vec![
+                PolicyViolation::ValueAboveRequestCap {
+                    value_wei: Wei::from(1_001),
+                    max_value_wei: Wei::from(1_000)
+                },
+                PolicyViolation::ValueAboveWindowCap {
+                    window_value_wei: Wei::from(4_000),
+                    value_wei: Wei::from(1_001),
+                    max_value_wei: Wei::from(5_000),
+                    window_sec: 3600
+                },
+                PolicyViolation::DeadlineBeyondHorizon {
+                    deadline_timestamp: NOW + 3601,
+                    max_deadline_horizon_sec: 3600
+                },
+            ]
+        );
+    }
+}
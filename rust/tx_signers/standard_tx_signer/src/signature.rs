@@ -1,10 +1,10 @@
 use alloy::{
-    primitives::{Address, U256, Uint, keccak256},
+    primitives::{Address, B256, U256, keccak256},
     signers::Signature,
     sol,
 };
-use alloy_sol_types::Eip712Domain;
-use eip712_domain::constants::{EIP712_DOMAIN_NAME, EIP712_DOMAIN_VERSION};
+use alloy_sol_types::SolStruct;
+use eip712_domain::build_domain;
 use ow_wallet_adapter::wallet::OwWallet;
 use tx_request::standard::StandardTxRequestBody;
 // use signer_queue::tx_request::TxRequestBody;
@@ -24,22 +24,29 @@ pub async fn sign_tx_request(
     tx_request_body: &StandardTxRequestBody,
     wallet: &OwWallet,
 ) -> anyhow::Result<Signature> {
-    let signed_call = sEOA::SignedCall {
+    let signed_call = build_signed_call(tx_request_body)?;
+    let domain = build_domain(tx_request_body.chain_id, wallet.get_address()?);
+
+    let signature = wallet.sign_typed_data(&signed_call, &domain).await?;
+    Ok(signature)
+}
+
+/// EIP-712 digest the signer at `signer_address` signs for this request.
+pub fn signing_digest(
+    tx_request_body: &StandardTxRequestBody,
+    signer_address: Address,
+) -> anyhow::Result<B256> {
+    let signed_call = build_signed_call(tx_request_body)?;
+    let domain = build_domain(tx_request_body.chain_id, signer_address);
+    Ok(signed_call.eip712_signing_hash(&domain))
+}
+
+fn build_signed_call(tx_request_body: &StandardTxRequestBody) -> anyhow::Result<sEOA::SignedCall> {
+    Ok(sEOA::SignedCall {
         target: Address::from_str(&tx_request_body.to_address)?,
         payloadHash: keccak256(parse_calldata(&tx_request_body.calldata)?),
-        value: U256::from(tx_request_body.value_wei),
+        value: tx_request_body.value_wei.into(),
         salt: keccak256(tx_request_body.tx_id.as_bytes()),
         deadline: U256::from(tx_request_body.deadline_timestamp),
-    };
-
-    let domain = Eip712Domain {
-        name: Some(EIP712_DOMAIN_NAME.into()),
-        version: Some(EIP712_DOMAIN_VERSION.into()),
-        chain_id: Some(Uint::<256, 4>::from(tx_request_body.chain_id)),
-        verifying_contract: Some(wallet.get_address()?),
-        salt: None,
-    };
-
-    let signature = wallet.sign_typed_data(&signed_call, &domain).await?;
-    Ok(signature)
+    })
 }
@@ -0,0 +1,106 @@
+use crate::calldata::parse_calldata;
+use alloy::primitives::Address;
+use std::{fmt, str::FromStr};
+use tx_request::standard::StandardTxRequestBody;
+use uuid::Uuid;
+
+#[cfg(test)]
+mod tests;
+
+/// Why a request is refused before it is signed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RejectionReason {
+    DeadlinePassed {
+        deadline_timestamp: i64,
+    },
+    UnknownChainId {
+        chain_id: i64,
+    },
+    InvalidToAddress {
+        to_address: String,
+    },
+    InvalidCalldata {
+        reason: String,
+    },
+    OperatorWalletNotFound {
+        operator_wallet_id: Uuid,
+    },
+    OperatorWalletOnOtherChain {
+        operator_wallet_id: Uuid,
+        chain_id: i64,
+    },
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DeadlinePassed { deadline_timestamp } => {
+                write!(f, "deadline {deadline_timestamp} has already passed")
+            }
+            Self::UnknownChainId { chain_id } => {
+                write!(f, "no network configured for chain_id {chain_id}")
+            }
+            Self::InvalidToAddress { to_address } => {
+                write!(f, "to_address {to_address} is not a valid address")
+            }
+            Self::InvalidCalldata { reason } => write!(f, "invalid calldata: {reason}"),
+            Self::OperatorWalletNotFound { operator_wallet_id } => {
+                write!(f, "operator wallet {operator_wallet_id} not found")
+            }
+            Self::OperatorWalletOnOtherChain {
+                operator_wallet_id,
+                chain_id,
+            } => write!(
+                f,
+                "operator wallet {operator_wallet_id} belongs to chain_id {chain_id}"
+            ),
+        }
+    }
+}
+
+/// Checks a request against the configured networks before anything is signed or stored.
+/// `operator_wallet_chain_id` is the chain of the requested operator wallet, or None when
+/// that wallet does not exist.
+pub fn validate_tx_request(
+    tx_request_body: &StandardTxRequestBody,
+    chain_ids: &[i64],
+    operator_wallet_chain_id: Option<i64>,
+    now: i64,
+) -> Vec<RejectionReason> {
+    let mut reasons = Vec::new();
+
+    if tx_request_body.deadline_timestamp <= now {
+        reasons.push(RejectionReason::DeadlinePassed {
+            deadline_timestamp: tx_request_body.deadline_timestamp,
+        });
+    }
+    if !chain_ids.contains(&tx_request_body.chain_id) {
+        reasons.push(RejectionReason::UnknownChainId {
+            chain_id: tx_request_body.chain_id,
+        });
+    }
+    if Address::from_str(&tx_request_body.to_address).is_err() {
+        reasons.push(RejectionReason::InvalidToAddress {
+            to_address: tx_request_body.to_address.clone(),
+        });
+    }
+    if let Err(err) = parse_calldata(&tx_request_body.calldata) {
+        reasons.push(RejectionReason::InvalidCalldata {
+            reason: err.to_string(),
+        });
+    }
+    if let Some(operator_wallet_id) = tx_request_body.use_operator_wallet_id {
+        match operator_wallet_chain_id {
+            None => reasons.push(RejectionReason::OperatorWalletNotFound { operator_wallet_id }),
+            Some(chain_id) if chain_id != tx_request_body.chain_id => {
+                reasons.push(RejectionReason::OperatorWalletOnOtherChain {
+                    operator_wallet_id,
+                    chain_id,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    reasons
+}
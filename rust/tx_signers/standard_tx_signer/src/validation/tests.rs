@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use crate::validation::{RejectionReason, validate_tx_request};
+    use db_types::Wei;
+    use tx_request::standard::StandardTxRequestBody;
+    use uuid::Uuid;
+
+    const NOW: i64 = 1_800_000_000;
+    const CHAIN_ID: i64 = 31337;
+
+    fn tx_request_body() -> StandardTxRequestBody {
+        StandardTxRequestBody {
+            tx_id: "tx-1".to_string(),
+            requester_id: "requester-1".to_string(),
+            chain_id: CHAIN_ID,
+            calldata: "0xdeadbeef".to_string(),
+            to_address: "0x5FbDB2315678afecb367f032d93F642f64180aa3".to_string(),
+            value_wei: Wei::ZERO,
+            deadline_timestamp: NOW + 600,
+            pass_value_from_operator_wallet: false,
+            use_operator_wallet_id: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn accepts_valid_request() {
+        let reasons = validate_tx_request(&tx_request_body(), &[CHAIN_ID], None, NOW);
+
+        assert!(reasons.is_empty());
+    }
+
+    #[test]
+    fn rejects_past_deadline_unknown_chain_and_bad_input() {
+        let mut body = tx_request_body();
+        body.deadline_timestamp = NOW - 1;
+        body.chain_id = 1;
+        body.to_address = "0x1234".to_string();
+        body.calldata = "0xzz".to_string();
+
+        let reasons = validate_tx_request(&body, &[CHAIN_ID], None, NOW);
+
+        assert_eq!(
+            reasons,
+            vec![
+                RejectionReason::DeadlinePassed {
+                    deadline_timestamp: NOW - 1
+                },
+                RejectionReason::UnknownChainId { chain_id: 1 },
+                RejectionReason::InvalidToAddress {
+                    to_address: "0x1234".to_string()
+                },
+                RejectionReason::InvalidCalldata {
+                    reason: "CALLDATA contains non-hex characters".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_operator_wallet_on_other_chain() {
+        let operator_wallet_id = Uuid::new_v4();
+        let mut body = tx_request_body();
+        body.use_operator_wallet_id = Some(operator_wallet_id);
+
+        let reasons = validate_tx_request(&body, &[CHAIN_ID], Some(1), NOW);
+
+        assert_eq!(
+            reasons,
+            vec![RejectionReason::OperatorWalletOnOtherChain {
+                operator_wallet_id,
+                chain_id: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_missing_operator_wallet() {
+        let operator_wallet_id = Uuid::new_v4();
+        let mut body = tx_request_body();
+        body.use_operator_wallet_id = Some(operator_wallet_id);
+
+        let reasons = validate_tx_request(&body, &[CHAIN_ID], None, NOW);
+
+        assert_eq!(
+            reasons,
+            vec![RejectionReason::OperatorWalletNotFound { operator_wallet_id }]
+        );
+    }
+}
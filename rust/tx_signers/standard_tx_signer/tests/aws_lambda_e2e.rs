@@ -5,6 +5,7 @@ mod tests {
     use alloy::node_bindings::Anvil;
     use aws_config::{BehaviorVersion, meta::region::RegionProviderChain};
     use aws_lambda_events::sqs::{SqsEvent, SqsMessage};
+    use db_types::WalletSelectionStrategy;
     use lambda_runtime::{Context, LambdaEvent};
     use network_db::networks::{NetworkRepo, NewNetwork};
     use serde_json::json;
@@ -43,6 +44,15 @@ mod tests {
                 blob_gas_estimation_buffer_ppm: 1_000_000,
                 max_retry_attempts: 3,
                 tx_max_age_sec: 3600,
+                max_fee_per_gas_ceiling: None,
+                max_fee_per_blob_gas_ceiling: None,
+                operator_wallet_balance_warning_level: None,
+                wallet_selection_strategy: WalletSelectionStrategy::LeastRecentlyUsed,
+                max_in_flight_txs_per_wallet: 1,
+                max_batch_items: None,
+                max_batch_gas_ppm: None,
+                max_batch_value_wei: None,
+                max_blobs_per_tx: None,
             })
             .await?;
         Ok(())
@@ -1,2 +1,5 @@
 pub mod manager;
+pub mod reconciliation;
 pub mod wallet;
+
+pub use db_types::WalletSelectionStrategy;
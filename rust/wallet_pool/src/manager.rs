@@ -1,19 +1,40 @@
-use crate::wallet::Wallet;
+use crate::{
+    reconciliation::{NonceReconciliation, reconcile_nonce},
+    wallet::Wallet,
+};
+use alloy::primitives::U256;
 use anyhow::bail;
 use network_db::networks::Network;
-use operator_wallet_db::operator_wallets::{OperatorWallet, OperatorWalletRepo};
+use operator_wallet_db::{
+    nonce_reservations::NonceReservationRepo,
+    operator_wallets::{OperatorWallet, OperatorWalletRepo},
+};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Covers the longest Lambda run. Wallets held longer, e.g. while waiting for a receipt, are
+/// handed back to their lease owner with a fresh lease by the sweeper once it sees their
+/// transactions in flight.
+const LEASE_DURATION_SEC: f64 = 900.0;
+
+pub struct LowBalanceWallet {
+    pub operator_wallet: OperatorWallet,
+    pub balance: U256,
+    pub warning_level: i64,
+}
+
 enum AcquireAttemptResult {
     Acquired(Wallet),
     NoWalletAvailable,
     InsufficientFunds(Uuid),
+    NeedsManualReview,
 }
 
 pub struct WalletPoolManager {
     operator_wallet_repo: OperatorWalletRepo,
+    nonce_reservation_repo: NonceReservationRepo,
     networks_map: HashMap<i64, Network>,
+    lease_owner: String,
 }
 
 impl WalletPoolManager {
@@ -23,25 +44,41 @@ impl WalletPoolManager {
             networks_map.insert(network.chain_id, network.clone());
         }
         Self {
+            nonce_reservation_repo: NonceReservationRepo::new(operator_wallet_repo.pool.clone()),
             operator_wallet_repo,
             networks_map,
+            lease_owner: Uuid::new_v4().to_string(),
         }
     }
 
     async fn fetch_and_lock(
         &self,
-        chain_id: i64,
+        network: &Network,
         use_operator_wallet_id: Option<Uuid>,
+        requester_id: Option<&str>,
     ) -> anyhow::Result<Option<OperatorWallet>> {
         if let Some(operator_wallet_id) = use_operator_wallet_id {
             return Ok(self
                 .operator_wallet_repo
-                .lock_by_id(operator_wallet_id, chain_id)
+                .lock_by_id(
+                    operator_wallet_id,
+                    network.chain_id,
+                    i64::from(network.max_in_flight_txs_per_wallet),
+                    &self.lease_owner,
+                    LEASE_DURATION_SEC,
+                )
                 .await?);
         } else {
             return Ok(self
                 .operator_wallet_repo
-                .lock_any_by_chain(chain_id)
+                .lock_any_by_chain(
+                    network.chain_id,
+                    &network.wallet_selection_strategy,
+                    requester_id,
+                    i64::from(network.max_in_flight_txs_per_wallet),
+                    &self.lease_owner,
+                    LEASE_DURATION_SEC,
+                )
                 .await?);
         }
     }
@@ -50,26 +87,59 @@ impl WalletPoolManager {
         &self,
         chain_id: i64,
         use_operator_wallet_id: Option<Uuid>,
+        requester_id: Option<&str>,
     ) -> anyhow::Result<AcquireAttemptResult> {
         let Some(network) = self.networks_map.get(&chain_id) else {
             bail!("Network not found for chain_id: {chain_id}");
         };
 
         let Some(operator_wallet) = self
-            .fetch_and_lock(chain_id, use_operator_wallet_id)
+            .fetch_and_lock(network, use_operator_wallet_id, requester_id)
             .await?
         else {
-            println!("fetch and lock failed in wallet acquire");
+            tracing::info!("fetch and lock failed in wallet acquire");
             return Ok(AcquireAttemptResult::NoWalletAvailable);
         };
 
         let mut wallet = Wallet::build(&operator_wallet, &network).await?;
 
-        if wallet.has_enough_balance().await? == false {
+        let balance = wallet.get_balance().await?;
+        self.operator_wallet_repo
+            .set_balance(operator_wallet.id, &balance.to_string())
+            .await?;
+        if U256::from(wallet.min_balance) > balance {
             return Ok(AcquireAttemptResult::InsufficientFunds(operator_wallet.id));
         }
 
-        wallet.set_next_nonce().await?;
+        // In-flight reservations are ahead of the chain by design, only an idle wallet can be
+        // compared against it.
+        let reservations = self
+            .nonce_reservation_repo
+            .select_by_wallet(operator_wallet.id)
+            .await?;
+        if reservations.is_empty() {
+            match self.reconcile_wallet_nonce(&mut wallet).await? {
+                NonceReconciliation::InSync | NonceReconciliation::Resync(_) => {}
+                NonceReconciliation::Pending => {
+                    self.release_unused(operator_wallet.id).await?;
+                    return Ok(AcquireAttemptResult::NoWalletAvailable);
+                }
+                NonceReconciliation::ManualReview(_) => {
+                    return Ok(AcquireAttemptResult::NeedsManualReview);
+                }
+            }
+        }
+
+        if is_pipelined(network) {
+            let latest_nonce = wallet.get_latest_nonce().await?;
+            let nonce = self
+                .nonce_reservation_repo
+                .reserve_next(operator_wallet.id, i64::try_from(latest_nonce)?)
+                .await?;
+            wallet.next_nonce = Some(u64::try_from(nonce)?);
+        } else {
+            wallet.set_next_nonce().await?;
+        }
 
         Ok(AcquireAttemptResult::Acquired(wallet))
     }
@@ -78,10 +148,11 @@ impl WalletPoolManager {
         &self,
         chain_id: i64,
         use_operator_wallet_id: Option<Uuid>,
+        requester_id: Option<&str>,
     ) -> anyhow::Result<Option<Wallet>> {
         loop {
             match self
-                .try_acquire_once(chain_id, use_operator_wallet_id)
+                .try_acquire_once(chain_id, use_operator_wallet_id, requester_id)
                 .await?
             {
                 AcquireAttemptResult::Acquired(wallet) => return Ok(Some(wallet)),
@@ -96,24 +167,403 @@ impl WalletPoolManager {
                         continue;
                     }
                 }
+                AcquireAttemptResult::NeedsManualReview => {
+                    if use_operator_wallet_id.is_some() {
+                        return Ok(None);
+                    } else {
+                        continue;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Compares the DB nonce of a locked wallet with the chain and its execution attempts.
+    /// Resyncs the DB nonce when it is safe, otherwise flags the wallet for manual review
+    /// and releases it.
+    pub async fn reconcile_wallet_nonce(
+        &self,
+        wallet: &mut Wallet,
+    ) -> anyhow::Result<NonceReconciliation> {
+        let db_nonce = u64::try_from(wallet.db_record.nonce)?;
+        let latest_nonce = wallet.get_latest_nonce().await?;
+        let pending_nonce = wallet.get_pending_nonce().await?;
+        let history = self
+            .operator_wallet_repo
+            .select_nonce_history(
+                wallet.db_record.id,
+                i64::try_from(db_nonce.min(latest_nonce))?,
+            )
+            .await?;
+
+        let reconciliation = reconcile_nonce(db_nonce, latest_nonce, pending_nonce, &history)?;
+
+        match &reconciliation {
+            NonceReconciliation::Resync(nonce) => {
+                tracing::info!(
+                    "Resyncing nonce of wallet {} from {} to {}",
+                    wallet.db_record.id,
+                    db_nonce,
+                    nonce
+                );
+                let nonce = i64::try_from(*nonce)?;
+                if !self
+                    .operator_wallet_repo
+                    .set_nonce(wallet.db_record.id, &self.lease_owner, nonce)
+                    .await?
+                {
+                    bail!("Lease of wallet {} was lost", wallet.db_record.id);
+                }
+                wallet.db_record.nonce = nonce;
+            }
+            NonceReconciliation::ManualReview(reason) => {
+                tracing::warn!(
+                    "Wallet {} needs manual review: {}",
+                    wallet.db_record.id,
+                    reason
+                );
+                self.operator_wallet_repo
+                    .mark_needs_manual_review(wallet.db_record.id)
+                    .await?;
+            }
+            NonceReconciliation::InSync | NonceReconciliation::Pending => {}
+        }
+
+        Ok(reconciliation)
+    }
+
+    /// Reconciles the nonce of every wallet without a lease. Meant for scheduled jobs.
+    pub async fn reconcile_idle_wallets(&self) -> anyhow::Result<()> {
+        for operator_wallet in self.operator_wallet_repo.select_idle().await? {
+            let Some(network) = self.networks_map.get(&operator_wallet.chain_id) else {
+                continue;
+            };
+            let mut wallet = Wallet::build(&operator_wallet, network).await?;
+
+            let Some(locked_wallet) = self
+                .operator_wallet_repo
+                .lock_by_id(
+                    operator_wallet.id,
+                    operator_wallet.chain_id,
+                    i64::from(network.max_in_flight_txs_per_wallet),
+                    &self.lease_owner,
+                    LEASE_DURATION_SEC,
+                )
+                .await?
+            else {
+                continue;
+            };
+            wallet.db_record = locked_wallet;
+
+            let reconciliation = self.reconcile_wallet_nonce(&mut wallet).await;
+            if !matches!(reconciliation, Ok(NonceReconciliation::ManualReview(_))) {
+                self.release_unused(operator_wallet.id).await?;
+            }
+            reconciliation?;
+        }
+        Ok(())
+    }
+
+    /// Reclaims wallets with an expired lease. Wallets with transactions still in flight go back
+    /// to their lease owner with a fresh lease, the rest returns to the pool after nonce
+    /// reconciliation. Pipelined wallets
+    /// track their in-flight nonces in reservations, so they only drop the unsent ones.
+    pub async fn sweep_expired_leases(&self) -> anyhow::Result<()> {
+        for operator_wallet in self
+            .operator_wallet_repo
+            .take_over_expired_leases(&self.lease_owner, LEASE_DURATION_SEC)
+            .await?
+        {
+            let Some(network) = self.networks_map.get(&operator_wallet.chain_id) else {
+                continue;
+            };
+            if is_pipelined(network) {
+                tracing::info!(
+                    "Reclaiming pipelined wallet {} from expired lease",
+                    operator_wallet.id
+                );
+                self.release_unused(operator_wallet.id).await?;
+                continue;
+            }
+            let mut wallet = Wallet::build(&operator_wallet, network).await?;
+
+            match self.reconcile_wallet_nonce(&mut wallet).await? {
+                NonceReconciliation::Pending => {
+                    tracing::info!(
+                        "Wallet {} has transactions in flight, extending its lease",
+                        operator_wallet.id
+                    );
+                    self.hand_back_lease(&operator_wallet).await?;
+                }
+                NonceReconciliation::InSync | NonceReconciliation::Resync(_) => {
+                    tracing::info!(
+                        "Reclaiming wallet {} from expired lease",
+                        operator_wallet.id
+                    );
+                    self.release_unused(operator_wallet.id).await?;
+                }
+                NonceReconciliation::ManualReview(_) => {}
             }
         }
+        Ok(())
     }
 
-    pub async fn release_used(&self, operator_wallet_id: Uuid) -> anyhow::Result<()> {
+    /// Returns a taken over lease to the owner still waiting for its transactions, so its
+    /// release bumps the nonce as usual.
+    async fn hand_back_lease(&self, operator_wallet: &OperatorWallet) -> anyhow::Result<()> {
+        let Some(previous_lease_owner) = &operator_wallet.lease_owner else {
+            return Ok(());
+        };
+        if !self
+            .operator_wallet_repo
+            .hand_back_lease(
+                operator_wallet.id,
+                &self.lease_owner,
+                previous_lease_owner,
+                LEASE_DURATION_SEC,
+            )
+            .await?
+        {
+            tracing::warn!("Lease of wallet {} was lost", operator_wallet.id);
+        }
+        Ok(())
+    }
+
+    /// Re-enables `no_funds` wallets that were topped up above the network minimum and returns
+    /// the wallets whose balance just dropped below the network warning level. A wallet is
+    /// returned again only after it was refilled above the warning level in between. Wallets
+    /// whose balance can't be read are skipped until the next sweep.
+    pub async fn sweep_balances(&self) -> anyhow::Result<Vec<LowBalanceWallet>> {
+        let mut low_balance_wallets = Vec::new();
+
+        for operator_wallet in self.operator_wallet_repo.select_enabled().await? {
+            let Some(network) = self.networks_map.get(&operator_wallet.chain_id) else {
+                continue;
+            };
+            let operator_wallet_id = operator_wallet.id;
+            match self.sweep_wallet_balance(operator_wallet, network).await {
+                Ok(Some(low_balance_wallet)) => low_balance_wallets.push(low_balance_wallet),
+                Ok(None) => {}
+                Err(err) => {
+                    tracing::warn!(
+                        "Skipping balance sweep of wallet {operator_wallet_id}: {err:?}"
+                    );
+                }
+            }
+        }
+
+        Ok(low_balance_wallets)
+    }
+
+    async fn sweep_wallet_balance(
+        &self,
+        operator_wallet: OperatorWallet,
+        network: &Network,
+    ) -> anyhow::Result<Option<LowBalanceWallet>> {
+        let wallet = Wallet::build(&operator_wallet, network).await?;
+        let balance = wallet.get_balance().await?;
         self.operator_wallet_repo
-            .release_used(operator_wallet_id)
+            .set_balance(operator_wallet.id, &balance.to_string())
             .await?;
+
+        if operator_wallet.no_funds && balance >= U256::from(network.min_operator_wallet_balance) {
+            tracing::info!("Re-enabling refilled wallet {}", operator_wallet.id);
+            self.operator_wallet_repo
+                .clear_no_funds(operator_wallet.id)
+                .await?;
+        }
+
+        let Some(warning_level) = network
+            .operator_wallet_balance_warning_level
+            .filter(|warning_level| balance < U256::from(*warning_level))
+        else {
+            self.operator_wallet_repo
+                .set_low_balance_warned(operator_wallet.id, false)
+                .await?;
+            return Ok(None);
+        };
+
+        let crossed = self
+            .operator_wallet_repo
+            .set_low_balance_warned(operator_wallet.id, true)
+            .await?;
+        Ok(crossed.then_some(LowBalanceWallet {
+            operator_wallet,
+            balance,
+            warning_level,
+        }))
+    }
+
+    /// Called once the transaction using `nonce` is resolved on chain. `lease_owner` is the
+    /// owner that sent it; a lease taken over since is left to the sweeper, which resyncs the
+    /// nonce from chain.
+    pub async fn release_used(
+        &self,
+        operator_wallet_id: Uuid,
+        lease_owner: Option<&str>,
+        nonce: i64,
+    ) -> anyhow::Result<()> {
+        let operator_wallet = self
+            .operator_wallet_repo
+            .find_by_id(operator_wallet_id)
+            .await?;
+
+        if self.is_pipelined_chain(operator_wallet.chain_id)? {
+            self.nonce_reservation_repo
+                .confirm(operator_wallet_id, nonce)
+                .await?;
+            return Ok(());
+        }
+
+        let released = match lease_owner {
+            Some(lease_owner) => {
+                self.operator_wallet_repo
+                    .release_used(operator_wallet_id, lease_owner)
+                    .await?
+            }
+            None => false,
+        };
+        if !released {
+            tracing::warn!(
+                "Lease of wallet {operator_wallet_id} was lost before nonce {nonce} was released"
+            );
+        }
         Ok(())
     }
 
+    /// Called when the nonce of a stuck transaction was mined by another transaction. The DB
+    /// nonce is resynced to the chain instead of bumped, since the stuck one may not be the
+    /// only transaction mined in the meantime.
+    pub async fn release_mined_elsewhere(
+        &self,
+        wallet: &Wallet,
+        lease_owner: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let latest_nonce = i64::try_from(wallet.get_latest_nonce().await?)?;
+
+        if self.is_pipelined_chain(wallet.chain_id)? {
+            self.nonce_reservation_repo
+                .confirm(wallet.db_record.id, latest_nonce - 1)
+                .await?;
+            return Ok(());
+        }
+
+        let Some(lease_owner) = lease_owner else {
+            tracing::warn!("Wallet {} was sent without a lease", wallet.db_record.id);
+            return Ok(());
+        };
+        if !self
+            .operator_wallet_repo
+            .set_nonce(wallet.db_record.id, lease_owner, latest_nonce)
+            .await?
+            || !self
+                .operator_wallet_repo
+                .release_unused(wallet.db_record.id, lease_owner)
+                .await?
+        {
+            tracing::warn!(
+                "Lease of wallet {} was lost before nonce {latest_nonce} was resynced",
+                wallet.db_record.id
+            );
+        }
+        Ok(())
+    }
+
+    /// Called once the transaction of an acquired wallet is broadcasted. Pipelined wallets go
+    /// back to the pool right away and keep the nonce reserved until its receipt is resolved,
+    /// the rest stay leased until then.
+    pub async fn release_sent(&self, wallet: &Wallet, nonce: Option<u64>) -> anyhow::Result<()> {
+        if !self.is_pipelined_chain(wallet.chain_id)? {
+            return Ok(());
+        }
+        let Some(nonce) = nonce else {
+            bail!(
+                "Sent transaction has no nonce, wallet: {}",
+                wallet.db_record.id
+            );
+        };
+
+        self.nonce_reservation_repo
+            .mark_broadcasted(wallet.db_record.id, i64::try_from(nonce)?)
+            .await?;
+        if !self
+            .operator_wallet_repo
+            .release_unused(wallet.db_record.id, &self.lease_owner)
+            .await?
+        {
+            tracing::warn!("Lease of wallet {} was lost", wallet.db_record.id);
+        }
+        Ok(())
+    }
+
+    /// Releases the lease together with every nonce reserved but never broadcasted. A released
+    /// nonce below other in-flight ones is a gap, which the next reservation fills.
     pub async fn release_unused(&self, operator_wallet_id: Uuid) -> anyhow::Result<()> {
-        self.operator_wallet_repo
-            .release_unused(operator_wallet_id)
+        self.nonce_reservation_repo
+            .release_reserved_by_lease(operator_wallet_id, &self.lease_owner)
             .await?;
+        if !self
+            .operator_wallet_repo
+            .release_unused(operator_wallet_id, &self.lease_owner)
+            .await?
+        {
+            tracing::warn!("Lease of wallet {operator_wallet_id} was lost");
+        }
         Ok(())
     }
 
+    pub async fn is_nonce_reserved(
+        &self,
+        operator_wallet_id: Uuid,
+        nonce: i64,
+    ) -> anyhow::Result<bool> {
+        Ok(self
+            .nonce_reservation_repo
+            .select_by_wallet(operator_wallet_id)
+            .await?
+            .iter()
+            .any(|reservation| reservation.nonce == nonce))
+    }
+
+    /// Reserves a gap nonce of a pipelined wallet without leasing it. Returns false if a sender
+    /// already took it.
+    pub async fn reserve_gap_nonce(
+        &self,
+        operator_wallet_id: Uuid,
+        nonce: i64,
+    ) -> anyhow::Result<bool> {
+        self.nonce_reservation_repo
+            .reserve(operator_wallet_id, nonce)
+            .await
+    }
+
+    pub async fn mark_gap_nonce_broadcasted(
+        &self,
+        operator_wallet_id: Uuid,
+        nonce: i64,
+    ) -> anyhow::Result<()> {
+        self.nonce_reservation_repo
+            .mark_broadcasted(operator_wallet_id, nonce)
+            .await
+    }
+
+    pub async fn release_gap_nonce(
+        &self,
+        operator_wallet_id: Uuid,
+        nonce: i64,
+    ) -> anyhow::Result<()> {
+        self.nonce_reservation_repo
+            .release_reserved(operator_wallet_id, Some(nonce))
+            .await
+    }
+
+    fn is_pipelined_chain(&self, chain_id: i64) -> anyhow::Result<bool> {
+        let Some(network) = self.networks_map.get(&chain_id) else {
+            bail!("Network not found for chain_id: {chain_id}");
+        };
+        Ok(is_pipelined(network))
+    }
+
     pub async fn get_by_id(&self, operator_wallet_id: Uuid) -> anyhow::Result<Wallet> {
         let operator_wallet = self
             .operator_wallet_repo
@@ -132,3 +582,9 @@ impl WalletPoolManager {
         Ok(wallet)
     }
 }
+
+/// Networks allowing more than one in-flight transaction per wallet reserve nonces instead of
+/// holding the wallet lease until the receipt.
+pub fn is_pipelined(network: &Network) -> bool {
+    network.max_in_flight_txs_per_wallet > 1
+}
@@ -0,0 +1,59 @@
+use db_types::TxExecutionOutcome;
+use operator_wallet_db::operator_wallets::NonceHistoryEntry;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NonceReconciliation {
+    InSync,
+    /// Transactions of the wallet are still in flight, so nothing can be decided yet.
+    Pending,
+    Resync(u64),
+    ManualReview(String),
+}
+
+/// Decides whether the DB nonce can be moved to the chain nonce. `history` holds the broadcasted
+/// execution attempts of the wallet from the lower of both nonces upwards.
+pub fn reconcile_nonce(
+    db_nonce: u64,
+    latest_nonce: u64,
+    pending_nonce: u64,
+    history: &[NonceHistoryEntry],
+) -> anyhow::Result<NonceReconciliation> {
+    if pending_nonce != latest_nonce || history.iter().any(|entry| entry.outcome.is_none()) {
+        return Ok(NonceReconciliation::Pending);
+    }
+
+    if latest_nonce == db_nonce {
+        return Ok(NonceReconciliation::InSync);
+    }
+
+    if latest_nonce > db_nonce {
+        // Every nonce mined since the DB one has to come from our own execution attempts.
+        let mut known_nonces = Vec::new();
+        for entry in history {
+            known_nonces.push(u64::try_from(entry.nonce_used)?);
+        }
+        if (db_nonce..latest_nonce).all(|nonce| known_nonces.contains(&nonce)) {
+            return Ok(NonceReconciliation::Resync(latest_nonce));
+        }
+
+        return Ok(NonceReconciliation::ManualReview(format!(
+            "Chain nonce {latest_nonce} is ahead of DB nonce {db_nonce} with transactions not sent by the pool"
+        )));
+    }
+
+    // The DB is ahead of the chain, which is only safe to undo if none of the attempts above
+    // the chain nonce was confirmed.
+    if history
+        .iter()
+        .any(|entry| entry.outcome == Some(TxExecutionOutcome::SUCCEED))
+    {
+        return Ok(NonceReconciliation::ManualReview(format!(
+            "DB nonce {db_nonce} is ahead of chain nonce {latest_nonce} with succeeded attempts above the chain nonce"
+        )));
+    }
+
+    Ok(NonceReconciliation::Resync(latest_nonce))
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use crate::reconciliation::{NonceReconciliation, reconcile_nonce};
+    use db_types::TxExecutionOutcome;
+    use operator_wallet_db::operator_wallets::NonceHistoryEntry;
+
+    fn entry(nonce_used: i64, outcome: Option<TxExecutionOutcome>) -> NonceHistoryEntry {
+        NonceHistoryEntry {
+            nonce_used,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn in_sync_when_nonces_match() {
+        let result = reconcile_nonce(5, 5, 5, &[]).unwrap();
+
+        assert_eq!(result, NonceReconciliation::InSync);
+    }
+
+    #[test]
+    fn pending_when_mempool_has_wallet_tx() {
+        let result = reconcile_nonce(5, 5, 6, &[]).unwrap();
+
+        assert_eq!(result, NonceReconciliation::Pending);
+    }
+
+    #[test]
+    fn pending_when_attempt_is_unresolved() {
+        let result = reconcile_nonce(5, 6, 6, &[entry(5, None)]).unwrap();
+
+        assert_eq!(result, NonceReconciliation::Pending);
+    }
+
+    #[test]
+    fn resyncs_when_chain_ahead_with_known_attempts() {
+        let history = [
+            entry(5, Some(TxExecutionOutcome::STUCK)),
+            entry(5, Some(TxExecutionOutcome::SUCCEED)),
+            entry(6, Some(TxExecutionOutcome::FAILED)),
+        ];
+
+        let result = reconcile_nonce(5, 7, 7, &history).unwrap();
+
+        assert_eq!(result, NonceReconciliation::Resync(7));
+    }
+
+    #[test]
+    fn manual_review_when_chain_ahead_with_unknown_txs() {
+        let history = [entry(5, Some(TxExecutionOutcome::SUCCEED))];
+
+        let result = reconcile_nonce(5, 7, 7, &history).unwrap();
+
+        assert!(matches!(result, NonceReconciliation::ManualReview(_)));
+    }
+
+    #[test]
+    fn resyncs_when_db_ahead_without_confirmed_attempts() {
+        let history = [entry(5, Some(TxExecutionOutcome::DROPPED))];
+
+        let result = reconcile_nonce(6, 5, 5, &history).unwrap();
+
+        assert_eq!(result, NonceReconciliation::Resync(5));
+    }
+
+    #[test]
+    fn manual_review_when_db_ahead_of_confirmed_attempts() {
+        let history = [entry(5, Some(TxExecutionOutcome::SUCCEED))];
+
+        let result = reconcile_nonce(6, 5, 5, &history).unwrap();
+
+        assert!(matches!(result, NonceReconciliation::ManualReview(_)));
+    }
+}
@@ -1,9 +1,13 @@
 use alloy::providers::Provider;
-use alloy::{eips::BlockId, primitives::U256};
+use alloy::{
+    eips::BlockId,
+    primitives::{B256, U256},
+};
 use anyhow::bail;
 use network_db::networks::Network;
 use operator_wallet_db::operator_wallets::{KeyType, OperatorWallet};
 use ow_wallet_adapter::{OwWalletConfig, wallet::OwWallet};
+use std::str::FromStr;
 
 pub struct Wallet {
     pub db_record: OperatorWallet,
@@ -81,6 +85,16 @@ impl Wallet {
     }
 
     pub async fn has_enough_balance(&self) -> anyhow::Result<bool> {
+        let balance = self.get_balance().await?;
+
+        if U256::from(self.min_balance) > balance {
+            return Ok(false);
+        } else {
+            return Ok(true);
+        }
+    }
+
+    pub async fn get_balance(&self) -> anyhow::Result<U256> {
         let wallet_address = self.ow_wallet.get_address()?;
         let balance = self
             .ow_wallet
@@ -89,11 +103,7 @@ impl Wallet {
             .block_id(BlockId::latest())
             .await?;
 
-        if U256::from(self.min_balance) > balance {
-            return Ok(false);
-        } else {
-            return Ok(true);
-        }
+        Ok(balance)
     }
 
     pub async fn get_pending_nonce(&self) -> anyhow::Result<u64> {
@@ -121,4 +131,15 @@ impl Wallet {
 
         Ok(nonce)
     }
+
+    /// Whether the transaction with `tx_hash` has a receipt.
+    pub async fn is_mined(&self, tx_hash: &str) -> anyhow::Result<bool> {
+        let receipt = self
+            .ow_wallet
+            .provider
+            .get_transaction_receipt(B256::from_str(tx_hash)?)
+            .await?;
+
+        Ok(receipt.is_some())
+    }
 }